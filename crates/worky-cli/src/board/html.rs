@@ -1,6 +1,71 @@
 //! HTML templates for the kanban board.
 
-pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
+use std::collections::BTreeMap;
+use worky_fs::config::{LabelSettings, WorkflowSettings};
+
+/// Replaced with the JSON array of workflow states when serving the page.
+const STATES_PLACEHOLDER: &str = "__WORKY_STATES__";
+/// Replaced with the JSON map of WIP limits by state when serving the page.
+const WIP_LIMITS_PLACEHOLDER: &str = "__WORKY_WIP_LIMITS__";
+/// Replaced with the JSON map of label colors when serving the page.
+const LABEL_COLORS_PLACEHOLDER: &str = "__WORKY_LABEL_COLORS__";
+
+/// The board page with columns for the workflow's states, their WIP
+/// limits, and tags colored by `labels`.
+pub fn index_html(workflow: &WorkflowSettings, labels: &LabelSettings) -> String {
+    // `<` can't end the script element early once escaped
+    let states = serde_json::to_string(&workflow.states)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace('<', "\\u003c");
+    let wip_limits = serde_json::to_string(&workflow.wip_limits)
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace('<', "\\u003c");
+
+    INDEX_HTML
+        .replace(STATES_PLACEHOLDER, &states)
+        .replace(WIP_LIMITS_PLACEHOLDER, &wip_limits)
+        .replace(LABEL_COLORS_PLACEHOLDER, &label_colors_json(labels))
+}
+
+/// `{label: {background, color}}` for every colored label, lowercased.
+fn label_colors_json(labels: &LabelSettings) -> String {
+    let colors: BTreeMap<String, serde_json::Value> = labels
+        .palette()
+        .into_iter()
+        .map(|(label, rgb)| {
+            let [r, g, b] = rgb;
+            let colors = serde_json::json!({
+                "background": format!("#{r:02x}{g:02x}{b:02x}"),
+                "color": text_color(rgb),
+            });
+            (label, colors)
+        })
+        .collect();
+    serde_json::to_string(&colors)
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace('<', "\\u003c")
+}
+
+/// Black or white, whichever contrasts more with `background` (WCAG
+/// relative luminance).
+fn text_color([r, g, b]: [u8; 3]) -> &'static str {
+    let linear = |channel: u8| {
+        let c = f64::from(channel) / 255.0;
+        if c <= 0.039_28 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let luminance = 0.0722f64.mul_add(
+        linear(b),
+        0.2126f64.mul_add(linear(r), 0.7152 * linear(g)),
+    );
+    // Contrast with black beats contrast with white above this luminance
+    if luminance > 0.179 { "#111827" } else { "#ffffff" }
+}
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
@@ -12,46 +77,18 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
 <body>
     <header>
         <h1>📋 worky Board</h1>
+        <div id="filters">
+            <input id="filter-query" type="search" aria-label="Search"
+                placeholder="Search title, UID, description">
+            <select id="filter-state" aria-label="State"></select>
+            <select id="filter-assignee" aria-label="Assignee"></select>
+            <select id="filter-label" aria-label="Label"></select>
+            <button id="filter-clear" hidden>Clear</button>
+        </div>
         <button id="refresh-btn" onclick="loadItems()">↻ Refresh</button>
     </header>
 
-    <main id="board">
-        <div class="column" data-state="TODO">
-            <div class="column-header">
-                <span class="column-title">📝 TODO</span>
-                <span class="column-count">0</span>
-            </div>
-            <div class="cards"></div>
-        </div>
-        <div class="column" data-state="IN_PROGRESS">
-            <div class="column-header">
-                <span class="column-title">🔄 In Progress</span>
-                <span class="column-count">0</span>
-            </div>
-            <div class="cards"></div>
-        </div>
-        <div class="column" data-state="IN_REVIEW">
-            <div class="column-header">
-                <span class="column-title">👀 In Review</span>
-                <span class="column-count">0</span>
-            </div>
-            <div class="cards"></div>
-        </div>
-        <div class="column" data-state="BLOCKED">
-            <div class="column-header">
-                <span class="column-title">🚫 Blocked</span>
-                <span class="column-count">0</span>
-            </div>
-            <div class="cards"></div>
-        </div>
-        <div class="column" data-state="DONE">
-            <div class="column-header">
-                <span class="column-title">✅ Done</span>
-                <span class="column-count">0</span>
-            </div>
-            <div class="cards"></div>
-        </div>
-    </main>
+    <main id="board"></main>
 
     <div id="modal" class="modal hidden">
         <div class="modal-content">
@@ -61,7 +98,238 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
     </div>
 
     <script>
-        const STATES = ['TODO', 'IN_PROGRESS', 'IN_REVIEW', 'BLOCKED', 'DONE'];
+        // Workflow states from the workspace config, in column order
+        const STATES = __WORKY_STATES__;
+        // Most cards allowed per column, by state; columns without one are unlimited
+        const WIP_LIMITS = __WORKY_WIP_LIMITS__;
+        const OTHER = '__OTHER__';
+        const COLUMNS = [...STATES, OTHER];
+        const COLUMN_TITLES = {
+            TODO: '📝 TODO',
+            IN_PROGRESS: '🔄 In Progress',
+            IN_REVIEW: '👀 In Review',
+            BLOCKED: '🚫 Blocked',
+            DONE: '✅ Done',
+            [OTHER]: '❔ Other',
+        };
+        const POLL_INTERVAL_MS = 30000;
+        // Tag colors from `labels.colors` and the built-in palette, by
+        // lowercased label; others keep the default style
+        const LABEL_COLORS = __WORKY_LABEL_COLORS__;
+
+        // Current items keyed by UID, patched in place by live updates
+        let itemsByUid = {};
+        let pollTimer = null;
+
+        // Column elements keyed by state
+        const columns = {};
+
+        // Filter choices by name, kept in the page URL under the same
+        // parameters `/api/items` accepts so a filtered view can be shared
+        const FILTERS = ['query', 'state', 'assignee', 'label'];
+        const UNASSIGNED = '__UNASSIGNED__';
+        const filters = {};
+
+        function buildFilters() {
+            const params = new URLSearchParams(location.search);
+            FILTERS.forEach(name => filters[name] = params.get(name) ?? '');
+            if (params.get('no_assignee') === 'true') filters.assignee = UNASSIGNED;
+
+            setOptions('state', 'All states', STATES.map(s => [s, s.replace(/_/g, ' ')]));
+            FILTERS.forEach(name => {
+                const control = document.getElementById('filter-' + name);
+                control.value = filters[name];
+                control.addEventListener('input', () => {
+                    filters[name] = control.value;
+                    applyFilters();
+                });
+            });
+
+            document.getElementById('filter-clear').onclick = () => {
+                FILTERS.forEach(name => {
+                    filters[name] = '';
+                    document.getElementById('filter-' + name).value = '';
+                });
+                applyFilters();
+            };
+        }
+
+        function applyFilters() {
+            const params = new URLSearchParams();
+            FILTERS.forEach(name => {
+                if (name === 'assignee' && filters.assignee === UNASSIGNED) {
+                    params.set('no_assignee', 'true');
+                } else if (filters[name]) {
+                    params.set(name, filters[name]);
+                }
+            });
+            const search = params.toString();
+            history.replaceState(null, '', search ? '?' + search : location.pathname);
+
+            renderBoard(Object.values(itemsByUid));
+        }
+
+        function filtering() {
+            return FILTERS.some(name => filters[name].trim() !== '');
+        }
+
+        function matchesFilters(item) {
+            const text = filters.query.trim().toLowerCase();
+            if (text) {
+                const description = item.fields?.description ?? '';
+                const haystack = [item.title, item.uid, String(description)].join('\n');
+                if (!haystack.toLowerCase().includes(text)) return false;
+            }
+            if (filters.state && item.state !== filters.state) return false;
+            if (filters.assignee === UNASSIGNED) {
+                if (item.assignee) return false;
+            } else if (filters.assignee && item.assignee !== filters.assignee) {
+                return false;
+            }
+            return !filters.label || (item.labels ?? []).includes(filters.label);
+        }
+
+        // Offer the assignees and labels currently in use
+        function updateFilterOptions(items) {
+            const assignees = [...new Set(items.map(item => item.assignee).filter(Boolean))].sort();
+            const labels = [...new Set(items.flatMap(item => item.labels ?? []))].sort();
+            setOptions('assignee', 'Anyone', [
+                [UNASSIGNED, 'Unassigned'],
+                ...assignees.map(a => [a, a]),
+            ]);
+            setOptions('label', 'Any label', labels.map(l => [l, l]));
+        }
+
+        // Replace a filter's options, keeping the current choice even if no item has it
+        function setOptions(name, anyText, options) {
+            const select = document.getElementById('filter-' + name);
+            const current = filters[name];
+            if (current && !options.some(([value]) => value === current)) {
+                options.push([current, current]);
+            }
+            select.replaceChildren(
+                new Option(anyText, ''),
+                ...options.map(([value, text]) => new Option(text, value)),
+            );
+            select.value = current;
+        }
+
+        function buildColumns() {
+            const board = document.getElementById('board');
+
+            COLUMNS.forEach(state => {
+                const el = document.createElement('div');
+                el.className = 'column';
+
+                const header = document.createElement('div');
+                header.className = 'column-header';
+                const title = document.createElement('span');
+                title.className = 'column-title';
+                title.textContent = COLUMN_TITLES[state] ?? state.replace(/_/g, ' ');
+                const count = document.createElement('span');
+                count.className = 'column-count';
+                count.textContent = '0';
+                header.append(title, count);
+
+                const cards = document.createElement('div');
+                cards.className = 'cards';
+
+                el.append(header, cards);
+                // Cards can't be created outside the workflow
+                if (state !== OTHER) el.appendChild(buildAddForm(state));
+                board.appendChild(el);
+                columns[state] = { el, count, cards };
+            });
+        }
+
+        function buildAddForm(state) {
+            const footer = document.createElement('div');
+            footer.className = 'add-card';
+
+            const open = document.createElement('button');
+            open.className = 'add-card-open';
+            open.textContent = '+ Add card';
+
+            const form = document.createElement('form');
+            form.hidden = true;
+
+            const input = document.createElement('input');
+            input.type = 'text';
+            input.placeholder = 'Title';
+
+            const error = document.createElement('div');
+            error.className = 'add-card-error';
+
+            const submit = document.createElement('button');
+            submit.type = 'submit';
+            submit.textContent = 'Add';
+            const cancel = document.createElement('button');
+            cancel.type = 'button';
+            cancel.textContent = 'Cancel';
+            const actions = document.createElement('div');
+            actions.className = 'add-card-actions';
+            actions.append(submit, cancel);
+
+            const close = () => {
+                form.hidden = true;
+                open.hidden = false;
+                input.value = '';
+                error.textContent = '';
+            };
+
+            open.onclick = () => {
+                form.hidden = false;
+                open.hidden = true;
+                input.focus();
+            };
+            cancel.onclick = close;
+            input.addEventListener('keydown', e => {
+                if (e.key !== 'Escape') return;
+                // Don't also close the detail modal
+                e.stopPropagation();
+                close();
+            });
+
+            form.addEventListener('submit', async e => {
+                e.preventDefault();
+                const title = input.value.trim();
+                if (!title) {
+                    error.textContent = 'Enter a title';
+                    return;
+                }
+
+                submit.disabled = true;
+                const message = await createItem(title, state);
+                submit.disabled = false;
+                if (message) {
+                    error.textContent = message;
+                    input.focus();
+                    return;
+                }
+
+                close();
+                loadItems();
+            });
+
+            form.append(input, error, actions);
+            footer.append(open, form);
+            return footer;
+        }
+
+        // Returns an error message, or null once the item exists
+        async function createItem(title, state) {
+            try {
+                const response = await fetch('/api/items', {
+                    method: 'POST',
+                    headers: { 'Content-Type': 'application/json' },
+                    body: JSON.stringify({ title, state }),
+                });
+                const data = await response.json();
+                return data.error ?? null;
+            } catch (e) {
+                return 'Failed to create item: ' + e.message;
+            }
+        }
 
         async function loadItems() {
             try {
@@ -73,48 +341,194 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
                     return;
                 }
 
+                itemsByUid = {};
+                data.items.forEach(item => itemsByUid[item.uid] = item);
                 renderBoard(data.items);
             } catch (e) {
                 alert('Failed to load items: ' + e.message);
             }
         }
 
+        async function reloadItem(uid, withDependents = true) {
+            try {
+                const previous = itemsByUid[uid];
+                const response = await fetch('/api/items/' + encodeURIComponent(uid));
+                if (response.status === 404) {
+                    delete itemsByUid[uid];
+                } else {
+                    const item = await response.json();
+                    if (item.error) return;
+                    itemsByUid[uid] = item;
+                }
+                renderBoard(Object.values(itemsByUid));
+
+                // Cards blocked by this one may have been unblocked, or blocked again
+                const current = itemsByUid[uid];
+                if (withDependents && (!current || !previous || current.state !== previous.state)) {
+                    Object.values(itemsByUid)
+                        .filter(other => (other.blocked_by || []).includes(uid))
+                        .forEach(other => reloadItem(other.uid, false));
+                }
+            } catch (e) {
+                console.warn('Failed to reload ' + uid, e);
+            }
+        }
+
+        function startPolling() {
+            if (pollTimer === null) {
+                pollTimer = setInterval(loadItems, POLL_INTERVAL_MS);
+            }
+        }
+
+        function stopPolling() {
+            if (pollTimer !== null) {
+                clearInterval(pollTimer);
+                pollTimer = null;
+            }
+        }
+
+        function subscribe() {
+            if (!window.EventSource) {
+                startPolling();
+                return;
+            }
+
+            const source = new EventSource('/api/events');
+            source.addEventListener('item', e => reloadItem(e.data));
+            source.onopen = () => {
+                // Catch up on anything missed while disconnected
+                if (pollTimer !== null) loadItems();
+                stopPolling();
+            };
+            source.onerror = () => startPolling();
+        }
+
         function renderBoard(items) {
-            // Clear all columns
-            STATES.forEach(state => {
-                const column = document.querySelector(`[data-state="${state}"] .cards`);
-                column.innerHTML = '';
-            });
+            updateFilterOptions(items);
+            const filtered = filtering();
+            document.getElementById('filter-clear').hidden = !filtered;
 
-            // Group items by state
+            // Group items by state; states outside the workflow go to Other.
+            // WIP limits count every item, shown or filtered out
             const grouped = {};
-            STATES.forEach(s => grouped[s] = []);
+            const totals = {};
+            COLUMNS.forEach(s => {
+                grouped[s] = [];
+                totals[s] = 0;
+            });
 
             items.forEach(item => {
-                const state = STATES.includes(item.state) ? item.state : 'TODO';
-                grouped[state].push(item);
+                const state = STATES.includes(item.state) ? item.state : OTHER;
+                totals[state] += 1;
+                if (matchesFilters(item)) grouped[state].push(item);
             });
 
+            // Manually ranked cards first, the rest keep their update order
+            COLUMNS.forEach(state => grouped[state].sort(compareRank));
+
             // Render items
-            STATES.forEach(state => {
-                const column = document.querySelector(`[data-state="${state}"] .cards`);
-                const countEl = document.querySelector(`[data-state="${state}"] .column-count`);
-                countEl.textContent = grouped[state].length;
+            COLUMNS.forEach(state => {
+                const column = columns[state];
+                column.cards.innerHTML = '';
+                const shown = grouped[state].length;
+                const count = totals[state];
+                const limit = wipLimit(state);
+                if (filtered) {
+                    column.count.textContent = `${shown} of ${count}`;
+                } else {
+                    column.count.textContent = limit === undefined ? count : `${count}/${limit}`;
+                }
+                column.count.title = limit === undefined ? '' : `WIP limit: ${limit}`;
+                column.count.classList.toggle('over-limit', limit !== undefined && count > limit);
 
                 grouped[state].forEach(item => {
-                    column.appendChild(createCard(item));
+                    column.cards.appendChild(createCard(item));
+                });
+            });
+
+            columns[OTHER].el.hidden = grouped[OTHER].length === 0;
+        }
+
+        function compareRank(a, b) {
+            const ra = a.rank ?? null;
+            const rb = b.rank ?? null;
+            if (ra === null && rb === null) return 0;
+            if (ra === null) return 1;
+            if (rb === null) return -1;
+            return ra - rb;
+        }
+
+        let draggedUid = null;
+
+        async function moveItem(uid, placement) {
+            try {
+                const response = await fetch('/api/items/' + encodeURIComponent(uid) + '/move', {
+                    method: 'POST',
+                    headers: { 'Content-Type': 'application/json' },
+                    body: JSON.stringify(placement),
                 });
+                const data = await response.json();
+                if (data.error) {
+                    alert(data.error);
+                    return;
+                }
+                // Renumbering may touch the whole column
+                loadItems();
+            } catch (e) {
+                alert('Failed to move item: ' + e.message);
+            }
+        }
+
+        function enableDrag(card, item) {
+            card.draggable = true;
+
+            card.addEventListener('dragstart', e => {
+                draggedUid = item.uid;
+                e.dataTransfer.effectAllowed = 'move';
+                card.classList.add('dragging');
+            });
+            card.addEventListener('dragend', () => {
+                draggedUid = null;
+                card.classList.remove('dragging');
+            });
+
+            card.addEventListener('dragover', e => {
+                const dragged = itemsByUid[draggedUid];
+                // Reordering only happens within a column
+                if (!dragged || dragged.uid === item.uid || dragged.state !== item.state) return;
+                e.preventDefault();
+                card.classList.add('drop-target');
+            });
+            card.addEventListener('dragleave', () => card.classList.remove('drop-target'));
+
+            card.addEventListener('drop', e => {
+                e.preventDefault();
+                card.classList.remove('drop-target');
+                if (!draggedUid || draggedUid === item.uid) return;
+
+                const box = card.getBoundingClientRect();
+                const placement = e.clientY < box.top + box.height / 2
+                    ? { before: item.uid }
+                    : { after: item.uid };
+                moveItem(draggedUid, placement);
             });
         }
 
         function createCard(item) {
             const card = document.createElement('div');
-            card.className = 'card';
+            card.className = item.blocked ? 'card blocked' : 'card';
             card.onclick = () => showDetail(item);
+            enableDrag(card, item);
 
             const title = document.createElement('div');
             title.className = 'card-title';
-            title.textContent = item.title;
+            if (item.priority) {
+                const pip = document.createElement('span');
+                pip.className = 'priority-pip priority-' + item.priority;
+                pip.title = 'Priority: ' + item.priority;
+                title.appendChild(pip);
+            }
+            title.appendChild(document.createTextNode(item.title));
             card.appendChild(title);
 
             const uid = document.createElement('div');
@@ -122,6 +536,13 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
             uid.textContent = item.uid;
             card.appendChild(uid);
 
+            if (item.blocked) {
+                const blocked = document.createElement('div');
+                blocked.className = 'card-blocked';
+                blocked.textContent = '⛔ Blocked by ' + item.blocked_by.join(', ');
+                card.appendChild(blocked);
+            }
+
             if (item.assignee) {
                 const assignee = document.createElement('div');
                 assignee.className = 'card-assignee';
@@ -136,6 +557,11 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
                     const tag = document.createElement('span');
                     tag.className = 'label';
                     tag.textContent = label;
+                    const colors = LABEL_COLORS[label.toLowerCase()];
+                    if (colors) {
+                        tag.style.background = colors.background;
+                        tag.style.color = colors.color;
+                    }
                     labels.appendChild(tag);
                 });
                 card.appendChild(labels);
@@ -163,12 +589,20 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
                 </div>
             `;
 
+            if (item.priority) {
+                html += `<div class="detail-row"><strong>Priority:</strong> <span class="priority-pip priority-${item.priority}"></span>${item.priority}</div>`;
+            }
+
             if (item.assignee) {
                 html += `<div class="detail-row"><strong>Assignee:</strong> ${escapeHtml(item.assignee)}</div>`;
             }
 
+            if (item.blocked_by && item.blocked_by.length > 0) {
+                html += `<div class="detail-row"><strong>Blocked by:</strong> ${item.blocked_by.map(b => `<code>${escapeHtml(b)}</code>`).join(' ')}</div>`;
+            }
+
             if (item.labels && item.labels.length > 0) {
-                html += `<div class="detail-row"><strong>Labels:</strong> ${item.labels.map(l => `<span class="label">${escapeHtml(l)}</span>`).join(' ')}</div>`;
+                html += `<div class="detail-row"><strong>Labels:</strong> ${item.labels.map(l => `<span class="label"${labelStyle(l)}>${escapeHtml(l)}</span>`).join(' ')}</div>`;
             }
 
             html += `
@@ -197,6 +631,24 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
                 }
             }
 
+            if (item.checklist && item.checklist.length > 0) {
+                const done = item.checklist.filter(c => c.done).length;
+                html += `<h3>Checklist (${done}/${item.checklist.length})</h3><ul class="checklist">`;
+                item.checklist.forEach(c => {
+                    html += `<li class="${c.done ? 'done' : ''}">${c.done ? '☑' : '☐'} ${escapeHtml(c.text)}</li>`;
+                });
+                html += `</ul>`;
+            }
+
+            if (item.artifacts && item.artifacts.length > 0) {
+                html += `<h3>Artifacts (${item.artifacts.length})</h3><ul class="artifacts">`;
+                item.artifacts.forEach(name => {
+                    const href = `/artifacts/${encodeURIComponent(item.uid)}/${encodeURIComponent(name)}`;
+                    html += `<li><a href="${href}" download>${escapeHtml(name)}</a></li>`;
+                });
+                html += `</ul>`;
+            }
+
             if (item.comments && item.comments.length > 0) {
                 html += `<h3>Comments (${item.comments.length})</h3><div class="comments">`;
                 item.comments.forEach(c => {
@@ -204,7 +656,7 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
                         <div class="comment">
                             <div class="comment-header">
                                 <span class="comment-author">${escapeHtml(c.actor || 'user')}</span>
-                                <span class="comment-time">${c.timestamp}</span>
+                                <span class="comment-time">${c.timestamp}${c.edited ? ' (edited)' : ''}</span>
                             </div>
                             <div class="comment-body markdown-content">${renderMarkdown(c.message)}</div>
                         </div>
@@ -221,6 +673,16 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
             document.getElementById('modal').classList.add('hidden');
         }
 
+        function wipLimit(state) {
+            const key = Object.keys(WIP_LIMITS).find(s => s.toUpperCase() === state.toUpperCase());
+            return key === undefined ? undefined : WIP_LIMITS[key];
+        }
+
+        function labelStyle(label) {
+            const colors = LABEL_COLORS[label.toLowerCase()];
+            return colors ? ` style="background: ${colors.background}; color: ${colors.color}"` : '';
+        }
+
         function escapeHtml(text) {
             const div = document.createElement('div');
             div.textContent = text;
@@ -249,17 +711,17 @@ pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
             if (e.target.id === 'modal') closeModal();
         });
 
-        // Load items on page load
+        // Load items on page load, then follow live updates
+        buildColumns();
+        buildFilters();
         loadItems();
-
-        // Auto-refresh every 30 seconds
-        setInterval(loadItems, 30000);
+        subscribe();
     </script>
 </body>
 </html>
 "#;
 
-pub const STYLES_CSS: &str = r#"
+pub const STYLES_CSS: &str = r"
 * {
     margin: 0;
     padding: 0;
@@ -302,6 +764,48 @@ header h1 {
     background: #1a4a7a;
 }
 
+#filters {
+    display: flex;
+    gap: 0.5rem;
+    flex: 1;
+    justify-content: flex-end;
+    margin: 0 1rem;
+}
+
+#filters input,
+#filters select {
+    background: #1a1a2e;
+    color: #eee;
+    border: 1px solid #0f3460;
+    border-radius: 4px;
+    padding: 0.4rem 0.5rem;
+    font-size: 0.85rem;
+}
+
+#filters input {
+    width: 16rem;
+}
+
+#filters input:focus,
+#filters select:focus {
+    outline: none;
+    border-color: #4ecca3;
+}
+
+#filter-clear {
+    background: none;
+    color: #94a3b8;
+    border: 1px solid #0f3460;
+    border-radius: 4px;
+    padding: 0.4rem 0.8rem;
+    cursor: pointer;
+    font-size: 0.85rem;
+}
+
+#filter-clear:hover {
+    color: #eee;
+}
+
 main {
     display: flex;
     gap: 1rem;
@@ -320,6 +824,11 @@ main {
     flex-direction: column;
 }
 
+/* Display rules on .column would otherwise override [hidden] */
+.column[hidden] {
+    display: none;
+}
+
 .column-header {
     padding: 1rem;
     border-bottom: 1px solid #0f3460;
@@ -341,12 +850,78 @@ main {
     font-size: 0.8rem;
 }
 
+.column-count.over-limit {
+    background: #7f1d1d;
+    color: #fecaca;
+}
+
 .cards {
     padding: 0.5rem;
     flex: 1;
     overflow-y: auto;
 }
 
+.add-card {
+    padding: 0.5rem;
+    border-top: 1px solid #0f3460;
+}
+
+/* Display rules below would otherwise override [hidden] */
+.add-card [hidden] {
+    display: none;
+}
+
+.add-card button {
+    background: #0f3460;
+    color: #eee;
+    border: none;
+    padding: 0.4rem 0.8rem;
+    border-radius: 4px;
+    cursor: pointer;
+    font-size: 0.85rem;
+}
+
+.add-card button:hover {
+    background: #1a4a7a;
+}
+
+.add-card button:disabled {
+    opacity: 0.6;
+    cursor: default;
+}
+
+.add-card-open {
+    width: 100%;
+    text-align: left;
+}
+
+.add-card input {
+    width: 100%;
+    background: #1a1a2e;
+    color: #eee;
+    border: 1px solid #0f3460;
+    border-radius: 4px;
+    padding: 0.5rem;
+    font-size: 0.9rem;
+}
+
+.add-card input:focus {
+    outline: none;
+    border-color: #4ecca3;
+}
+
+.add-card-error {
+    color: #f87171;
+    font-size: 0.75rem;
+    margin: 0.25rem 0;
+    min-height: 1rem;
+}
+
+.add-card-actions {
+    display: flex;
+    gap: 0.5rem;
+}
+
 .card {
     background: #1a1a2e;
     border: 1px solid #0f3460;
@@ -363,12 +938,35 @@ main {
     border-color: #e94560;
 }
 
+.card.dragging {
+    opacity: 0.4;
+}
+
+.card.drop-target {
+    border-color: #4ecca3;
+    border-style: dashed;
+}
+
 .card-title {
     font-weight: 500;
     margin-bottom: 0.5rem;
     line-height: 1.3;
 }
 
+.priority-pip {
+    display: inline-block;
+    width: 0.6rem;
+    height: 0.6rem;
+    border-radius: 50%;
+    margin-right: 0.4rem;
+    vertical-align: middle;
+}
+
+.priority-low { background: #64748b; }
+.priority-medium { background: #3b82f6; }
+.priority-high { background: #f59e0b; }
+.priority-critical { background: #ef4444; }
+
 .card-uid {
     font-size: 0.75rem;
     color: #64748b;
@@ -376,6 +974,17 @@ main {
     margin-bottom: 0.5rem;
 }
 
+.card.blocked {
+    border-left: 3px solid #ef4444;
+    opacity: 0.85;
+}
+
+.card-blocked {
+    font-size: 0.75rem;
+    color: #f87171;
+    margin-bottom: 0.5rem;
+}
+
 .card-assignee {
     font-size: 0.8rem;
     color: #94a3b8;
@@ -486,6 +1095,37 @@ main {
 .state-blocked { background: #ef4444; color: white; }
 .state-done { background: #22c55e; color: white; }
 
+.checklist {
+    list-style: none;
+    padding: 0;
+    margin: 0 0 1rem;
+}
+
+.checklist li {
+    padding: 0.25rem 0;
+    font-size: 0.9rem;
+}
+
+.checklist li.done {
+    color: #64748b;
+    text-decoration: line-through;
+}
+
+.artifacts {
+    list-style: none;
+    padding: 0;
+    margin: 0 0 1rem;
+}
+
+.artifacts li {
+    padding: 0.25rem 0;
+    font-size: 0.9rem;
+}
+
+.artifacts a {
+    color: #60a5fa;
+}
+
 .comments {
     display: flex;
     flex-direction: column;
@@ -640,4 +1280,4 @@ main {
 ::-webkit-scrollbar-thumb:hover {
     background: #1a4a7a;
 }
-"#;
+";
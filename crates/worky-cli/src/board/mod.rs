@@ -2,23 +2,40 @@
 
 mod html;
 
+use crate::output;
 use anyhow::{Context, Result};
 use axum::{
-    extract::State,
-    http::header,
-    response::{Html, IntoResponse, Json},
-    routing::get,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
+    routing::{get, post},
     Router,
 };
-use std::net::SocketAddr;
+use notify::{RecursiveMode, Watcher};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::net::TcpListener;
-use tracing::info;
-use worky_fs::Workspace;
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tracing::{debug, info, warn};
+use worky_core::WorkItem;
+use worky_fs::{
+    workspace::{FilterQuery, ItemFilter, NewItemFields, Placement},
+    FsError, Workspace, WorkspaceConfig,
+};
+
+/// Timestamp pattern on cards unless `display.time_format` is set.
+const BOARD_TIME: &str = "%Y-%m-%d %H:%M";
 
 struct AppState {
     workspace_path: PathBuf,
+    /// UIDs of items whose files changed on disk.
+    changes: broadcast::Sender<String>,
 }
 
 /// Serve the kanban board on the specified host and port.
@@ -31,31 +48,101 @@ pub fn serve(workspace_path: &std::path::Path, host: &str, port: u16) -> Result<
 
 async fn serve_async(workspace_path: &std::path::Path, host: &str, port: u16) -> Result<()> {
     // Verify workspace exists
-    let _ = Workspace::open(workspace_path).context("Failed to open workspace")?;
+    let ws = Workspace::open(workspace_path).context("Failed to open workspace")?;
+
+    let (changes, _) = broadcast::channel(64);
+
+    // Keep the watcher alive for the lifetime of the server
+    let _watcher = watch_items(&ws.items_dir(), changes.clone())?;
 
     let state = Arc::new(AppState {
         workspace_path: workspace_path.to_path_buf(),
+        changes,
     });
 
     let app = Router::new()
         .route("/", get(index_handler))
-        .route("/api/items", get(items_handler))
+        .route("/api/items", get(items_handler).post(create_handler))
+        .route("/api/items/{uid}", get(item_handler))
+        .route("/api/items/{uid}/move", post(move_handler))
+        .route("/api/events", get(events_handler))
+        .route("/artifacts/{uid}/{name}", get(artifact_handler))
         .route("/styles.css", get(styles_handler))
+        .layer(worky_toolserver::compression_layer())
         .with_state(state);
 
-    let addr: SocketAddr = format!("{host}:{port}").parse()?;
-    let listener = TcpListener::bind(addr).await?;
+    let listener = worky_toolserver::bind(host, port).await?;
+    let addr = listener.local_addr()?;
 
     info!("Kanban board available at http://{}", addr);
     println!("🎯 Kanban board running at http://{addr}");
     println!("   Press Ctrl+C to stop");
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(worky_toolserver::shutdown_signal())
+        .await?;
+
+    println!("Kanban board stopped");
     Ok(())
 }
 
-async fn index_handler() -> Html<&'static str> {
-    Html(html::INDEX_HTML)
+/// Watch the items directory and broadcast the UID of every item with a
+/// changed file: its metadata, its event log (new comments), or artifacts.
+fn watch_items(
+    items_dir: &std::path::Path,
+    changes: broadcast::Sender<String>,
+) -> Result<notify::RecommendedWatcher> {
+    let root = items_dir.to_path_buf();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let event = match res {
+            Ok(event) => event,
+            Err(e) => {
+                warn!(error = %e, "File watcher error");
+                return;
+            }
+        };
+
+        // Reads (including our own list/get calls) are not changes
+        if event.kind.is_access() {
+            return;
+        }
+
+        for path in &event.paths {
+            // Only paths inside an item's directory, not the directory itself
+            let Ok(relative) = path.strip_prefix(&root) else {
+                continue;
+            };
+            let mut components = relative.components();
+            let (Some(slug), Some(_)) = (components.next(), components.next()) else {
+                continue;
+            };
+            let slug = slug.as_os_str();
+
+            let uid = format!("fs:{}", slug.to_string_lossy());
+            debug!(uid = %uid, "Item changed on disk");
+            // No subscribers is fine; the board may not be open
+            let _ = changes.send(uid);
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    watcher
+        .watch(items_dir, RecursiveMode::Recursive)
+        .context("Failed to watch items directory")?;
+
+    Ok(watcher)
+}
+
+async fn index_handler(State(state): State<Arc<AppState>>) -> Html<String> {
+    let config = match Workspace::open(&state.workspace_path) {
+        Ok(ws) => ws.config().clone(),
+        Err(e) => {
+            warn!(error = %e, "Failed to read workspace config; using the defaults");
+            WorkspaceConfig::default()
+        }
+    };
+
+    Html(html::index_html(&config.workflow, &config.labels))
 }
 
 async fn styles_handler() -> impl IntoResponse {
@@ -65,7 +152,30 @@ async fn styles_handler() -> impl IntoResponse {
     )
 }
 
-async fn items_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+async fn events_handler(
+    State(state): State<Arc<AppState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Lagged receivers just skip missed notifications
+    let stream = BroadcastStream::new(state.changes.subscribe())
+        .filter_map(Result::ok)
+        .map(|uid| Ok(Event::default().event("item").data(uid)));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Items on the board, narrowed by the same filters as the tool server's
+/// `/search` (e.g. `?state=TODO&label=bug&query=login`).
+async fn items_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FilterQuery>,
+) -> impl IntoResponse {
+    let filter = match ItemFilter::try_from(query) {
+        Ok(filter) => filter,
+        Err(e) => {
+            return Json(serde_json::json!({ "error": format!("Invalid filter: {e}") }));
+        }
+    };
+
     let ws = match Workspace::open(&state.workspace_path) {
         Ok(ws) => ws,
         Err(e) => {
@@ -84,42 +194,253 @@ async fn items_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse
         }
     };
 
-    // Group items by state and include comments
+    let states: HashMap<String, String> = items
+        .iter()
+        .map(|item| (item.uid.clone(), item.state.clone()))
+        .collect();
+
+    // Blockers are looked up among all items, filtered out or not
     let items_with_comments: Vec<serde_json::Value> = items
         .iter()
-        .map(|item| {
-            let comments = ws
-                .read_events(&item.uid, None)
-                .unwrap_or_default()
-                .into_iter()
-                .filter(|e| matches!(e.event_type, worky_core::EventType::CommentAdded))
-                .map(|e| {
-                    let message = if let worky_core::EventPayload::Comment(p) = &e.payload {
-                        p.message.clone()
-                    } else {
-                        String::new()
-                    };
-                    serde_json::json!({
-                        "timestamp": e.timestamp.format("%Y-%m-%d %H:%M").to_string(),
-                        "actor": e.actor,
-                        "message": message
-                    })
-                })
-                .collect::<Vec<_>>();
+        .filter(|item| filter.matches(item))
+        .map(|item| item_json(&ws, item, &states))
+        .collect();
 
+    Json(serde_json::json!({ "items": items_with_comments }))
+}
+
+async fn item_handler(
+    State(state): State<Arc<AppState>>,
+    Path(uid): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let ws = match Workspace::open(&state.workspace_path) {
+        Ok(ws) => ws,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": format!("Failed to open workspace: {e}")
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let item = match ws.get_item(&uid) {
+        Ok(item) => item,
+        Err(e) => {
+            // The board drops the card on 404, so only when it's really gone
+            let status = match e {
+                FsError::ItemNotFound(_) => StatusCode::NOT_FOUND,
+                FsError::InvalidUid(_) | FsError::AmbiguousUid { .. } => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            return (
+                status,
+                Json(serde_json::json!({
+                    "error": format!("Failed to get item: {e}")
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let states: HashMap<String, String> = item
+        .blocked_by
+        .iter()
+        .filter_map(|b| ws.get_linked_item(b).ok())
+        .map(|blocker| (blocker.uid, blocker.state))
+        .collect();
+
+    // The browser revalidates with If-None-Match, so unchanged items come back as 304
+    worky_toolserver::conditional_json(&headers, &item_json(&ws, &item, &states))
+}
+
+async fn artifact_handler(
+    State(state): State<Arc<AppState>>,
+    Path((uid, name)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let result = Workspace::open(&state.workspace_path)
+        .and_then(|ws| ws.artifact_path(&uid, &name))
+        .and_then(|path| Ok(std::fs::read(path)?));
+
+    match result {
+        Ok(data) => {
+            // Header values must be visible ASCII
+            let filename: String = name
+                .chars()
+                .map(|c| if c.is_ascii_graphic() && c != '"' { c } else { '_' })
+                .collect();
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{filename}\""),
+                    ),
+                ],
+                data,
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("Failed to get artifact: {e}") })),
+        )
+            .into_response(),
+    }
+}
+
+/// A card added from a column's "+ Add card" form.
+#[derive(Deserialize)]
+struct CreateRequest {
+    title: String,
+    /// The column's state.
+    state: String,
+}
+
+async fn create_handler(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateRequest>,
+) -> impl IntoResponse {
+    let title = req.title.trim();
+    if title.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "Title must not be empty" })),
+        );
+    }
+
+    let ws = match Workspace::open(&state.workspace_path) {
+        Ok(ws) => ws,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": format!("Failed to open workspace: {e}")
+                })),
+            );
+        }
+    };
+
+    if !ws.config().workflow.states.contains(&req.state) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!("'{}' is not a board column", req.state)
+            })),
+        );
+    }
+
+    let fields = NewItemFields {
+        state: Some(req.state),
+        ..NewItemFields::default()
+    };
+
+    match ws.create_item_with(title, &fields) {
+        Ok(item) => (
+            StatusCode::CREATED,
+            Json(item_json(&ws, &item, &HashMap::new())),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": format!("Failed to create item: {e}") })),
+        ),
+    }
+}
+
+/// Drop target for drag-and-drop reordering: exactly one of `before`/`after`.
+#[derive(Deserialize)]
+struct MoveRequest {
+    before: Option<String>,
+    after: Option<String>,
+}
+
+async fn move_handler(
+    State(state): State<Arc<AppState>>,
+    Path(uid): Path<String>,
+    Json(req): Json<MoveRequest>,
+) -> impl IntoResponse {
+    let placement = match (req.before, req.after) {
+        (Some(before), None) => Placement::Before(before),
+        (None, Some(after)) => Placement::After(after),
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "pass exactly one of before or after" })),
+            );
+        }
+    };
+
+    let result = Workspace::open(&state.workspace_path)
+        .and_then(|ws| ws.set_rank(&uid, &placement));
+
+    match result {
+        Ok(item) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "uid": item.uid, "rank": item.rank })),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": format!("Failed to move item: {e}") })),
+        ),
+    }
+}
+
+/// Build the board's JSON view of an item, given the known states of other items.
+fn item_json(
+    ws: &Workspace,
+    item: &WorkItem,
+    states: &HashMap<String, String>,
+) -> serde_json::Value {
+    let comments = ws
+        .comments(&item.uid)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| {
+            let (message, edited) = if let worky_core::EventPayload::Comment(p) = &e.payload {
+                (p.message.clone(), p.replaces.is_some())
+            } else {
+                (String::new(), false)
+            };
             serde_json::json!({
-                "uid": item.uid,
-                "title": item.title,
-                "state": item.state,
-                "assignee": item.assignee,
-                "labels": item.labels,
-                "created_at": item.created_at.format("%Y-%m-%d %H:%M").to_string(),
-                "updated_at": item.updated_at.format("%Y-%m-%d %H:%M").to_string(),
-                "fields": item.fields,
-                "comments": comments
+                "timestamp": output::time_format().format_or(&e.timestamp, BOARD_TIME),
+                "actor": e.actor,
+                "message": message,
+                "edited": edited
             })
         })
+        .collect::<Vec<_>>();
+
+    let artifacts: Vec<String> = ws
+        .list_artifacts(&item.uid)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| a.name)
         .collect();
 
-    Json(serde_json::json!({ "items": items_with_comments }))
+    // Blockers that no longer exist don't count
+    let blocked = item.blocked_by.iter().any(|b| {
+        states.get(b).is_some_and(|s| !ws.is_terminal(s))
+    });
+
+    serde_json::json!({
+        "uid": item.uid,
+        "title": item.title,
+        "state": item.state,
+        "assignee": item.assignee,
+        "priority": item.priority,
+        "rank": item.rank,
+        "labels": item.labels,
+        "created_at": output::time_format().format_or(&item.created_at, BOARD_TIME),
+        "updated_at": output::time_format().format_or(&item.updated_at, BOARD_TIME),
+        "fields": item.fields,
+        "blocked_by": item.blocked_by,
+        "blocked": blocked,
+        "checklist": item.checklist,
+        "artifacts": artifacts,
+        "comments": comments
+    })
 }
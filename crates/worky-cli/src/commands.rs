@@ -1,242 +1,1215 @@
 //! CLI command implementations.
 
 use crate::interactive;
-use crate::output::{self, OutputFormat, WorkItemSummary};
-use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
+use crate::output::{
+    self, BlameEntry, ConfigEntry, CountEntry, FeedEntry, ItemCount, OutputFormat,
+    SavedFilterEntry, TransitionResult, WorkItemSummary,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
 use console::style;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, IsTerminal, Read, Write};
 use std::path::Path;
-use worky_core::SetOperation;
-use worky_fs::{workspace::ItemFilter, Workspace};
+use worky_core::{EventType, PatchOp, SetOperation, WorkItem, Workflow};
+use worky_fs::{
+    actor,
+    workspace::{ItemFilter, NewItemFields, Placement},
+    Artifact, ExportFormat, ImportMode, Workspace, WorkspaceConfig,
+};
 
 /// Initialize a new workspace.
-pub fn init(path: &Path, format: OutputFormat) -> Result<()> {
-    Workspace::init(path).context("Failed to initialize workspace")?;
+pub fn init(
+    path: &Path,
+    name: Option<&str>,
+    states: Vec<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let config = name
+        .map_or_else(WorkspaceConfig::default, WorkspaceConfig::new)
+        .with_states(states);
+
+    let ws = Workspace::init_with_config(path, config)
+        .context("Failed to initialize workspace")?;
     output::print_success(
-        &format!("Initialized workspace at {}", path.display()),
+        &format!(
+            "Initialized workspace at {} (config: {})",
+            path.display(),
+            ws.config_path().display()
+        ),
         format,
     );
+
+    if format.is_human() && !output::is_quiet() {
+        println!();
+        println!("Next steps:");
+        println!("  worky new \"First task\"  # create an item");
+        println!("  worky list              # see what's open");
+        println!("  worky board             # open the kanban board");
+    }
+    Ok(())
+}
+
+/// Create a new work item (non-interactive). Without a title, the
+/// template's is used.
+pub fn new_item(
+    path: &Path,
+    title: Option<&str>,
+    mut fields: NewItemFields,
+    template: Option<&str>,
+    vars: &BTreeMap<String, String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let template = match template {
+        Some(name) => Some(ws.load_template(name)?.render(vars)?),
+        None => None,
+    };
+    if let Some(template) = &template {
+        template.apply_to(&mut fields);
+    }
+
+    let Some(title) = title.or_else(|| template.as_ref()?.title.as_deref()) else {
+        bail!("a title is required; the template doesn't set one");
+    };
+    let item = ws
+        .create_item_with(title, &fields)
+        .context("Failed to create item")?;
+
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Print a template with `vars` filled in.
+pub fn template_render(
+    path: &Path,
+    name: &str,
+    vars: &BTreeMap<String, String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let template = ws.load_template(name)?.render(vars)?;
+    output::print(&template, format);
+    Ok(())
+}
+
+/// Parse a `--var NAME=VALUE` argument.
+pub fn parse_var(input: &str) -> std::result::Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected NAME=VALUE, got '{input}'")),
+    }
+}
+
+/// Create a new work item interactively. A template is rendered with
+/// `vars`, prompting for any it needs that are missing.
+pub fn new_interactive(
+    path: &Path,
+    template: Option<&str>,
+    vars: &BTreeMap<String, String>,
+    format: OutputFormat,
+) -> Result<()> {
+    // Verify workspace exists first
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    // Fail before prompting if the requested template is missing
+    if let Some(name) = template {
+        ws.load_template(name)?;
+    }
+
+    let templates = ws
+        .list_templates()
+        .context("Failed to list templates")?
+        .into_iter()
+        .map(|name| ws.load_template(&name).map(|t| (name, t)))
+        .collect::<worky_fs::Result<Vec<_>>>()
+        .context("Failed to load templates")?;
+    let editor = interactive::editor_command(ws.config().editor.as_deref());
+    let labels: Vec<String> = ws
+        .all_labels()
+        .context("Failed to list labels")?
+        .into_iter()
+        .map(|(label, _)| label)
+        .collect();
+    drop(ws); // Close it for now
+
+    // Run interactive prompts (returns None if cancelled)
+    let Some(input) = interactive::prompt_new_item(&templates, template, vars, &labels, &editor)?
+    else {
+        println!("{}", style("  Cancelled.").dim());
+        return Ok(());
+    };
+
+    // Create the item
+    let ws = Workspace::open(path)?;
+
+    let fields = NewItemFields {
+        state: Some(input.state),
+        assignee: input.assignee,
+        labels: input.labels,
+        description: input.description,
+        ..NewItemFields::default()
+    };
+
+    let final_item = ws
+        .create_item_with(&input.title, &fields)
+        .context("Failed to create item")?;
+
+    println!();
+    println!("{}", style("  ✓ Created!").green().bold());
+    println!();
+    output::print(&final_item, format);
+    Ok(())
+}
+
+/// Sort order for item listings.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum SortKey {
+    /// Most recently updated first
+    #[default]
+    Updated,
+    /// Highest priority first, then most recently updated
+    Priority,
+}
+
+/// List work items.
+pub fn list(
+    path: &Path,
+    filter: &ItemFilter,
+    open: bool,
+    ready: bool,
+    sort: SortKey,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let mut items = if ready {
+        ws.ready_items(Some(filter))
+    } else {
+        ws.list_items(Some(filter))
+    }
+    .context("Failed to list items")?;
+
+    if open {
+        items.retain(|item| !ws.is_terminal(&item.state));
+    }
+
+    // Items arrive newest first; a stable sort keeps that as the tiebreaker
+    if matches!(sort, SortKey::Priority) {
+        items.sort_by_key(|item| std::cmp::Reverse(item.priority));
+    }
+
+    let summaries: Vec<WorkItemSummary> = items
+        .iter()
+        .map(|item| WorkItemSummary {
+            closed: ws.is_terminal(&item.state),
+            ..WorkItemSummary::from(item)
+        })
+        .collect();
+    output::print_item_list(&summaries, format);
+
+    Ok(())
+}
+
+/// Print how many items match, without sorting or formatting them.
+pub fn list_count(
+    path: &Path,
+    filter: &ItemFilter,
+    open: bool,
+    ready: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let is_open = |item: &WorkItem| !open || !ws.is_terminal(&item.state);
+
+    let count = if ready {
+        let items = ws.ready_items(Some(filter)).context("Failed to list items")?;
+        items.iter().filter(|item| is_open(item)).count()
+    } else {
+        let items = ws.iter_items(Some(filter)).context("Failed to list items")?;
+        items.filter(|item| is_open(item)).count()
+    };
+
+    output::print(&ItemCount { count }, format);
+    Ok(())
+}
+
+/// Write matching items as NDJSON, one line per item as soon as it is read.
+///
+/// Items come out in directory order. Stops quietly if the reader goes away
+/// (e.g. `| head`).
+pub fn list_stream(path: &Path, filter: &ItemFilter, open: bool) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let mut stdout = std::io::stdout().lock();
+
+    for item in ws.iter_items(Some(filter)).context("Failed to list items")? {
+        let closed = ws.is_terminal(&item.state);
+        if open && closed {
+            continue;
+        }
+
+        let summary = WorkItemSummary {
+            closed,
+            ..WorkItemSummary::from(&item)
+        };
+        let line = serde_json::to_string(&summary)?;
+
+        // Stdout is line-buffered, so each item is flushed as it is written
+        match writeln!(stdout, "{line}") {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e).context("Failed to write item"),
+        }
+    }
+
+    Ok(())
+}
+
+/// List the open items assigned to the current actor.
+pub fn mine(path: &Path, states: &[String], format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let items = ws
+        .workload(ws.actor(), states)
+        .context("Failed to list items")?;
+
+    let summaries: Vec<WorkItemSummary> = items.iter().map(WorkItemSummary::from).collect();
+    output::print_item_list(&summaries, format);
+    Ok(())
+}
+
+/// Show the item to work on next, optionally starting it. Without
+/// `states`, it is picked from the workflow's first two steps.
+pub fn next(
+    path: &Path,
+    states: &[String],
+    start: bool,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let steps = ws.config().workflow.steps();
+    let states = if states.is_empty() {
+        steps.iter().take(2).map(ToString::to_string).collect()
+    } else {
+        states.to_vec()
+    };
+
+    let Some(item) = ws
+        .next_item(ws.actor(), &states)
+        .context("Failed to list items")?
+    else {
+        output::print_success("Nothing to work on", format);
+        return Ok(());
+    };
+
+    if !start {
+        output::print(&item, format);
+        return Ok(());
+    }
+
+    // An item still in the first step moves on to the next one
+    let mut operations = vec![SetOperation::assignee(Some(ws.actor()))];
+    if steps.first().is_some_and(|s| s.eq_ignore_ascii_case(&item.state))
+        && let Some(state) = Workflow::new(&steps).next(&item.state)
+    {
+        operations.insert(0, SetOperation::new("state", state));
+    }
+    if dry_run {
+        let plan = ws
+            .plan_update(&item.uid, &operations)
+            .context("Failed to update item")?;
+        output::print_dry_run(&item.uid, &plan, format);
+        return Ok(());
+    }
+
+    let started = ws
+        .update_item(&item.uid, &operations)
+        .context("Failed to update item")?;
+
+    output::print(&started, format);
+    Ok(())
+}
+
+/// Print who changes are attributed to, and why.
+pub fn whoami(format: OutputFormat) {
+    output::print(&actor::resolve(None), format);
+}
+
+/// Show workspace statistics.
+pub fn stats(path: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let stats = ws.stats().context("Failed to compute stats")?;
+    output::print(&stats, format);
+    Ok(())
+}
+
+/// List the labels in use with their item counts.
+pub fn labels(path: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let labels = ws.all_labels().context("Failed to list labels")?;
+    print_counts(labels, "No labels in use", format);
+    Ok(())
+}
+
+/// List the assignees in use with their item counts.
+pub fn assignees(path: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let assignees = ws.all_assignees().context("Failed to list assignees")?;
+    print_counts(assignees, "No items are assigned", format);
+    Ok(())
+}
+
+fn print_counts(counts: Vec<(String, usize)>, empty: &str, format: OutputFormat) {
+    if counts.is_empty() {
+        output::print_success(empty, format);
+        return;
+    }
+
+    let entries: Vec<CountEntry> = counts
+        .into_iter()
+        .map(|(name, count)| CountEntry { name, count })
+        .collect();
+    output::print_list(&entries, format);
+}
+
+/// Check the workspace for corrupt items, optionally repairing them.
+pub fn doctor(path: &Path, fix: bool, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let report = ws.doctor(fix).context("Failed to check workspace")?;
+
+    output::print(&report, format);
+
+    let remaining = report.remaining();
+    if remaining > 0 {
+        if fix {
+            bail!("{remaining} problem(s) need manual attention");
+        }
+        bail!("{remaining} problem(s) found; run `worky doctor --fix` to repair what can be fixed");
+    }
+
+    Ok(())
+}
+
+/// Compact the event log of `uid`, or with `all` of every item.
+pub fn compact(path: &Path, uid: Option<&str>, all: bool, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let compacted = match uid {
+        Some(uid) if !all => {
+            let summary = ws.compact_events(uid).context("Failed to compact event log")?;
+            if summary.changed() { vec![summary] } else { Vec::new() }
+        }
+        _ => ws.compact_all().context("Failed to compact event logs")?,
+    };
+
+    if compacted.is_empty() {
+        output::print_success("Nothing to compact", format);
+        return Ok(());
+    }
+    output::print_list(&compacted, format);
+    Ok(())
+}
+
+/// Rebuild the on-disk item index.
+pub fn reindex(path: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let count = ws.reindex().context("Failed to rebuild index")?;
+    output::print_success(&format!("Indexed {count} item(s)"), format);
+    Ok(())
+}
+
+/// Permanently delete finished items last updated before `cutoff`.
+///
+/// Lists them first, then asks unless `force` is set.
+pub fn purge(
+    path: &Path,
+    cutoff: DateTime<Utc>,
+    states: &[String],
+    force: bool,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let items = ws
+        .purge_candidates(cutoff, states)
+        .context("Failed to list items")?;
+
+    if items.is_empty() {
+        output::print_success("Nothing to purge", format);
+        return Ok(());
+    }
+
+    let summaries: Vec<WorkItemSummary> = items
+        .iter()
+        .map(|item| WorkItemSummary {
+            closed: true,
+            ..WorkItemSummary::from(item)
+        })
+        .collect();
+
+    if dry_run {
+        if format.is_human() {
+            println!("Would delete {} item(s):", items.len());
+        }
+        output::print_item_list(&summaries, format);
+        return Ok(());
+    }
+
+    if !force {
+        if !std::io::stdin().is_terminal() {
+            bail!("refusing to delete {} item(s) without --force", items.len());
+        }
+        output::print_item_list(&summaries, OutputFormat::Human);
+        let prompt = format!("Permanently delete these {} item(s)?", items.len());
+        if !interactive::confirm(&prompt, false)? {
+            bail!("Purge cancelled; nothing was deleted");
+        }
+    }
+
+    for item in &items {
+        ws.delete_item(&item.uid)
+            .with_context(|| format!("Failed to delete {}", item.uid))?;
+    }
+
+    if format.is_human() {
+        output::print_success(&format!("Deleted {} item(s)", items.len()), format);
+    } else {
+        output::print_item_list(&summaries, format);
+    }
+    Ok(())
+}
+
+/// What `export` writes.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportKind {
+    /// Bundle of items, events, and notes as one JSON array
+    Json,
+    /// The same bundle, one item per line
+    Ndjson,
+    /// iCalendar feed of items with a due date
+    Ical,
+}
+
+/// Export the whole workspace as `kind`, or failing that as the bundle
+/// `format` names.
+pub fn export(
+    path: &Path,
+    output: Option<&Path>,
+    kind: Option<ExportKind>,
+    format: OutputFormat,
+) -> Result<()> {
+    let kind = match (kind, format) {
+        (Some(kind), _) => kind,
+        (None, OutputFormat::Human | OutputFormat::Json) => ExportKind::Json,
+        (None, OutputFormat::Ndjson) => ExportKind::Ndjson,
+        (None, _) => bail!("export supports --format json or ndjson; see also --as ical"),
+    };
+    let bundle_format = match kind {
+        ExportKind::Json => ExportFormat::Json,
+        ExportKind::Ndjson => ExportFormat::Ndjson,
+        ExportKind::Ical => return export_calendar(path, output),
+    };
+
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    match output {
+        Some(file) => {
+            let file = File::create(file)
+                .with_context(|| format!("Failed to create {}", file.display()))?;
+            ws.export(BufWriter::new(file), bundle_format)
+        }
+        None => ws.export(std::io::stdout().lock(), bundle_format),
+    }
+    .context("Failed to export workspace")?;
+
+    Ok(())
+}
+
+/// Write an iCalendar feed of items with a due date.
+fn export_calendar(path: &Path, output: Option<&Path>) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let calendar = ws.calendar().context("Failed to export calendar")?;
+
+    match output {
+        Some(file) => std::fs::write(file, calendar)
+            .with_context(|| format!("Failed to write {}", file.display())),
+        None => std::io::stdout()
+            .write_all(calendar.as_bytes())
+            .context("Failed to write calendar"),
+    }
+}
+
+/// How `import` treats items that already exist.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ConflictMode {
+    /// Keep the existing item
+    #[default]
+    Skip,
+    /// Replace the existing item
+    Overwrite,
+    /// Abort the import
+    ErrorOnConflict,
+}
+
+impl From<ConflictMode> for ImportMode {
+    fn from(mode: ConflictMode) -> Self {
+        match mode {
+            ConflictMode::Skip => Self::Skip,
+            ConflictMode::Overwrite => Self::Overwrite,
+            ConflictMode::ErrorOnConflict => Self::ErrorOnConflict,
+        }
+    }
+}
+
+/// Import items from a bundle produced by `export`.
+pub fn import(path: &Path, bundle: &Path, mode: ConflictMode, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let file =
+        File::open(bundle).with_context(|| format!("Failed to open {}", bundle.display()))?;
+    let summary = ws
+        .import(BufReader::new(file), mode.into())
+        .context("Failed to import bundle")?;
+
+    output::print(&summary, format);
+    Ok(())
+}
+
+/// Import issues from a GitHub repository.
+#[cfg(feature = "github")]
+pub fn import_github(
+    path: &Path,
+    repo: &str,
+    token: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let summary = crate::github::import_issues(&ws, repo, token)?;
+
+    output::print_success(
+        &format!(
+            "Imported issues from {repo}: {} created, {} updated, {} skipped",
+            summary.created, summary.updated, summary.skipped
+        ),
+        format,
+    );
+    Ok(())
+}
+
+/// Import issues from a Jira CSV export.
+pub fn import_jira(
+    path: &Path,
+    csv: &Path,
+    mapping: Option<&Path>,
+    format: OutputFormat,
+) -> Result<()> {
+    // A bulk import brings over work already in progress elsewhere
+    let ws = Workspace::open(path)
+        .context("Failed to open workspace")?
+        .ignoring_wip_limits();
+    let mapping = mapping
+        .map(crate::jira::Mapping::load)
+        .transpose()?
+        .unwrap_or_default();
+    let summary = crate::jira::import_csv(&ws, csv, &mapping)?;
+
+    output::print_success(
+        &format!(
+            "Imported issues from {}: {} created, {} skipped",
+            csv.display(),
+            summary.created,
+            summary.skipped
+        ),
+        format,
+    );
+    Ok(())
+}
+
+/// Get a work item by UID.
+pub fn get(
+    path: &Path,
+    uid: &str,
+    comment_count: usize,
+    show_events: bool,
+    show_notes: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.get_item(uid).context("Failed to get item")?;
+
+    // Get comments if requested
+    let comments = if comment_count > 0 {
+        let events = ws.comments(uid).unwrap_or_default();
+        // Take the last N comments
+        events
+            .into_iter()
+            .rev()
+            .take(comment_count)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let events = show_events
+        .then(|| ws.read_events(uid, None, None, None))
+        .transpose()
+        .context("Failed to read events")?;
+    let notes = show_notes
+        .then(|| ws.read_notes(uid))
+        .transpose()
+        .context("Failed to read notes")?
+        .map(|notes| notes.map(|notes| notes.text).unwrap_or_default());
+
+    output::print_item_detail(
+        &item,
+        &comments,
+        events.as_deref(),
+        notes.as_deref(),
+        format,
+    );
+    Ok(())
+}
+
+/// Open the workspace, letting state changes exceed WIP limits if `force`
+/// is set.
+fn open_workspace(path: &Path, force: bool) -> Result<Workspace> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    Ok(if force { ws.ignoring_wip_limits() } else { ws })
+}
+
+/// Set field values on one or more work items.
+pub fn set(
+    path: &Path,
+    uids: &[String],
+    assignments: &[String],
+    stdin: bool,
+    force: bool,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = open_workspace(path, force)?;
+
+    // Fields from stdin first, so assignments on the command line win
+    let mut operations = if stdin {
+        let input: serde_json::Value = serde_json::from_reader(std::io::stdin().lock())
+            .context("Failed to read a JSON object from stdin")?;
+        SetOperation::from_object(input).context("Failed to read fields from stdin")?
+    } else {
+        Vec::new()
+    };
+    for assignment in assignments {
+        operations
+            .push(SetOperation::parse(assignment).context("Failed to parse assignments")?);
+    }
+
+    if dry_run {
+        for uid in uids {
+            let plan = ws
+                .plan_update(uid, &operations)
+                .with_context(|| format!("Failed to update {uid}"))?;
+            output::print_dry_run(uid, &plan, format);
+        }
+        return Ok(());
+    }
+
+    if let [uid] = uids {
+        let item = ws
+            .update_item(uid, &operations)
+            .context("Failed to update item")?;
+        output::print(&item, format);
+        return Ok(());
+    }
+
+    let items = ws
+        .update_items(uids, &operations)
+        .context("Failed to update items; none were changed")?;
+    output::print_list(&items, format);
+    Ok(())
+}
+
+/// Merge the front matter of an item's notes into its custom fields.
+pub fn sync_notes(path: &Path, uid: &str, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let plan = ws
+        .plan_sync_notes(uid)
+        .context("Failed to read notes front matter")?;
+    if dry_run {
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+    if plan.events.is_empty() {
+        output::print_success("Fields already match the notes front matter", format);
+        return Ok(());
+    }
+
+    let item = ws.sync_notes(uid).context("Failed to update item")?;
+
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Apply a JSON merge patch.
+pub fn patch(
+    path: &Path,
+    uid: &str,
+    merge_json: &str,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let merge_patch: serde_json::Value =
+        serde_json::from_str(merge_json).context("Invalid JSON patch")?;
+
+    if dry_run {
+        let plan = ws
+            .plan_patch(uid, &merge_patch)
+            .context("Failed to patch item")?;
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+
+    let item = ws
+        .patch_item(uid, &merge_patch)
+        .context("Failed to patch item")?;
+
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Open an item's YAML in the editor and apply the changes, one event per
+/// changed field.
+pub fn edit(path: &Path, uid: &str, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let original = ws.get_item(uid).context("Failed to get item")?;
+    let editor = interactive::editor_command(ws.config().editor.as_deref());
+
+    let mut text = serde_yaml::to_string(&original)?;
+    let (edited, plan) = loop {
+        // Unsaved or emptied, like an abandoned commit message
+        let Some(yaml) = interactive::edit_text(&editor, &text, ".yml")? else {
+            output::print_success("Edit cancelled; nothing was changed", format);
+            return Ok(());
+        };
+
+        let planned = serde_yaml::from_str::<serde_json::Value>(&yaml)
+            .map_err(|e| anyhow!("invalid YAML: {e}"))
+            .and_then(|edited| {
+                let plan = ws.plan_replace(uid, &edited)?;
+                Ok((edited, plan))
+            });
+        match planned {
+            Ok(planned) => break planned,
+            // Without a terminal to ask on, there is no way to try again
+            Err(e) if !std::io::stdin().is_terminal() => return Err(e),
+            Err(e) => {
+                eprintln!("{} {e}", style("Error:").red().bold());
+                if !interactive::confirm("Edit again?", true)? {
+                    bail!("Edit cancelled; nothing was changed");
+                }
+                text = yaml;
+            }
+        }
+    };
+
+    if plan.events.is_empty() {
+        output::print_success("No changes", format);
+        return Ok(());
+    }
+    if dry_run {
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+
+    // Applying our edit on top of someone else's would silently undo theirs
+    let current = ws.get_item(uid).context("Failed to get item")?;
+    if current.updated_at != original.updated_at {
+        bail!("{uid} changed while it was open in the editor; run `worky edit` again");
+    }
+
+    let item = ws
+        .replace_item(uid, &edited)
+        .context("Failed to update item")?;
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Apply a JSON Patch (RFC 6902) to an item.
+pub fn json_patch(
+    path: &Path,
+    uid: &str,
+    ops_json: &str,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let ops: Vec<PatchOp> =
+        serde_json::from_str(ops_json).context("Invalid JSON Patch operations")?;
+
+    if dry_run {
+        let plan = ws
+            .plan_json_patch(uid, &ops)
+            .context("Failed to patch item")?;
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+
+    let item = ws
+        .json_patch_item(uid, &ops)
+        .context("Failed to patch item")?;
+
+    output::print(&item, format);
     Ok(())
 }
 
-/// Create a new work item (non-interactive).
-pub fn new_item(
+/// Parse a `--since`/`--until` value: days ago (`7` or `7d`), a date
+/// (`2025-01-01`, midnight UTC), or an RFC 3339 timestamp.
+pub fn parse_time_bound(input: &str) -> std::result::Result<DateTime<Utc>, String> {
+    let input = input.trim();
+
+    if let Ok(days) = input.strip_suffix('d').unwrap_or(input).parse::<u32>() {
+        return Ok(Utc::now() - Duration::days(i64::from(days)));
+    }
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        .map_err(|_| {
+            format!(
+                "expected days ago (7d), a date (2025-01-01) or an RFC 3339 timestamp, got '{input}'"
+            )
+        })
+}
+
+/// Show event history.
+pub fn events(
     path: &Path,
-    title: &str,
-    state: Option<String>,
-    labels: Vec<String>,
-    assignee: Option<String>,
-    description: Option<String>,
+    uid: &str,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    types: &[EventType],
     format: OutputFormat,
 ) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
 
-    let item = ws.create_item(title).context("Failed to create item")?;
+    let types = (!types.is_empty()).then_some(types);
 
-    // Apply additional fields if specified
-    let mut operations = Vec::new();
+    let events = ws
+        .read_events(uid, since, until, types)
+        .context("Failed to read events")?;
 
-    if let Some(state) = state {
-        operations.push(SetOperation::new("state", state));
+    if events.is_empty() {
+        output::print_success("No events found", format);
+        return Ok(());
     }
 
-    if let Some(assignee) = assignee {
-        operations.push(SetOperation::new("assignee", assignee));
-    }
+    output::print_list(&events, format);
+    Ok(())
+}
+
+/// Show who last changed each field of a work item.
+pub fn blame(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let entries: Vec<BlameEntry> = ws
+        .field_provenance(uid)
+        .context("Failed to read item history")?
+        .into_iter()
+        .map(|(path, provenance)| BlameEntry { path, provenance })
+        .collect();
 
-    if !labels.is_empty() {
-        operations.push(SetOperation::new(
-            "labels",
-            serde_json::Value::Array(labels.into_iter().map(serde_json::Value::String).collect()),
-        ));
+    if entries.is_empty() {
+        output::print_success("No recorded changes", format);
+        return Ok(());
     }
 
-    if let Some(description) = description {
-        operations.push(SetOperation::new("fields.description", description));
+    output::print_list(&entries, format);
+    Ok(())
+}
+
+/// Print the config setting `key`.
+pub fn config_get(path: &Path, key: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let Some(value) = ws.config().get(key) else {
+        bail!("Config key '{key}' is not set");
+    };
+
+    if format.is_human() {
+        // Bare value, so it can be used in scripts
+        match value {
+            serde_json::Value::String(s) => println!("{s}"),
+            other => println!("{other}"),
+        }
+    } else {
+        let entry = ConfigEntry {
+            key: key.to_string(),
+            value,
+        };
+        output::print(&entry, format);
     }
+    Ok(())
+}
+
+/// Change the config setting `key`. `value` is read like the value half of
+/// `worky set`, so `'["OPEN", "DONE"]'` is a list and `null` a reset.
+pub fn config_set(path: &Path, key: &str, value: &str, format: OutputFormat) -> Result<()> {
+    let mut ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let value = SetOperation::parse(&format!("{key}={value}"))?.value;
+    ws.set_config(key, &value).context("Failed to update config")?;
 
-    let final_item = if operations.is_empty() {
-        item
+    let message = if value.is_null() {
+        format!("Reset {key} to its default")
     } else {
-        ws.update_item(&item.uid, &operations)
-            .context("Failed to apply initial fields")?
+        format!("Set {key} = {value}")
     };
-
-    output::print(&final_item, format);
+    output::print_success(&message, format);
     Ok(())
 }
 
-/// Create a new work item interactively.
-pub fn new_interactive(path: &Path, format: OutputFormat) -> Result<()> {
-    // Verify workspace exists first
+/// Print every config setting.
+pub fn config_list(path: &Path, format: OutputFormat) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
-    drop(ws); // Close it for now
 
-    // Run interactive prompts (returns None if cancelled)
-    let input = match interactive::prompt_new_item()? {
-        Some(input) => input,
-        None => {
-            println!("{}", style("  Cancelled.").dim());
-            return Ok(());
-        }
-    };
+    let entries: Vec<ConfigEntry> = ws
+        .config()
+        .entries()
+        .into_iter()
+        .map(|(key, value)| ConfigEntry { key, value })
+        .collect();
 
-    // Create the item
-    let ws = Workspace::open(path)?;
-    let item = ws.create_item(&input.title).context("Failed to create item")?;
+    output::print_list(&entries, format);
+    Ok(())
+}
 
-    // Apply fields
-    let mut operations = Vec::new();
+/// Bring `.worky/config.yml` up to the current config version.
+pub fn config_migrate(path: &Path, format: OutputFormat) -> Result<()> {
+    let mut ws = Workspace::open(path).context("Failed to open workspace")?;
+    let changes = ws.migrate_config().context("Failed to migrate config")?;
 
-    if input.state != "TODO" {
-        operations.push(SetOperation::new("state", input.state.clone()));
-    }
+    let version = ws.config().version;
+    let message = if changes.is_empty() {
+        format!("Config is already at version {version}")
+    } else {
+        format!("Migrated config to version {version}: {}", changes.join("; "))
+    };
+    output::print_success(&message, format);
+    Ok(())
+}
 
-    if let Some(assignee) = &input.assignee {
-        operations.push(SetOperation::new("assignee", assignee.clone()));
-    }
+/// Save `list` filter flags as `name`, replacing any filter by that name.
+pub fn filter_save(path: &Path, name: &str, args: &[String], format: OutputFormat) -> Result<()> {
+    let mut ws = Workspace::open(path).context("Failed to open workspace")?;
 
-    if !input.labels.is_empty() {
-        operations.push(SetOperation::new(
-            "labels",
-            serde_json::Value::Array(
-                input
-                    .labels
-                    .iter()
-                    .map(|l| serde_json::Value::String(l.clone()))
-                    .collect(),
-            ),
-        ));
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        bail!("filter name '{name}' may only contain letters, digits, '-', and '_'");
     }
+    ws.set_config(&format!("saved_filters.{name}"), &args.into())
+        .context("Failed to update config")?;
 
-    if let Some(ref description) = input.description {
-        operations.push(SetOperation::new("fields.description", description.clone()));
-    }
+    output::print_success(
+        &format!("Saved filter '{name}'; use `worky list --saved {name}`"),
+        format,
+    );
+    Ok(())
+}
 
-    let final_item = if operations.is_empty() {
-        item
+/// List the saved filters.
+pub fn filter_list(path: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let entries: Vec<SavedFilterEntry> = ws
+        .config()
+        .saved_filters
+        .iter()
+        .map(|(name, args)| SavedFilterEntry {
+            name: name.clone(),
+            args: args.clone(),
+        })
+        .collect();
+    if entries.is_empty() {
+        output::print_success("No saved filters", format);
     } else {
-        ws.update_item(&item.uid, &operations)
-            .context("Failed to apply fields")?
-    };
+        output::print_list(&entries, format);
+    }
+    Ok(())
+}
 
-    println!();
-    println!("{}", style("  ✓ Created!").green().bold());
-    println!();
-    output::print(&final_item, format);
+/// Show the workspace audit log.
+pub fn audit(path: &Path, since: Option<DateTime<Utc>>, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let entries: Vec<FeedEntry> = ws
+        .read_audit(since)
+        .context("Failed to read audit log")?
+        .into_iter()
+        .map(|entry| FeedEntry {
+            uid: entry.uid,
+            event: entry.event,
+        })
+        .collect();
+
+    if entries.is_empty() {
+        let message = if ws.config().audit.enabled {
+            "No audit entries found"
+        } else {
+            "No audit entries found; enable the log with `worky config set audit.enabled true`"
+        };
+        output::print_success(message, format);
+        return Ok(());
+    }
+
+    output::print_list(&entries, format);
     Ok(())
 }
 
-/// List work items.
-pub fn list(
+/// Show the most recent events across the workspace.
+pub fn feed(
     path: &Path,
-    state: Option<String>,
-    assignee: Option<String>,
-    label: Option<String>,
+    since: Option<DateTime<Utc>>,
+    limit: usize,
     format: OutputFormat,
 ) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
 
-    let filter = if state.is_some() || assignee.is_some() || label.is_some() {
-        Some(ItemFilter {
-            state,
-            assignee,
-            label,
-        })
-    } else {
-        None
-    };
-
-    let items = ws
-        .list_items(filter.as_ref())
-        .context("Failed to list items")?;
+    let entries: Vec<FeedEntry> = ws
+        .recent_events(since, limit)
+        .context("Failed to read events")?
+        .into_iter()
+        .map(|(uid, event)| FeedEntry { uid, event })
+        .collect();
 
-    let summaries: Vec<WorkItemSummary> = items.iter().map(WorkItemSummary::from).collect();
-    output::print_item_list(&summaries, format);
+    if entries.is_empty() {
+        output::print_success("No events found", format);
+        return Ok(());
+    }
 
+    output::print_list(&entries, format);
     Ok(())
 }
 
-/// Get a work item by UID.
-pub fn get(path: &Path, uid: &str, comment_count: usize, format: OutputFormat) -> Result<()> {
-    let ws = Workspace::open(path).context("Failed to open workspace")?;
-    let item = ws.get_item(uid).context("Failed to get item")?;
+/// Assign a work item to `person`, or to the current actor with `me`.
+pub fn assign(
+    path: &Path,
+    uid: &str,
+    person: Option<&str>,
+    me: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let user = me.then(actor::current);
 
-    // Get comments if requested
-    let comments = if comment_count > 0 {
-        let events = ws.read_events(uid, None).unwrap_or_default();
-        // Filter to only COMMENT_ADDED events and take last N
-        events
-            .into_iter()
-            .filter(|e| matches!(e.event_type, worky_core::EventType::CommentAdded))
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .take(comment_count)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect()
-    } else {
-        vec![]
+    let Some(assignee) = user.as_deref().or(person).filter(|p| !p.trim().is_empty()) else {
+        bail!("assignee must not be empty; use `worky unassign` to clear it");
     };
 
-    output::print_item_with_comments(&item, &comments, format);
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws
+        .assign(uid, Some(assignee))
+        .context("Failed to assign item")?;
+
+    output::print(&item, format);
     Ok(())
 }
 
-/// Set field values on a work item.
-pub fn set(path: &Path, uid: &str, assignments: &[String], format: OutputFormat) -> Result<()> {
+/// Clear a work item's assignee.
+pub fn unassign(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.assign(uid, None).context("Failed to unassign item")?;
 
-    let operations: Vec<SetOperation> = assignments
-        .iter()
-        .map(|a| SetOperation::parse(a))
-        .collect::<worky_core::Result<Vec<_>>>()
-        .context("Failed to parse assignments")?;
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Mark a work item as blocked by one or more other items.
+pub fn link(path: &Path, uid: &str, blockers: &[String], format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
 
+    // All blockers are added or none are
     let item = ws
-        .update_item(uid, &operations)
-        .context("Failed to update item")?;
+        .transaction(&[uid], |ws| {
+            let mut item = ws.get_item(uid)?;
+            for blocker in blockers {
+                item = ws.add_blocker(uid, blocker)?;
+            }
+            Ok(item)
+        })
+        .with_context(|| format!("Failed to link {uid}"))?;
 
     output::print(&item, format);
     Ok(())
 }
 
-/// Apply a JSON merge patch.
-pub fn patch(path: &Path, uid: &str, merge_json: &str, format: OutputFormat) -> Result<()> {
-    let ws = Workspace::open(path).context("Failed to open workspace")?;
+/// A change to an item's checklist; steps are numbered from 1.
+pub enum ChecklistEdit {
+    Add(String),
+    Done(usize),
+    Undone(usize),
+    Remove(usize),
+}
 
-    let patch: serde_json::Value =
-        serde_json::from_str(merge_json).context("Invalid JSON patch")?;
+/// Edit an item's checklist and show the result.
+pub fn check(path: &Path, uid: &str, edit: ChecklistEdit, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
 
-    let item = ws.patch_item(uid, &patch).context("Failed to patch item")?;
+    let item = match edit {
+        ChecklistEdit::Add(text) => ws.add_checklist_item(uid, &text),
+        ChecklistEdit::Done(number) => ws.set_checklist_done(uid, number, true),
+        ChecklistEdit::Undone(number) => ws.set_checklist_done(uid, number, false),
+        ChecklistEdit::Remove(number) => ws.remove_checklist_item(uid, number),
+    }
+    .context("Failed to update checklist")?;
 
     output::print(&item, format);
     Ok(())
 }
 
-/// Show event history.
-pub fn events(
+/// Log time spent on an item.
+pub fn log_time(
     path: &Path,
     uid: &str,
-    since_days: Option<u32>,
+    minutes: u64,
+    note: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.log_time(uid, minutes, note).context("Failed to log time")?;
 
-    let since = since_days.map(|days| Utc::now() - Duration::days(i64::from(days)));
+    let total = item.time_spent_minutes.unwrap_or(minutes);
+    output::print_success(
+        &format!(
+            "Logged {} on {uid} ({} total)",
+            output::format_minutes(minutes),
+            output::format_minutes(total)
+        ),
+        format,
+    );
+    Ok(())
+}
 
-    let events = ws
-        .read_events(uid, since)
-        .context("Failed to read events")?;
+/// List the time logged on an item, with the total.
+pub fn time_report(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let entries = ws.time_log(uid).context("Failed to read item history")?;
 
-    if events.is_empty() {
-        output::print_success("No events found", format);
-        return Ok(());
+    output::print_list(&entries, format);
+    if format.is_human() {
+        let total: u64 = entries.iter().map(|e| e.minutes).sum();
+        println!("Total: {}", output::format_minutes(total));
     }
-
-    output::print_list(&events, format);
     Ok(())
 }
 
@@ -249,95 +1222,270 @@ pub fn log(path: &Path, uid: &str, message: &str, format: OutputFormat) -> Resul
     Ok(())
 }
 
-/// State workflow order.
-const STATE_WORKFLOW: &[&str] = &["TODO", "IN_PROGRESS", "IN_REVIEW", "DONE"];
+/// Contents of a text file, or of stdin if `file` is `-`.
+pub fn read_text(file: &Path) -> Result<String> {
+    if file == Path::new("-") {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .context("Failed to read stdin")?;
+        return Ok(text);
+    }
+
+    if !file.exists() {
+        bail!("{} does not exist", file.display());
+    }
+    std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))
+}
+
+/// A comment message from `-m`, or else from a file (`-` for stdin).
+///
+/// Trailing whitespace is dropped, so a file's final newline isn't kept.
+pub fn read_message(message: Option<String>, from_file: Option<&Path>) -> Result<String> {
+    let message = match (message, from_file) {
+        (Some(message), _) => message,
+        (None, Some(file)) => read_text(file).context("Failed to read message")?,
+        (None, None) => String::new(),
+    };
 
-/// Advance a work item to the next state.
-pub fn advance(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
+    let message = message.trim_end();
+    if message.trim_start().is_empty() {
+        bail!("comment message is empty");
+    }
+    Ok(message.to_string())
+}
+
+/// Replace a comment with corrected text.
+pub fn edit_comment(
+    path: &Path,
+    uid: &str,
+    event_id: &str,
+    message: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    ws.edit_comment(uid, event_id, message)
+        .context("Failed to edit comment")?;
+    output::print_success("Comment edited", format);
+    Ok(())
+}
+
+/// Copy a file into an item's artifacts.
+pub fn attach(path: &Path, uid: &str, file: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let stored = ws
+        .add_artifact(uid, file)
+        .with_context(|| format!("Failed to attach {}", file.display()))?;
+
+    let artifact = Artifact {
+        name: stored
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size: std::fs::metadata(&stored)?.len(),
+    };
+
+    output::print(&artifact, format);
+    Ok(())
+}
+
+/// List an item's artifacts.
+pub fn artifacts(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let artifacts = ws.list_artifacts(uid).context("Failed to list artifacts")?;
+    output::print_list(&artifacts, format);
+    Ok(())
+}
+
+/// Advance a work item to the next state.
+pub fn advance(
+    path: &Path,
+    uid: &str,
+    force: bool,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = open_workspace(path, force)?;
     let item = ws.get_item(uid).context("Failed to get item")?;
 
     let current_state = item.state.as_str();
 
-    // Find current position in workflow
-    let current_idx = STATE_WORKFLOW
-        .iter()
-        .position(|&s| s.eq_ignore_ascii_case(current_state));
-
-    let next_state = match current_idx {
-        Some(idx) if idx < STATE_WORKFLOW.len() - 1 => STATE_WORKFLOW[idx + 1],
-        Some(_) => {
-            output::print_success(
-                &format!("{uid} is already at final state ({current_state})"),
-                format,
-            );
-            return Ok(());
-        }
-        None => {
-            // Unknown state (e.g., BLOCKED), move to IN_PROGRESS
-            "IN_PROGRESS"
-        }
+    // Unknown states (e.g., BLOCKED) move to the second step
+    let steps = ws.config().workflow.steps();
+    let Some(next_state) = Workflow::new(&steps).next(current_state) else {
+        output::print_success(
+            &format!("{uid} is already at final state ({current_state})"),
+            format,
+        );
+        return Ok(());
     };
 
     let operations = vec![SetOperation::new("state", next_state)];
+    if dry_run {
+        let plan = ws
+            .plan_update(uid, &operations)
+            .context("Failed to update item")?;
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+
     let updated = ws
         .update_item(uid, &operations)
         .context("Failed to update item")?;
 
-    println!(
-        "{}",
-        style(format!("  {} → {}", current_state, next_state))
-            .green()
-            .bold()
-    );
-    output::print(&updated, format);
+    let result = TransitionResult {
+        old_state: current_state.to_string(),
+        new_state: next_state.to_string(),
+        item: updated,
+        reverted: false,
+    };
+    output::print(&result, format);
     Ok(())
 }
 
 /// Revert a work item to the previous state.
-pub fn revert(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
+pub fn revert(path: &Path, uid: &str, dry_run: bool, format: OutputFormat) -> Result<()> {
     let ws = Workspace::open(path).context("Failed to open workspace")?;
     let item = ws.get_item(uid).context("Failed to get item")?;
 
     let current_state = item.state.as_str();
 
-    // Find current position in workflow
-    let current_idx = STATE_WORKFLOW
-        .iter()
-        .position(|&s| s.eq_ignore_ascii_case(current_state));
-
-    let prev_state = match current_idx {
-        Some(idx) if idx > 0 => STATE_WORKFLOW[idx - 1],
-        Some(_) => {
-            output::print_success(
-                &format!("{uid} is already at initial state ({current_state})"),
-                format,
-            );
-            return Ok(());
-        }
-        None => {
-            // Unknown state (e.g., BLOCKED), move to TODO
-            "TODO"
-        }
+    // Unknown states (e.g., BLOCKED) move to the first step
+    let steps = ws.config().workflow.steps();
+    let Some(prev_state) = Workflow::new(&steps).previous(current_state) else {
+        output::print_success(
+            &format!("{uid} is already at initial state ({current_state})"),
+            format,
+        );
+        return Ok(());
     };
 
     let operations = vec![SetOperation::new("state", prev_state)];
+    if dry_run {
+        let plan = ws
+            .plan_update(uid, &operations)
+            .context("Failed to update item")?;
+        output::print_dry_run(uid, &plan, format);
+        return Ok(());
+    }
+
     let updated = ws
         .update_item(uid, &operations)
         .context("Failed to update item")?;
 
-    println!(
-        "{}",
-        style(format!("  {} → {}", current_state, prev_state))
+    let result = TransitionResult {
+        old_state: current_state.to_string(),
+        new_state: prev_state.to_string(),
+        item: updated,
+        reverted: true,
+    };
+    output::print(&result, format);
+    Ok(())
+}
+
+/// Move a work item before or after another in the same state.
+pub fn move_item(
+    path: &Path,
+    uid: &str,
+    placement: &Placement,
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws
+        .set_rank(uid, placement)
+        .context("Failed to move item")?;
+
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Undo the most recent change to a work item.
+pub fn undo(path: &Path, uid: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.undo_last(uid).context("Failed to undo")?;
+
+    output::print_note(style("  Undid last change").yellow().bold(), format);
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Create a copy of a work item under a new title.
+pub fn clone_item(path: &Path, uid: &str, title: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws
+        .clone_item(uid, title)
+        .context("Failed to clone item")?;
+
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Move a work item to the workspace at `to`.
+pub fn transfer(path: &Path, uid: &str, to: &Path, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let target = Workspace::open(to).context("Failed to open target workspace")?;
+    let item = ws
+        .move_item_to_workspace(uid, &target)
+        .context("Failed to transfer item")?;
+
+    output::print_note(
+        style(format!("  Moved to {}", to.display())).green().bold(),
+        format,
+    );
+    output::print(&item, format);
+    Ok(())
+}
+
+/// Reopen a finished work item into an active state.
+pub fn reopen(path: &Path, uid: &str, state: &str, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.get_item(uid).context("Failed to get item")?;
+
+    let Some(updated) = ws
+        .reopen_item(uid, state)
+        .context("Failed to reopen item")?
+    else {
+        output::print_success(&format!("{uid} is already open ({})", item.state), format);
+        return Ok(());
+    };
+
+    output::print_note(
+        style(format!("  {} → {}", item.state, updated.state))
             .yellow()
-            .bold()
+            .bold(),
+        format,
     );
     output::print(&updated, format);
     Ok(())
 }
 
+/// Print every item UID, one per line, for shell completion.
+///
+/// Errors are swallowed so a missing workspace simply completes nothing.
+pub fn complete_uids(path: &Path) {
+    let Ok(ws) = Workspace::open(path) else {
+        return;
+    };
+
+    for item in ws.list_items(None).unwrap_or_default() {
+        println!("{}", item.uid);
+    }
+}
+
 /// Start the tool server.
-pub fn tool_serve(path: &Path, host: &str, port: u16) -> Result<()> {
+pub fn tool_serve(
+    path: &Path,
+    host: &str,
+    port: u16,
+    limits: worky_toolserver::Limits,
+    metrics: bool,
+    cors_origins: &[String],
+) -> Result<()> {
     let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(async { worky_toolserver::serve(path, host, port).await })
+    rt.block_on(async {
+        worky_toolserver::serve(path, host, port, limits, metrics, cors_origins).await
+    })?;
+    println!("Tool server stopped");
+    Ok(())
 }
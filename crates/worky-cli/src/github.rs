@@ -0,0 +1,261 @@
+//! GitHub Issues import adapter.
+
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, LINK, USER_AGENT};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+use worky_core::SetOperation;
+use worky_fs::{workspace::NewItemFields, FsError, Workspace};
+
+const API_BASE: &str = "https://api.github.com";
+
+/// Custom field holding the GitHub issue number.
+const ISSUE_FIELD: &str = "github_issue";
+
+/// Longest we'll sleep waiting for a rate limit window to reset.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_mins(1);
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    assignee: Option<User>,
+    /// Present when the "issue" is actually a pull request.
+    pull_request: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+}
+
+/// Counts reported after a GitHub import.
+#[derive(Debug, Default)]
+pub struct GithubImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+/// Import all issues (open and closed) from `repo` into the workspace.
+///
+/// Items are matched to issues by `fields.github_issue`, so re-running the
+/// import updates existing items instead of creating duplicates.
+pub fn import_issues(
+    ws: &Workspace,
+    repo: &str,
+    token: Option<&str>,
+) -> Result<GithubImportSummary> {
+    if repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+        bail!("expected --repo in the form owner/name, got '{repo}'");
+    }
+
+    let issues = fetch_issues(repo, token)?;
+
+    let mut existing: HashMap<u64, String> = ws
+        .list_items(None)
+        .context("Failed to list items")?
+        .into_iter()
+        .filter_map(|item| {
+            let number = item.fields.get(ISSUE_FIELD)?.as_u64()?;
+            Some((number, item.uid))
+        })
+        .collect();
+
+    let closed_state = ws
+        .config()
+        .workflow
+        .terminal
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "DONE".to_string());
+    let open_state = ws.config().defaults.state.clone();
+
+    let mut summary = GithubImportSummary::default();
+
+    for issue in issues.into_iter().filter(|i| i.pull_request.is_none()) {
+        let closed = issue.state == "closed";
+        let labels: Vec<String> = issue.labels.into_iter().map(|l| l.name).collect();
+        let assignee = issue.assignee.map(|u| u.login);
+
+        if let Some(uid) = existing.get(&issue.number) {
+            let item = ws.get_item(uid).context("Failed to get item")?;
+
+            let mut operations = vec![
+                SetOperation::new("title", issue.title),
+                SetOperation::new("labels", labels),
+                SetOperation::new("assignee", assignee),
+                SetOperation::new("fields.description", issue.body),
+            ];
+
+            // Keep local progress on open issues; only sync open/closed transitions
+            if closed && !ws.is_terminal(&item.state) {
+                operations.push(SetOperation::new("state", closed_state.as_str()));
+            } else if !closed && ws.is_terminal(&item.state) {
+                operations.push(SetOperation::new("state", open_state.as_str()));
+            }
+
+            ws.update_item(uid, &operations)
+                .with_context(|| format!("Failed to update {uid}"))?;
+            summary.updated += 1;
+            continue;
+        }
+
+        let fields = NewItemFields {
+            state: Some(if closed { &closed_state } else { &open_state }.clone()),
+            assignee,
+            labels,
+            description: issue.body,
+            ..NewItemFields::default()
+        };
+
+        let item = match ws.create_item_with(&issue.title, &fields) {
+            Ok(item) => item,
+            Err(FsError::ItemExists(uid)) => {
+                warn!(
+                    issue = issue.number,
+                    uid = %uid,
+                    "An item with this title already exists, skipping"
+                );
+                summary.skipped += 1;
+                continue;
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to import issue #{}", issue.number));
+            }
+        };
+
+        ws.update_item(
+            &item.uid,
+            &[SetOperation::new(format!("fields.{ISSUE_FIELD}"), issue.number)],
+        )
+        .context("Failed to record issue number")?;
+
+        existing.insert(issue.number, item.uid);
+        summary.created += 1;
+    }
+
+    info!(
+        repo = %repo,
+        created = summary.created,
+        updated = summary.updated,
+        skipped = summary.skipped,
+        "Imported GitHub issues"
+    );
+
+    Ok(summary)
+}
+
+/// Fetch every issue page for `repo`, following `Link: rel="next"` headers.
+fn fetch_issues(repo: &str, token: Option<&str>) -> Result<Vec<Issue>> {
+    let client = Client::builder()
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let mut url = Some(format!("{API_BASE}/repos/{repo}/issues?state=all&per_page=100"));
+    let mut issues = Vec::new();
+
+    while let Some(page_url) = url.take() {
+        let response = get_with_rate_limit(&client, &page_url, token)?;
+        url = next_page(response.headers());
+
+        let page: Vec<Issue> = response
+            .json()
+            .with_context(|| format!("Failed to parse issues from {page_url}"))?;
+        debug!(url = %page_url, count = page.len(), "Fetched issue page");
+        issues.extend(page);
+    }
+
+    Ok(issues)
+}
+
+/// GET a URL, waiting out a short rate limit window once before giving up.
+fn get_with_rate_limit(client: &Client, url: &str, token: Option<&str>) -> Result<Response> {
+    for attempt in 0..2 {
+        let mut request = client
+            .get(url)
+            .header(USER_AGENT, "worky")
+            .header(ACCEPT, "application/vnd.github+json");
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let response = request
+            .send()
+            .with_context(|| format!("Failed to reach {url}"))?;
+        let status = response.status();
+
+        if status.is_success() {
+            if let Some(remaining) = header_u64(response.headers(), "x-ratelimit-remaining")
+                && remaining < 10
+            {
+                warn!(remaining, "GitHub rate limit nearly exhausted");
+            }
+            return Ok(response);
+        }
+
+        let rate_limited = status.as_u16() == 429
+            || (status.as_u16() == 403
+                && header_u64(response.headers(), "x-ratelimit-remaining") == Some(0));
+        if !rate_limited {
+            bail!("GitHub returned {status} for {url}");
+        }
+
+        let wait = rate_limit_wait(response.headers());
+        if attempt > 0 || wait > MAX_RATE_LIMIT_WAIT {
+            bail!(
+                "GitHub rate limit exceeded; retry in {}s or pass --token",
+                wait.as_secs()
+            );
+        }
+
+        warn!(seconds = wait.as_secs(), "GitHub rate limit hit, waiting");
+        std::thread::sleep(wait);
+    }
+
+    unreachable!("the second attempt always returns")
+}
+
+/// How long until the rate limit resets, from `retry-after` or `x-ratelimit-reset`.
+fn rate_limit_wait(headers: &HeaderMap) -> Duration {
+    if let Some(seconds) = header_u64(headers, "retry-after") {
+        return Duration::from_secs(seconds);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    header_u64(headers, "x-ratelimit-reset")
+        .map_or(MAX_RATE_LIMIT_WAIT, |reset| {
+            Duration::from_secs(reset.saturating_sub(now) + 1)
+        })
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Extract the `rel="next"` URL from a `Link` header.
+fn next_page(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .contains(r#"rel="next""#)
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
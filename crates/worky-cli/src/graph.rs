@@ -0,0 +1,141 @@
+//! Dependency diagrams for `worky graph`.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+use worky_core::WorkItem;
+use worky_fs::Workspace;
+
+/// Diagram language for `worky graph`.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum GraphStyle {
+    /// Mermaid flowchart
+    #[default]
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+/// Print the blocked-by graph of every item that blocks or is blocked by
+/// another, with an edge from each blocker to the item it blocks.
+pub fn graph(path: &Path, style: GraphStyle) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let items = ws.list_items(None).context("Failed to list items")?;
+
+    let graph = Graph::new(&items);
+    let text = match style {
+        GraphStyle::Mermaid => graph.mermaid(),
+        GraphStyle::Dot => graph.dot(),
+    };
+    print!("{text}");
+    Ok(())
+}
+
+/// Nodes and edges, sorted by UID so the output is stable between runs.
+///
+/// Edges are read straight off each item's `blocked_by`, never followed, so
+/// cycles render like any other edge.
+struct Graph<'a> {
+    /// Node UIDs, with the item if it exists (blockers may have been deleted).
+    nodes: BTreeMap<&'a str, Option<&'a WorkItem>>,
+    /// `(blocker, blocked)` pairs.
+    edges: BTreeSet<(&'a str, &'a str)>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(items: &'a [WorkItem]) -> Self {
+        let by_uid: BTreeMap<&str, &WorkItem> =
+            items.iter().map(|item| (item.uid.as_str(), item)).collect();
+
+        let mut nodes = BTreeMap::new();
+        let mut edges = BTreeSet::new();
+        for item in items {
+            for blocker in &item.blocked_by {
+                nodes.insert(blocker.as_str(), by_uid.get(blocker.as_str()).copied());
+                nodes.insert(item.uid.as_str(), Some(item));
+                edges.insert((blocker.as_str(), item.uid.as_str()));
+            }
+        }
+
+        Self { nodes, edges }
+    }
+
+    /// Mermaid node IDs can't contain `:`, so nodes are numbered instead.
+    fn mermaid(&self) -> String {
+        let ids: BTreeMap<&str, String> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, uid)| (*uid, format!("n{i}")))
+            .collect();
+
+        let mut out = String::from("graph TD\n");
+        for (uid, item) in &self.nodes {
+            let label = mermaid_escape(&label(uid, *item));
+            let _ = writeln!(out, "    {}[\"{label}\"]", ids[uid]);
+        }
+        for (blocker, blocked) in &self.edges {
+            let _ = writeln!(out, "    {} --> {}", ids[blocker], ids[blocked]);
+        }
+        for (uid, item) in &self.nodes {
+            if let Some((fill, text)) = item.and_then(|item| state_colors(&item.state)) {
+                let _ = writeln!(out, "    style {} fill:{fill},color:{text}", ids[uid]);
+            }
+        }
+        out
+    }
+
+    fn dot(&self) -> String {
+        let mut out = String::from("digraph worky {\n");
+        out.push_str("    node [shape=box, style=\"rounded,filled\", fillcolor=\"#e2e8f0\"];\n");
+        for (uid, item) in &self.nodes {
+            let label = dot_escape(&label(uid, *item));
+            let _ = write!(out, "    \"{}\" [label=\"{label}\"", dot_escape(uid));
+            if let Some((fill, text)) = item.and_then(|item| state_colors(&item.state)) {
+                let _ = write!(out, ", fillcolor=\"{fill}\", fontcolor=\"{text}\"");
+            }
+            out.push_str("];\n");
+        }
+        for (blocker, blocked) in &self.edges {
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\";",
+                dot_escape(blocker),
+                dot_escape(blocked)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Node text: the UID, then the title and state if the item exists.
+fn label(uid: &str, item: Option<&WorkItem>) -> String {
+    item.map_or_else(
+        || format!("{uid}\n(missing)"),
+        |item| format!("{uid}\n{}\n[{}]", item.title, item.state),
+    )
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Fill and text color for a state, matching the board palette.
+fn state_colors(state: &str) -> Option<(&'static str, &'static str)> {
+    match state.to_ascii_uppercase().as_str() {
+        "TODO" => Some(("#3b82f6", "#ffffff")),
+        "IN_PROGRESS" => Some(("#f59e0b", "#000000")),
+        "IN_REVIEW" => Some(("#8b5cf6", "#ffffff")),
+        "BLOCKED" => Some(("#ef4444", "#ffffff")),
+        "DONE" => Some(("#22c55e", "#ffffff")),
+        _ => None,
+    }
+}
@@ -2,12 +2,17 @@
 
 use anyhow::{Context, Result};
 use console::style;
-use dialoguer::{theme::ColorfulTheme, Editor, Input, MultiSelect, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, MultiSelect, Select};
+use std::collections::BTreeMap;
+use worky_fs::ItemTemplate;
+
+/// Editor used when neither the environment nor the config names one.
+const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad.exe" } else { "vi" };
 
 /// Default states available for selection.
 const DEFAULT_STATES: &[&str] = &["TODO", "IN_PROGRESS", "IN_REVIEW", "BLOCKED", "DONE"];
 
-/// Common labels for quick selection.
+/// Labels offered in a workspace that doesn't use any yet.
 const COMMON_LABELS: &[&str] = &[
     "backend",
     "frontend",
@@ -24,6 +29,7 @@ const COMMON_LABELS: &[&str] = &[
 /// Data collected from interactive prompts.
 #[derive(Debug, Default, Clone)]
 pub struct NewItemInput {
+    pub template: Option<String>,
     pub title: String,
     pub state: String,
     pub assignee: Option<String>,
@@ -34,6 +40,7 @@ pub struct NewItemInput {
 /// Steps in the wizard.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Step {
+    Template,
     Title,
     State,
     Assignee,
@@ -44,30 +51,31 @@ enum Step {
 }
 
 impl Step {
-    fn next(self) -> Option<Step> {
+    const fn next(self) -> Option<Self> {
         match self {
-            Step::Title => Some(Step::State),
-            Step::State => Some(Step::Assignee),
-            Step::Assignee => Some(Step::Labels),
-            Step::Labels => Some(Step::CustomLabels),
-            Step::CustomLabels => Some(Step::Description),
-            Step::Description => Some(Step::Confirm),
-            Step::Confirm => None,
+            Self::Template => Some(Self::Title),
+            Self::Title => Some(Self::State),
+            Self::State => Some(Self::Assignee),
+            Self::Assignee => Some(Self::Labels),
+            Self::Labels => Some(Self::CustomLabels),
+            Self::CustomLabels => Some(Self::Description),
+            Self::Description => Some(Self::Confirm),
+            Self::Confirm => None,
         }
     }
 
-    fn prev(self) -> Option<Step> {
+    const fn prev(self) -> Option<Self> {
         match self {
-            Step::Title => None,
-            Step::State => Some(Step::Title),
-            Step::Assignee => Some(Step::State),
-            Step::Labels => Some(Step::Assignee),
-            Step::CustomLabels => Some(Step::Labels),
-            Step::Description => Some(Step::CustomLabels),
-            Step::Confirm => Some(Step::Description),
+            Self::Template => None,
+            Self::Title => Some(Self::Template),
+            Self::State => Some(Self::Title),
+            Self::Assignee => Some(Self::State),
+            Self::Labels => Some(Self::Assignee),
+            Self::CustomLabels => Some(Self::Labels),
+            Self::Description => Some(Self::CustomLabels),
+            Self::Confirm => Some(Self::Description),
         }
     }
-
 }
 
 /// Result of a prompt - either a value, go back, or cancel.
@@ -84,10 +92,36 @@ fn is_back_command(input: &str) -> bool {
 }
 
 /// Run interactive prompts to collect work item data.
-pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
+///
+/// When `templates` is non-empty the wizard starts with an optional template
+/// picker; `preset` pre-selects one and skips straight to the title. The
+/// label picker offers `labels`, the ones already in use.
+///
+/// A chosen template is rendered with `vars`, asking for any of its
+/// variables missing from them.
+#[allow(clippy::too_many_lines)]
+pub fn prompt_new_item(
+    templates: &[(String, ItemTemplate)],
+    preset: Option<&str>,
+    vars: &BTreeMap<String, String>,
+    labels: &[String],
+    editor: &str,
+) -> Result<Option<NewItemInput>> {
     let theme = ColorfulTheme::default();
     let mut input = NewItemInput::default();
-    let mut step = Step::Title;
+    let mut vars = vars.clone();
+
+    let preset = preset.and_then(|p| templates.iter().find(|(name, _)| name == p));
+    if let Some((name, template)) = preset {
+        let template = render_template(&theme, template, &mut vars)?;
+        apply_template(&mut input, name, &template);
+    }
+
+    let mut step = if templates.is_empty() || preset.is_some() {
+        Step::Title
+    } else {
+        Step::Template
+    };
 
     println!();
     println!("{}", style("  Create New Work Item").bold().cyan());
@@ -100,12 +134,41 @@ pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
 
     loop {
         match step {
+            Step::Template => {
+                match prompt_template(&theme, templates, input.template.as_deref())? {
+                    PromptResult::Value(Some(idx)) => {
+                        let (name, template) = &templates[idx];
+                        if input.template.as_deref() != Some(name) {
+                            let template = render_template(&theme, template, &mut vars)?;
+                            apply_template(&mut input, name, &template);
+                        }
+                        step = step.next().unwrap();
+                    }
+                    PromptResult::Value(None) => {
+                        if input.template.take().is_some() {
+                            input.state.clear();
+                            input.labels.clear();
+                            input.description = None;
+                        }
+                        step = step.next().unwrap();
+                    }
+                    PromptResult::Back => {
+                        // Can't go back from first step
+                        println!("  {}", style("Already at first step").dim());
+                    }
+                    PromptResult::Cancel => return Ok(None),
+                }
+            }
+
             Step::Title => {
                 match prompt_title(&theme, &input.title)? {
                     PromptResult::Value(v) => {
                         input.title = v;
                         step = step.next().unwrap();
                     }
+                    PromptResult::Back if !templates.is_empty() => {
+                        step = step.prev().unwrap();
+                    }
                     PromptResult::Back => {
                         // Can't go back from first step
                         println!("  {}", style("Already at first step").dim());
@@ -141,7 +204,7 @@ pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
             }
 
             Step::Labels => {
-                match prompt_labels(&theme, &input.labels)? {
+                match prompt_labels(&theme, labels, &input.labels)? {
                     PromptResult::Value(v) => {
                         input.labels = v;
                         step = step.next().unwrap();
@@ -172,7 +235,7 @@ pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
             }
 
             Step::Description => {
-                match prompt_description(&theme, input.description.as_deref())? {
+                match prompt_description(&theme, input.description.as_deref(), editor)? {
                     PromptResult::Value(v) => {
                         input.description = v;
                         step = step.next().unwrap();
@@ -189,10 +252,9 @@ pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
                     PromptResult::Value(confirmed) => {
                         if confirmed {
                             return Ok(Some(input));
-                        } else {
-                            // Go back to allow editing
-                            step = Step::Title;
                         }
+                        // Go back to allow editing
+                        step = Step::Title;
                     }
                     PromptResult::Back => {
                         step = step.prev().unwrap();
@@ -204,6 +266,67 @@ pub fn prompt_new_item() -> Result<Option<NewItemInput>> {
     }
 }
 
+/// `template` rendered with `vars`, after asking for each of its variables
+/// not in them yet. The answers are kept in `vars`.
+fn render_template(
+    theme: &ColorfulTheme,
+    template: &ItemTemplate,
+    vars: &mut BTreeMap<String, String>,
+) -> Result<ItemTemplate> {
+    for name in &template.variables {
+        if !vars.contains_key(name) {
+            let value = Input::<String>::with_theme(theme)
+                .with_prompt(format!("{} {name}", style("Variable").dim()))
+                .interact_text()
+                .context("Failed to read template variable")?;
+            vars.insert(name.clone(), value.trim().to_string());
+        }
+    }
+    Ok(template.render(vars)?)
+}
+
+/// Replace the template-provided fields of `input` with those of `template`.
+fn apply_template(input: &mut NewItemInput, name: &str, template: &ItemTemplate) {
+    input.template = Some(name.to_string());
+    if let Some(title) = &template.title {
+        input.title.clone_from(title);
+    }
+    input.state = template.state.clone().unwrap_or_default();
+    input.labels.clone_from(&template.labels);
+    input.description.clone_from(&template.description);
+}
+
+/// Pick a template; `None` means start from a blank item.
+fn prompt_template(
+    theme: &ColorfulTheme,
+    templates: &[(String, ItemTemplate)],
+    current: Option<&str>,
+) -> Result<PromptResult<Option<usize>>> {
+    println!(
+        "  {} {}",
+        style("Template").dim(),
+        style("(optional, ↑↓ to select, enter to confirm)").dim()
+    );
+
+    let mut choices = vec!["(none)"];
+    choices.extend(templates.iter().map(|(name, _)| name.as_str()));
+
+    let default_index = current
+        .and_then(|c| templates.iter().position(|(name, _)| name == c))
+        .map_or(0, |idx| idx + 1);
+
+    let selection = Select::with_theme(theme)
+        .items(&choices)
+        .default(default_index)
+        .interact_opt()
+        .context("Failed to read template")?;
+
+    // Esc pressed goes back
+    Ok(selection.map_or(PromptResult::Back, |idx| {
+        PromptResult::Value(idx.checked_sub(1))
+    }))
+}
+
 fn prompt_title(theme: &ColorfulTheme, current: &str) -> Result<PromptResult<String>> {
     let prompt = format!("{} Title", style("[1/6]").dim());
 
@@ -249,10 +372,10 @@ fn prompt_state(theme: &ColorfulTheme, current: &str) -> Result<PromptResult<Str
         .interact_opt()
         .context("Failed to read state")?;
 
-    match selection {
-        Some(idx) => Ok(PromptResult::Value(DEFAULT_STATES[idx].to_string())),
-        None => Ok(PromptResult::Back), // Esc pressed
-    }
+    // Esc pressed goes back
+    Ok(selection.map_or(PromptResult::Back, |idx| {
+        PromptResult::Value(DEFAULT_STATES[idx].to_string())
+    }))
 }
 
 fn prompt_assignee(
@@ -285,35 +408,46 @@ fn prompt_assignee(
     }
 }
 
-fn prompt_labels(theme: &ColorfulTheme, current: &[String]) -> Result<PromptResult<Vec<String>>> {
+fn prompt_labels(
+    theme: &ColorfulTheme,
+    known: &[String],
+    current: &[String],
+) -> Result<PromptResult<Vec<String>>> {
     println!(
         "  {} {}",
         style("[4/6]").dim(),
         style("Labels (space to toggle, enter to confirm, Esc to go back)").dim()
     );
 
+    let mut choices: Vec<String> = if known.is_empty() {
+        COMMON_LABELS.iter().map(ToString::to_string).collect()
+    } else {
+        known.to_vec()
+    };
+    // Keep labels from a template selectable even if nothing uses them yet
+    for label in current {
+        if !choices.iter().any(|c| c.eq_ignore_ascii_case(label)) {
+            choices.push(label.clone());
+        }
+    }
+
     // Pre-select current labels
-    let defaults: Vec<bool> = COMMON_LABELS
+    let defaults: Vec<bool> = choices
         .iter()
-        .map(|&label| current.iter().any(|l| l.eq_ignore_ascii_case(label)))
+        .map(|label| current.iter().any(|l| l.eq_ignore_ascii_case(label)))
         .collect();
 
     let selection = MultiSelect::with_theme(theme)
-        .items(COMMON_LABELS)
+        .items(&choices)
         .defaults(&defaults)
         .interact_opt()
         .context("Failed to read labels")?;
 
-    match selection {
-        Some(indices) => {
-            let labels = indices
-                .iter()
-                .map(|&i| COMMON_LABELS[i].to_string())
-                .collect();
-            Ok(PromptResult::Value(labels))
-        }
-        None => Ok(PromptResult::Back), // Esc pressed
-    }
+    // Esc pressed goes back
+    Ok(selection.map_or(PromptResult::Back, |indices| {
+        let labels = indices.iter().map(|&i| choices[i].clone()).collect();
+        PromptResult::Value(labels)
+    }))
 }
 
 fn prompt_custom_labels(
@@ -347,6 +481,7 @@ fn prompt_custom_labels(
 fn prompt_description(
     theme: &ColorfulTheme,
     current: Option<&str>,
+    editor: &str,
 ) -> Result<PromptResult<Option<String>>> {
     let prompt = format!(
         "{} Description? (y=editor, n=skip, <=back)",
@@ -362,7 +497,7 @@ fn prompt_description(
     let selection = Select::with_theme(theme)
         .with_prompt(&prompt)
         .items(choices)
-        .default(if current.is_some() { 1 } else { 1 })
+        .default(1)
         .interact_opt()
         .context("Failed to read description choice")?;
 
@@ -373,15 +508,12 @@ fn prompt_description(
                 match idx {
                     0 => {
                         // Edit in editor
-                        let edited = Editor::new()
-                            .edit(current.unwrap_or(""))
-                            .context("Failed to open editor")?;
-                        Ok(PromptResult::Value(edited.filter(|s| !s.trim().is_empty())))
+                        let edited = edit_text(editor, current.unwrap_or(""), ".txt")?;
+                        Ok(PromptResult::Value(edited))
                     }
                     1 => Ok(PromptResult::Value(current.map(String::from))), // Keep
-                    2 => Ok(PromptResult::Value(None)),                       // Clear
                     3 => Ok(PromptResult::Back),
-                    _ => Ok(PromptResult::Value(None)),
+                    _ => Ok(PromptResult::Value(None)), // Clear
                 }
             } else {
                 match idx {
@@ -391,23 +523,58 @@ fn prompt_description(
                             "  {}",
                             style("Opening editor... (save and close to continue)").dim()
                         );
-                        let edited = Editor::new()
-                            .edit("")
-                            .context("Failed to open editor")?;
-                        Ok(PromptResult::Value(edited.filter(|s| !s.trim().is_empty())))
+                        let edited = edit_text(editor, "", ".txt")?;
+                        Ok(PromptResult::Value(edited))
                     }
-                    1 => Ok(PromptResult::Value(None)), // Skip
                     2 => Ok(PromptResult::Back),
-                    _ => Ok(PromptResult::Value(None)),
+                    _ => Ok(PromptResult::Value(None)), // Skip
                 }
             }
         }
     }
 }
 
+/// The editor command to launch: `$VISUAL`, then `$EDITOR`, then
+/// `configured`, then [`DEFAULT_EDITOR`]. Blank values are skipped.
+pub fn editor_command(configured: Option<&str>) -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .chain(configured.map(String::from))
+        .find(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Open `text` in `editor` as a file ending in `extension` (e.g. `.yml`,
+/// for syntax highlighting); `None` if the result is blank.
+pub fn edit_text(editor: &str, text: &str, extension: &str) -> Result<Option<String>> {
+    let edited = Editor::new()
+        .executable(editor)
+        .extension(extension)
+        .edit(text)
+        .with_context(|| {
+            format!(
+                "Could not launch editor '{editor}'; set $VISUAL or $EDITOR, \
+                 or `editor` in .worky/config.yml"
+            )
+        })?;
+    Ok(edited.filter(|s| !s.trim().is_empty()))
+}
+
+/// Ask a yes/no question; `default` is the answer on Enter.
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default)
+        .interact()?)
+}
+
 fn prompt_confirm(theme: &ColorfulTheme, input: &NewItemInput) -> Result<PromptResult<bool>> {
     println!();
     println!("{}", style("  ┌─ Summary ─────────────────────────────").dim());
+    if let Some(template) = &input.template {
+        println!("  │ Template: {}", style(template).cyan());
+    }
     println!("  │ Title:    {}", style(&input.title).green());
     println!("  │ State:    {}", style(&input.state).yellow());
     if let Some(assignee) = &input.assignee {
@@ -440,11 +607,9 @@ fn prompt_confirm(theme: &ColorfulTheme, input: &NewItemInput) -> Result<PromptR
         .context("Failed to read confirmation")?;
 
     match selection {
-        None => Ok(PromptResult::Cancel),
         Some(0) => Ok(PromptResult::Value(true)),  // Create
         Some(1) => Ok(PromptResult::Value(false)), // Edit
-        Some(2) => Ok(PromptResult::Cancel),       // Cancel
-        _ => Ok(PromptResult::Cancel),
+        _ => Ok(PromptResult::Cancel),             // Cancel or Esc
     }
 }
 
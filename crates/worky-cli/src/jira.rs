@@ -0,0 +1,322 @@
+//! Jira CSV import adapter.
+//!
+//! Reads the CSV written by Jira's "Export issues (CSV, all fields)".
+//! Repeated headers (Jira writes one `Labels` column per label) are merged,
+//! and columns without a mapping are kept under `fields.jira.*`.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use tracing::{info, warn};
+use worky_fs::{workspace::NewItemFields, Workspace};
+
+/// Custom field holding columns that don't map to an item field.
+const STASH_FIELD: &str = "jira";
+
+/// Columns read into item fields; everything else is stashed.
+const MAPPED_COLUMNS: &[&str] =
+    &["summary", "status", "assignee", "labels", "description", "issue key"];
+
+/// Jira statuses understood without a mapping file, matched case-insensitively.
+const DEFAULT_STATUSES: &[(&str, &str)] = &[
+    ("to do", "TODO"),
+    ("open", "TODO"),
+    ("backlog", "TODO"),
+    ("selected for development", "TODO"),
+    ("in progress", "IN_PROGRESS"),
+    ("in review", "IN_REVIEW"),
+    ("code review", "IN_REVIEW"),
+    ("blocked", "BLOCKED"),
+    ("done", "DONE"),
+    ("closed", "DONE"),
+    ("resolved", "DONE"),
+];
+
+/// Contents of a `--mapping` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mapping {
+    /// Jira status name to workspace state.
+    #[serde(default)]
+    status: BTreeMap<String, String>,
+}
+
+impl Mapping {
+    /// Read a mapping file such as:
+    ///
+    /// ```yaml
+    /// status:
+    ///   Ready for QA: IN_REVIEW
+    ///   Won't Do: DONE
+    /// ```
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&text)
+            .with_context(|| format!("Invalid mapping in {}", path.display()))
+    }
+
+    /// Workspace state for the Jira `status`, if it has one.
+    fn state(&self, status: &str) -> Option<&str> {
+        self.status
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(status))
+            .map(|(_, state)| state.as_str())
+            .or_else(|| {
+                DEFAULT_STATUSES
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(status))
+                    .map(|(_, state)| *state)
+            })
+    }
+}
+
+/// Counts reported after a Jira import.
+#[derive(Debug, Default)]
+pub struct JiraImportSummary {
+    pub created: usize,
+    pub skipped: usize,
+}
+
+/// Import every issue in the CSV export at `csv` into the workspace.
+///
+/// Items are matched to issues by their external ID (the issue key), so
+/// re-running the import skips issues that were already brought over.
+/// Issues sharing a summary get numbered slugs (`fix-login-2`), as with
+/// `slug.on_conflict: suffix`.
+pub fn import_csv(ws: &Workspace, csv: &Path, mapping: &Mapping) -> Result<JiraImportSummary> {
+    let ws = ws.clone().suffixing_slugs();
+    let text =
+        std::fs::read_to_string(csv).with_context(|| format!("Failed to read {}", csv.display()))?;
+    let mut rows = parse(&text)
+        .with_context(|| format!("Failed to parse {}", csv.display()))?
+        .into_iter();
+    let Some(headers) = rows.next() else {
+        bail!("{} is empty", csv.display());
+    };
+    let headers: Vec<String> = headers.iter().map(|h| h.trim().to_string()).collect();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    let (Some(summary_col), Some(key_col)) = (column("Summary"), column("Issue key")) else {
+        bail!(
+            "{} has no 'Summary' and 'Issue key' columns; is it a Jira export?",
+            csv.display()
+        );
+    };
+
+    let workflow = &ws.config().workflow.states;
+    for (status, state) in &mapping.status {
+        if !workflow.contains(state) {
+            warn!(status = %status, state = %state, "Mapped state is not in the workflow");
+        }
+    }
+    let default_state = ws.config().defaults.state.clone();
+
+    let mut existing: HashSet<String> = ws
+        .list_items(None)
+        .context("Failed to list items")?
+        .into_iter()
+        .filter_map(|item| item.external_id)
+        .collect();
+
+    let mut summary = JiraImportSummary::default();
+
+    for (line, row) in rows.enumerate() {
+        let row_number = line + 2;
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let cell = |index: usize| {
+            row.get(index)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let (Some(title), Some(key)) = (cell(summary_col), cell(key_col)) else {
+            warn!(row = row_number, "Row has no summary or issue key, skipping");
+            summary.skipped += 1;
+            continue;
+        };
+        if existing.contains(key) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let state = column("Status")
+            .and_then(cell)
+            .map_or_else(|| default_state.clone(), |status| {
+                resolve_state(mapping, workflow, status).unwrap_or_else(|| {
+                    warn!(issue = key, status, "No state mapped for status, using the default");
+                    default_state.clone()
+                })
+            });
+
+        let (labels, stash) = labels_and_stash(&headers, &row);
+
+        let mut fields = NewItemFields {
+            state: Some(state),
+            assignee: column("Assignee").and_then(cell).map(String::from),
+            labels,
+            // Keep the description's own line breaks and indentation
+            description: column("Description")
+                .and_then(|index| row.get(index))
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| value.replace("\r\n", "\n")),
+            external_id: Some(key.to_string()),
+            ..NewItemFields::default()
+        };
+        if !stash.is_empty() {
+            fields.fields.insert(STASH_FIELD.to_string(), Value::Object(stash));
+        }
+
+        ws.create_item_with(title, &fields)
+            .with_context(|| format!("Failed to import {key}"))?;
+
+        existing.insert(key.to_string());
+        summary.created += 1;
+    }
+
+    info!(created = summary.created, skipped = summary.skipped, "Jira import finished");
+    Ok(summary)
+}
+
+/// The workflow state `status` maps to, if the workflow has it.
+fn resolve_state(mapping: &Mapping, workflow: &[String], status: &str) -> Option<String> {
+    mapping
+        .state(status)
+        .filter(|state| workflow.iter().any(|s| s == state))
+        .map(String::from)
+}
+
+/// The row's labels, from every `Labels` column, and its unmapped columns.
+fn labels_and_stash(headers: &[String], row: &[String]) -> (Vec<String>, Map<String, Value>) {
+    let mut labels = Vec::new();
+    let mut stash = Map::new();
+    for (header, value) in headers.iter().zip(row) {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let name = header.to_ascii_lowercase();
+        if name == "labels" {
+            labels.extend(value.split_whitespace().map(String::from));
+        } else if !MAPPED_COLUMNS.contains(&name.as_str()) {
+            stash_value(&mut stash, &field_name(header), value);
+        }
+    }
+    (labels, stash)
+}
+
+/// Add `value` under `name`, turning repeated columns into a list.
+fn stash_value(stash: &mut Map<String, Value>, name: &str, value: &str) {
+    match stash.get_mut(name) {
+        Some(Value::Array(values)) => values.push(value.into()),
+        Some(previous) => *previous = Value::Array(vec![previous.take(), value.into()]),
+        None => {
+            stash.insert(name.to_string(), value.into());
+        }
+    }
+}
+
+/// `Custom field (Story Points)` -> `custom_field_story_points`.
+fn field_name(header: &str) -> String {
+    let mut name = String::with_capacity(header.len());
+    for c in header.chars() {
+        if c.is_alphanumeric() {
+            name.extend(c.to_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+/// Split RFC 4180 CSV into rows of fields.
+///
+/// Quoted fields may contain commas, doubled quotes, and line breaks, which
+/// Jira uses for multi-line descriptions.
+fn parse(text: &str) -> Result<Vec<Vec<String>>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        bail!("unterminated quoted field in row {}", rows.len() + 1);
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let csv = "\u{feff}Summary,Issue key,Description\r\n\
+                   \"Fix, login\",PROJ-1,\"Steps:\r\n  1. Open \"\"Login\"\"\n\"\r\n\
+                   Plain,PROJ-2,\n";
+        let rows = parse(csv).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ["Summary", "Issue key", "Description"]);
+        assert_eq!(rows[1], ["Fix, login", "PROJ-1", "Steps:\r\n  1. Open \"Login\"\n"]);
+        assert_eq!(rows[2], ["Plain", "PROJ-2", ""]);
+
+        // The last row needs no line break, but a quote must be closed
+        assert_eq!(parse("a,b").unwrap(), [["a", "b"]]);
+        assert!(parse("a,\"b\nc").is_err());
+    }
+
+    #[test]
+    fn test_labels_and_stash() {
+        let headers: Vec<String> = [
+            "Summary",
+            "Labels",
+            "Labels",
+            "Sprint",
+            "Sprint",
+            "Custom field (Story Points)",
+        ]
+        .map(String::from)
+        .to_vec();
+        let row: Vec<String> = ["Fix login", "backend auth", "ui", "S1", "S2", "3"]
+            .map(String::from)
+            .to_vec();
+
+        let (labels, stash) = labels_and_stash(&headers, &row);
+        assert_eq!(labels, ["backend", "auth", "ui"]);
+        assert_eq!(stash["sprint"], serde_json::json!(["S1", "S2"]));
+        assert_eq!(stash["custom_field_story_points"], "3");
+        assert!(!stash.contains_key("summary"));
+    }
+}
@@ -2,13 +2,26 @@
 
 mod board;
 mod commands;
+#[cfg(feature = "github")]
+mod github;
+mod graph;
 mod interactive;
+mod jira;
 mod mcp;
 mod output;
+mod watch;
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use commands::ChecklistEdit;
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Write};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use worky_core::{EventType, TimeFormat};
+use worky_fs::workspace::{FilterQuery, ItemFilter, NewItemFields, Placement, Predicate};
+use worky_fs::{FsError, Workspace};
 
 #[derive(Parser)]
 #[command(name = "worky")]
@@ -19,10 +32,36 @@ struct Cli {
     #[arg(long, global = true, default_value = "human")]
     format: output::OutputFormat,
 
-    /// Workspace path (defaults to current directory)
-    #[arg(long, short = 'C', global = true)]
+    /// Workspace path (defaults to the nearest directory with a `.worky`,
+    /// starting from the current one)
+    #[arg(long, short = 'C', global = true, env = "WORKY_WORKSPACE")]
     path: Option<std::path::PathBuf>,
 
+    /// Show the events `set`, `patch`, `edit`, `sync-notes`, `next --start`,
+    /// `advance`, and `revert` would append, or the items `purge` would
+    /// delete, without writing anything; other commands reject it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Only log warnings and errors, and leave out decorative lines such as
+    /// success messages (`-q` is `--query` on listing commands)
+    #[arg(long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log what worky is doing to stderr (`-vv` for more detail)
+    #[arg(long, short = 'v', global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Time zone for displayed timestamps, e.g. `Europe/Berlin` (defaults
+    /// to `$TZ`, then `display.timezone` in the config, then UTC)
+    #[arg(long, global = true)]
+    tz: Option<String>,
+
+    /// Who changes are attributed to, and whose items `mine` and `next`
+    /// pick (defaults to `$WORKY_ACTOR`, then `$USER`)
+    #[arg(long, global = true, value_name = "NAME")]
+    actor: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -30,7 +69,16 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new workspace
-    Init,
+    Init {
+        /// Workspace name stored in the config
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Workflow states in board order (comma-separated); new items start
+        /// in the first and the last is terminal
+        #[arg(long = "state", value_delimiter = ',')]
+        states: Vec<String>,
+    },
 
     /// Create a new work item
     New {
@@ -54,8 +102,25 @@ enum Commands {
         assignee: Option<String>,
 
         /// Description
-        #[arg(long, short = 'd')]
+        #[arg(long, short = 'd', conflicts_with = "description_file")]
         description: Option<String>,
+
+        /// Read the description from a file (`-` for stdin)
+        #[arg(long, value_name = "PATH")]
+        description_file: Option<std::path::PathBuf>,
+
+        /// Start from a template in .worky/templates (explicit flags take precedence)
+        #[arg(long, short = 't')]
+        template: Option<String>,
+
+        /// Fill `{{NAME}}` in the template's title and description (repeatable)
+        #[arg(
+            long = "var",
+            value_name = "NAME=VALUE",
+            value_parser = commands::parse_var,
+            requires = "template"
+        )]
+        vars: Vec<(String, String)>,
     },
 
     /// Create a new work item interactively (alias for `new -i`)
@@ -64,17 +129,131 @@ enum Commands {
     /// List work items
     #[command(alias = "ls")]
     List {
-        /// Filter by state
-        #[arg(long, short = 's')]
-        state: Option<String>,
+        #[command(flatten)]
+        filter: SavedFilter,
 
-        /// Filter by assignee
-        #[arg(long, short = 'a')]
-        assignee: Option<String>,
+        /// Start from a filter saved with `worky filter save`; flags given
+        /// here take precedence
+        #[arg(long, value_name = "NAME")]
+        saved: Option<String>,
 
-        /// Filter by label
-        #[arg(long, short = 'l')]
-        label: Option<String>,
+        /// Sort order
+        #[arg(long, default_value = "updated")]
+        sort: commands::SortKey,
+
+        /// Write NDJSON as items are read, unsorted, without buffering the list
+        #[arg(long, conflicts_with_all = ["ready", "sort"])]
+        stream: bool,
+
+        /// Print only the number of matching items
+        #[arg(long, conflicts_with_all = ["stream", "sort"])]
+        count: bool,
+    },
+
+    /// List open items assigned to you, highest priority and earliest due first
+    Mine {
+        /// Only items in these states (repeat or comma-separate)
+        #[arg(long, short = 's', value_delimiter = ',')]
+        state: Vec<String>,
+    },
+
+    /// Pick the one item to work on next: highest priority, unblocked,
+    /// yours or unassigned, oldest first
+    Next {
+        /// States to pick from (repeat or comma-separate; default: the
+        /// workflow's first two)
+        #[arg(long, short = 's', value_delimiter = ',')]
+        state: Vec<String>,
+
+        /// Assign the item to you and, if it is in the workflow's first
+        /// state, move it to the next
+        #[arg(long)]
+        start: bool,
+    },
+
+    /// Show who changes are attributed to, and where that name comes from
+    Whoami,
+
+    /// Summarize the workspace by state, assignee, and label
+    Stats,
+
+    /// List the labels in use, most used first
+    Labels,
+
+    /// List the assignees in use, busiest first
+    Assignees,
+
+    /// Print the blocked-by graph as a Mermaid or Graphviz DOT diagram
+    Graph {
+        /// Diagram language
+        #[arg(long = "as", value_name = "STYLE", default_value = "mermaid")]
+        style: graph::GraphStyle,
+    },
+
+    /// Rebuild `.worky/index.json`, which lets filtered listings skip
+    /// reading items that can't match
+    Reindex,
+
+    /// Permanently delete finished items not updated since a cutoff
+    Purge {
+        /// Only items last updated before this: days ago (90, 90d) or a date
+        /// (2025-01-01)
+        #[arg(long, value_parser = commands::parse_time_bound)]
+        older_than: DateTime<Utc>,
+
+        /// Only items in these terminal states (repeat or comma-separate)
+        #[arg(long, short = 's', value_delimiter = ',')]
+        state: Vec<String>,
+
+        /// Delete without asking
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check item directories for missing or corrupt files
+    Doctor {
+        /// Recreate missing files and quarantine unreadable event lines
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Fold runs of field changes in event logs, archiving the originals.
+    /// Intermediate field values no longer show in `events` or `blame`
+    Compact {
+        /// Item whose log to compact
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        uid: Option<String>,
+
+        /// Compact every item's log
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Export all items with their events and notes (--format json or ndjson)
+    Export {
+        /// Write to a file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<std::path::PathBuf>,
+
+        /// What to write, overriding --format; `ical` is a calendar of the
+        /// items with a due date
+        #[arg(long = "as", value_enum, value_name = "KIND")]
+        kind: Option<commands::ExportKind>,
+    },
+
+    /// Import items from an export bundle (JSON or NDJSON)
+    #[command(args_conflicts_with_subcommands = true)]
+    Import {
+        #[command(subcommand)]
+        source: Option<ImportSource>,
+
+        /// Bundle file to read
+        #[arg(required = true)]
+        bundle: Option<std::path::PathBuf>,
+
+        /// What to do when an item already exists
+        #[arg(long, default_value = "skip")]
+        mode: commands::ConflictMode,
     },
 
     /// Get a work item by UID
@@ -85,6 +264,14 @@ enum Commands {
         /// Show recent comments (default: 5, use 0 to hide)
         #[arg(long, short = 'c', default_value = "5")]
         comments: usize,
+
+        /// Also show the full event timeline
+        #[arg(long)]
+        events: bool,
+
+        /// Also show the item's notes
+        #[arg(long)]
+        notes: bool,
     },
 
     /// Set field values on a work item
@@ -93,18 +280,52 @@ enum Commands {
         uid: String,
 
         /// Field assignments (key=value)
-        #[arg(required = true)]
+        #[arg(required_unless_present = "stdin")]
         assignments: Vec<String>,
+
+        /// Read a JSON object of fields from stdin, e.g.
+        /// `{"state": "DONE", "fields": {"size": {"points": 3}}}`; nested
+        /// objects become dotted paths, each set like an assignment
+        #[arg(long, visible_alias = "json")]
+        stdin: bool,
+
+        /// Apply the same assignments to another item too (repeatable); if
+        /// any item fails, none is changed
+        #[arg(long, value_name = "UID")]
+        also: Vec<String>,
+
+        /// Change state even if the new state is at its WIP limit
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Edit a work item's YAML in $VISUAL or $EDITOR; each change is
+    /// recorded as an event
+    Edit {
+        /// Work item UID
+        uid: String,
+    },
+
+    /// Copy the YAML front matter of a work item's notes.md into its
+    /// custom fields
+    SyncNotes {
+        /// Work item UID
+        uid: String,
     },
 
-    /// Apply a JSON merge patch to a work item
+    /// Apply a JSON merge patch or JSON Patch to a work item
+    #[command(group = clap::ArgGroup::new("patch").required(true))]
     Patch {
         /// Work item UID
         uid: String,
 
-        /// JSON merge patch
-        #[arg(long)]
-        merge: String,
+        /// JSON merge patch (RFC 7396)
+        #[arg(long, group = "patch")]
+        merge: Option<String>,
+
+        /// JSON Patch (RFC 6902) array of operations
+        #[arg(long, group = "patch")]
+        json_patch: Option<String>,
     },
 
     /// Show event history for a work item
@@ -112,26 +333,153 @@ enum Commands {
         /// Work item UID
         uid: String,
 
-        /// Show events from the last N days
+        /// Only events at or after this time: days ago (7, 7d), a date
+        /// (2025-01-01), or an RFC 3339 timestamp
+        #[arg(long, value_parser = commands::parse_time_bound)]
+        since: Option<DateTime<Utc>>,
+
+        /// Only events before this time (same formats as --since)
+        #[arg(long, value_parser = commands::parse_time_bound, conflicts_with = "follow")]
+        until: Option<DateTime<Utc>>,
+
+        /// Only show these event types (comma-separated)
+        #[arg(long = "type", short = 't', value_delimiter = ',')]
+        types: Vec<EventType>,
+
+        /// Keep printing new events as they are appended, until Ctrl+C
+        #[arg(long, short = 'f')]
+        follow: bool,
+    },
+
+    /// Show who last changed each field of a work item, and when
+    Blame {
+        /// Work item UID
+        uid: String,
+    },
+
+    /// Show recent activity across all work items, newest first
+    Feed {
+        /// Only events at or after this time (same formats as `events --since`)
+        #[arg(long, value_parser = commands::parse_time_bound)]
+        since: Option<DateTime<Utc>>,
+
+        /// Maximum number of events to show
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Show the workspace audit log, oldest first (needs `audit.enabled`)
+    Audit {
+        /// Only entries at or after this time (same formats as `events --since`)
+        #[arg(long, value_parser = commands::parse_time_bound)]
+        since: Option<DateTime<Utc>>,
+    },
+
+    /// Assign a work item to someone
+    Assign {
+        /// Work item UID
+        uid: String,
+
+        /// Person to assign
+        #[arg(required_unless_present = "me", conflicts_with = "me")]
+        person: Option<String>,
+
+        /// Assign to yourself (see `worky whoami`)
         #[arg(long)]
-        since: Option<u32>,
+        me: bool,
+    },
+
+    /// Remove the assignee from a work item
+    Unassign {
+        /// Work item UID
+        uid: String,
+    },
+
+    /// Mark a work item as blocked by other items
+    Link {
+        /// Work item UID
+        uid: String,
+
+        /// UID of a blocking item (can be specified multiple times)
+        #[arg(long, required = true)]
+        blocked_by: Vec<String>,
+    },
+
+    /// Edit a work item's checklist of subtasks
+    Check {
+        /// Work item UID
+        uid: String,
+
+        #[command(subcommand)]
+        action: CheckAction,
+    },
+
+    /// Log time spent on a work item, or report what was logged
+    #[command(group = clap::ArgGroup::new("action").required(true))]
+    Time {
+        /// Work item UID
+        uid: String,
+
+        /// Time to add, e.g. `30m`, `2h`, or `1h30m` (a bare number is minutes)
+        #[arg(long, value_parser = worky_core::parse_minutes, group = "action")]
+        add: Option<u64>,
+
+        /// What the time went on
+        #[arg(long, short = 'm', requires = "add")]
+        note: Option<String>,
+
+        /// List each logged entry with the total
+        #[arg(long, group = "action")]
+        report: bool,
     },
 
     /// Add a comment/log entry to a work item
+    #[command(alias = "comment")]
+    #[command(group = clap::ArgGroup::new("source").required(true))]
     Log {
         /// Work item UID
         uid: String,
 
+        /// `-` to read the message from stdin
+        #[arg(value_name = "-", value_parser = ["-"], group = "source")]
+        stdin: Option<String>,
+
         /// Comment message
-        #[arg(short = 'm', long)]
-        message: String,
+        #[arg(short = 'm', long, group = "source")]
+        message: Option<String>,
+
+        /// Read the message from a file (`-` for stdin)
+        #[arg(long, value_name = "PATH", group = "source")]
+        from_file: Option<std::path::PathBuf>,
+
+        /// Replace the comment with this event ID instead of adding one
+        #[arg(long, value_name = "EVENT_ID")]
+        edit: Option<String>,
+    },
+
+    /// Attach a file to a work item
+    Attach {
+        /// Work item UID
+        uid: String,
+
+        /// File to copy into the item's artifacts directory
+        file: std::path::PathBuf,
+    },
+
+    /// List files attached to a work item
+    Artifacts {
+        /// Work item UID
+        uid: String,
     },
 
     /// Advance a work item to the next state in the workflow
-    #[command(alias = "next")]
     Advance {
         /// Work item UID
         uid: String,
+
+        /// Move even if the next state is at its WIP limit
+        #[arg(long)]
+        force: bool,
     },
 
     /// Move a work item back to the previous state
@@ -141,6 +489,78 @@ enum Commands {
         uid: String,
     },
 
+    /// Reorder a work item within its state column
+    Move {
+        /// Work item UID
+        uid: String,
+
+        /// Place directly before this item
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<String>,
+
+        /// Place directly after this item
+        #[arg(long)]
+        after: Option<String>,
+    },
+
+    /// Reverse the most recent state, assignee, or field change
+    Undo {
+        /// Work item UID
+        uid: String,
+    },
+
+    /// Copy a work item's labels, assignee, fields, and notes under a new title
+    Clone {
+        /// Work item UID to copy
+        uid: String,
+
+        /// Title for the copy
+        title: String,
+    },
+
+    /// Move a work item, with its history and artifacts, to another workspace
+    Transfer {
+        /// Work item UID
+        uid: String,
+
+        /// Root of the workspace to move it to
+        #[arg(long)]
+        to: std::path::PathBuf,
+    },
+
+    /// Move a finished work item straight back to an active state
+    Reopen {
+        /// Work item UID
+        uid: String,
+
+        /// State to reopen into
+        #[arg(long, short = 's', default_value = "IN_PROGRESS")]
+        state: String,
+    },
+
+    /// Read or change workspace settings in .worky/config.yml
+    #[command(subcommand)]
+    Config(ConfigCommands),
+
+    /// Save and list named filters for `worky list --saved`
+    #[command(subcommand)]
+    Filter(FilterCommands),
+
+    /// Work with item templates in .worky/templates
+    #[command(subcommand)]
+    Template(TemplateCommands),
+
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+
+    /// List item UIDs for shell completion functions
+    #[command(name = "__complete-uids", hide = true)]
+    CompleteUids,
+
     /// Start the tool server (for AI integration)
     #[command(subcommand)]
     Tool(ToolCommands),
@@ -149,6 +569,13 @@ enum Commands {
     #[command(subcommand)]
     Mcp(McpCommands),
 
+    /// Print item changes live as they happen
+    Watch {
+        /// Only show items in (or moving into or out of) this state
+        #[arg(long, short = 's')]
+        state: Option<String>,
+    },
+
     /// Start the kanban board web viewer
     Board {
         /// Port to listen on
@@ -161,6 +588,172 @@ enum Commands {
     },
 }
 
+impl Commands {
+    /// Whether the command honors `--dry-run`; the rest would ignore it and
+    /// write, so it is rejected for them.
+    const fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            Self::Set { .. }
+                | Self::Edit { .. }
+                | Self::SyncNotes { .. }
+                | Self::Patch { .. }
+                | Self::Next { .. }
+                | Self::Advance { .. }
+                | Self::Revert { .. }
+                | Self::Purge { .. }
+        )
+    }
+}
+
+/// Item filter flags for `list`.
+#[derive(clap::Args)]
+struct FilterArgs {
+    /// Filter by state (repeat or comma-separate to match any of them)
+    #[arg(long, short = 's', value_delimiter = ',')]
+    state: Vec<String>,
+
+    /// Filter by assignee
+    #[arg(long, short = 'a', conflicts_with = "no_assignee")]
+    assignee: Option<String>,
+
+    /// Only show unassigned items
+    #[arg(long)]
+    no_assignee: bool,
+
+    /// Exclude items assigned to this person
+    #[arg(long)]
+    not_assignee: Option<String>,
+
+    /// Filter by label
+    #[arg(long, short = 'l')]
+    label: Option<String>,
+
+    /// Exclude items with this label (can be specified multiple times)
+    #[arg(long)]
+    not_label: Vec<String>,
+
+    /// Only items whose title or description contains this text (ignoring case)
+    #[arg(long, short = 'q')]
+    query: Option<String>,
+
+    /// Treat --query as a regular expression
+    #[arg(long, requires = "query")]
+    regex: bool,
+}
+
+/// Filter flags for `list`, which can also be saved under a name.
+#[derive(Parser)]
+#[command(name = "worky list", no_binary_name = true)]
+struct SavedFilter {
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Only show items that are not in a terminal state
+    #[arg(long)]
+    open: bool,
+
+    /// Only show open items whose blockers are all done
+    #[arg(long)]
+    ready: bool,
+
+    /// Only show open items not updated in this many days
+    #[arg(long, value_name = "DAYS")]
+    stale: Option<u32>,
+}
+
+impl SavedFilter {
+    /// Parse the `args` of the saved filter `name`.
+    fn parse(name: &str, args: &[String]) -> Result<Self> {
+        Self::try_parse_from(args).map_err(|e| {
+            // Only the first line; the rest is usage for the wrong command
+            let message = e.to_string();
+            let reason = message.lines().next().unwrap_or_default();
+            anyhow::anyhow!(
+                "invalid filter '{name}': {}",
+                reason.trim_start_matches("error: ")
+            )
+        })
+    }
+
+    /// These flags, with `saved` filling in the ones not given.
+    fn or(self, saved: Self) -> Self {
+        let (filter, saved_filter) = (self.filter, saved.filter);
+        let (assignee, no_assignee) = if filter.assignee.is_some() || filter.no_assignee {
+            (filter.assignee, filter.no_assignee)
+        } else {
+            (saved_filter.assignee, saved_filter.no_assignee)
+        };
+        let (query, regex) = if filter.query.is_some() {
+            (filter.query, filter.regex)
+        } else {
+            (saved_filter.query, saved_filter.regex)
+        };
+        let mut not_label = saved_filter.not_label;
+        not_label.extend(filter.not_label);
+
+        Self {
+            filter: FilterArgs {
+                state: if filter.state.is_empty() {
+                    saved_filter.state
+                } else {
+                    filter.state
+                },
+                assignee,
+                no_assignee,
+                not_assignee: filter.not_assignee.or(saved_filter.not_assignee),
+                label: filter.label.or(saved_filter.label),
+                not_label,
+                query,
+                regex,
+            },
+            open: self.open || saved.open,
+            ready: self.ready || saved.ready,
+            stale: self.stale.or(saved.stale),
+        }
+    }
+}
+
+impl From<FilterArgs> for FilterQuery {
+    fn from(args: FilterArgs) -> Self {
+        Self {
+            state: args.state,
+            assignee: args.assignee,
+            no_assignee: args.no_assignee,
+            not_assignee: args.not_assignee,
+            label: args.label,
+            not_label: args.not_label,
+            query: args.query,
+            regex: args.regex,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum ImportSource {
+    /// Import issues from a GitHub repository
+    #[cfg(feature = "github")]
+    Github {
+        /// Repository as owner/name
+        #[arg(long)]
+        repo: String,
+
+        /// API token (needed for private repos and higher rate limits)
+        #[arg(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+
+    /// Import issues from a Jira CSV export
+    Jira {
+        /// CSV file from Jira's "Export issues (CSV, all fields)"
+        csv: std::path::PathBuf,
+
+        /// YAML file mapping Jira statuses to workflow states
+        #[arg(long)]
+        mapping: Option<std::path::PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 enum ToolCommands {
     /// Start the HTTP tool server
@@ -172,6 +765,102 @@ enum ToolCommands {
         /// Host to bind to
         #[arg(long, default_value = "127.0.0.1")]
         host: String,
+
+        /// Requests per second allowed from one client (0 disables the limit)
+        #[arg(long, default_value_t = worky_toolserver::DEFAULT_REQUESTS_PER_SECOND)]
+        rate_limit: u32,
+
+        /// Largest accepted request body in bytes
+        #[arg(long, default_value_t = worky_toolserver::DEFAULT_MAX_BODY_BYTES)]
+        max_body_bytes: usize,
+
+        /// Serve Prometheus metrics at /metrics
+        #[arg(long)]
+        metrics: bool,
+
+        /// Seconds a response is replayed for a repeated Idempotency-Key
+        #[arg(
+            long,
+            value_name = "SECS",
+            default_value_t = worky_toolserver::DEFAULT_IDEMPOTENCY_WINDOW.as_secs()
+        )]
+        idempotency_window: u64,
+
+        /// Let browser pages on this origin call the API, e.g.
+        /// `http://localhost:3000` (repeatable; `*` allows any)
+        #[arg(long = "cors-origin", value_name = "ORIGIN")]
+        cors_origins: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CheckAction {
+    /// Append a step
+    Add {
+        /// What needs doing
+        text: String,
+    },
+
+    /// Check off step N (counting from 1)
+    Done { number: usize },
+
+    /// Uncheck step N
+    Undone { number: usize },
+
+    /// Remove step N; later steps move up
+    Rm { number: usize },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print one setting, e.g. `defaults.state`
+    Get {
+        /// Dotted config key
+        key: String,
+    },
+
+    /// Change one setting (`null` restores the default)
+    Set {
+        /// Dotted config key
+        key: String,
+
+        /// New value, read like `worky set` (JSON for lists and numbers)
+        value: String,
+    },
+
+    /// Print every setting, defaults included
+    List,
+
+    /// Rewrite an older config.yml in the current format (drops comments)
+    Migrate,
+}
+
+#[derive(Subcommand)]
+enum FilterCommands {
+    /// Save `list` flags under a name, e.g. `save backend-bugs -l backend --open`
+    Save {
+        /// Name to recall the filter by
+        name: String,
+
+        /// Filter flags, as given to `worky list`
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// List saved filters
+    List,
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// Print a template with its variables filled in, without creating an item
+    Render {
+        /// Template name
+        name: String,
+
+        /// Value for `{{NAME}}` (repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE", value_parser = commands::parse_var)]
+        vars: Vec<(String, String)>,
     },
 }
 
@@ -181,22 +870,115 @@ enum McpCommands {
     Serve,
 }
 
+/// worky's own crates, whose logs `--verbose` turns up; others stay at `warn`.
+const WORKY_TARGETS: [&str; 5] = [
+    "worky",
+    "worky_core",
+    "worky_fs",
+    "worky_toolserver",
+    "worky_client",
+];
+
+/// The log filter for the verbosity flags. `RUST_LOG` applies only when
+/// neither `--quiet` nor `--verbose` is given.
+fn log_filter(quiet: bool, verbose: u8) -> EnvFilter {
+    let level = match (quiet, verbose) {
+        (true, _) => return EnvFilter::new("warn"),
+        (false, 0) => {
+            // Quiet unless asked, so stderr stays clean for scripts
+            if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+                return EnvFilter::from_default_env();
+            }
+            "warn"
+        }
+        (false, 1) => "debug",
+        (false, _) => "trace",
+    };
+
+    let directives: Vec<String> = std::iter::once("warn".to_string())
+        .chain(WORKY_TARGETS.iter().map(|target| format!("{target}={level}")))
+        .collect();
+    EnvFilter::new(directives.join(","))
+}
+
+#[allow(clippy::too_many_lines)]
 fn main() -> Result<()> {
-    // Initialize tracing
+    let cli = Cli::parse();
+    if cli.dry_run && !cli.command.supports_dry_run() {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--dry-run is only supported by set, patch, edit, sync-notes, next --start, \
+                 advance, revert, and purge",
+            )
+            .exit();
+    }
+
+    // Logs go to stderr so they never mix with output meant for scripts
     tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(EnvFilter::from_default_env())
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(log_filter(cli.quiet, cli.verbose))
         .init();
+    output::set_quiet(cli.quiet);
+    if let Some(actor) = &cli.actor {
+        worky_fs::actor::set_explicit(actor.as_str());
+    }
 
-    let cli = Cli::parse();
+    // Determine workspace path: --path or $WORKY_WORKSPACE, else search upwards
+    // like git. `init` creates a new workspace here rather than searching.
+    let workspace_path = if let Some(path) = cli.path {
+        path
+    } else {
+        let cwd = std::env::current_dir().context("Failed to get current directory")?;
+        if matches!(cli.command, Commands::Init { .. }) {
+            cwd
+        } else {
+            // Without a workspace, commands report it missing in the current
+            // directory; one with a broken config is reported as is
+            match Workspace::discover(&cwd) {
+                Ok(ws) => ws.root().to_path_buf(),
+                Err(FsError::WorkspaceNotFound(_)) => cwd,
+                Err(e) => return Err(e).context("Failed to open workspace"),
+            }
+        }
+    };
 
-    // Determine workspace path
-    let workspace_path = cli
-        .path
-        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
+    // An unrecognized $TZ (e.g. a POSIX rule) falls through to the config
+    let timezone = cli.tz.clone().or_else(|| {
+        std::env::var("TZ")
+            .ok()
+            .filter(|tz| TimeFormat::new(tz, None).is_ok())
+    });
+    let config = Workspace::open(&workspace_path)
+        .map(|ws| ws.config().clone())
+        .unwrap_or_default();
+    output::set_time_format(
+        config
+            .display
+            .time_format(timezone.as_deref())
+            .context("Invalid timestamp display settings")?,
+    );
+    output::set_label_colors(config.labels);
 
     match cli.command {
-        Commands::Init => commands::init(&workspace_path, cli.format),
+        Commands::Completions { shell } => {
+            // `generate` panics if a write fails, so the script is built up
+            // first; a reader such as `head` closing early isn't an error
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Cli::command(), "worky", &mut script);
+            match std::io::stdout().write_all(&script) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+                Err(e) => Err(e).context("Failed to write completions"),
+            }
+        }
+        Commands::CompleteUids => {
+            commands::complete_uids(&workspace_path);
+            Ok(())
+        }
+        Commands::Init { name, states } => {
+            commands::init(&workspace_path, name.as_deref(), states, cli.format)
+        }
         Commands::New {
             title,
             interactive,
@@ -204,49 +986,305 @@ fn main() -> Result<()> {
             label,
             assignee,
             description,
+            description_file,
+            template,
+            vars,
         } => {
-            // Use interactive mode if flag is set or no title provided
-            if interactive || title.is_none() {
-                commands::new_interactive(&workspace_path, cli.format)
+            let description = match description_file {
+                Some(file) => Some(
+                    commands::read_text(&file)
+                        .context("Failed to read description")?
+                        .trim_end()
+                        .to_string(),
+                ),
+                None => description,
+            };
+
+            // Use interactive mode if flag is set or no title provided, unless
+            // variables were given for the template to supply one
+            let vars: BTreeMap<_, _> = vars.into_iter().collect();
+            if interactive || (title.is_none() && vars.is_empty()) {
+                commands::new_interactive(&workspace_path, template.as_deref(), &vars, cli.format)
             } else {
                 commands::new_item(
                     &workspace_path,
-                    title.as_deref().unwrap_or(""),
-                    state,
-                    label,
-                    assignee,
-                    description,
+                    title.as_deref(),
+                    NewItemFields {
+                        state,
+                        assignee,
+                        labels: label,
+                        description,
+                        ..NewItemFields::default()
+                    },
+                    template.as_deref(),
+                    &vars,
                     cli.format,
                 )
             }
         }
-        Commands::Add => commands::new_interactive(&workspace_path, cli.format),
+        Commands::Add => {
+            commands::new_interactive(&workspace_path, None, &BTreeMap::new(), cli.format)
+        }
         Commands::List {
+            filter,
+            saved,
+            sort,
+            stream,
+            count,
+        } => {
+            let SavedFilter {
+                filter,
+                open,
+                ready,
+                stale,
+            } = match saved {
+                Some(name) => {
+                    let Some(args) = config.saved_filters.get(&name) else {
+                        anyhow::bail!("no saved filter named '{name}'; see `worky filter list`");
+                    };
+                    filter.or(SavedFilter::parse(&name, args)?)
+                }
+                None => filter,
+            };
+            let mut filter = ItemFilter::try_from(FilterQuery::from(filter))?;
+            if let Some(days) = stale {
+                let idle = chrono::Duration::days(i64::from(days));
+                filter = filter.with(Predicate::StaleFor(idle));
+            }
+
+            let open = open || stale.is_some();
+            if count {
+                commands::list_count(&workspace_path, &filter, open, ready, cli.format)
+            } else if stream {
+                commands::list_stream(&workspace_path, &filter, open)
+            } else {
+                commands::list(&workspace_path, &filter, open, ready, sort, cli.format)
+            }
+        }
+        Commands::Mine { state } => commands::mine(&workspace_path, &state, cli.format),
+        Commands::Next { state, start } => commands::next(
+            &workspace_path,
+            &state,
+            start,
+            cli.dry_run,
+            cli.format,
+        ),
+        Commands::Whoami => {
+            commands::whoami(cli.format);
+            Ok(())
+        }
+        Commands::Stats => commands::stats(&workspace_path, cli.format),
+        Commands::Labels => commands::labels(&workspace_path, cli.format),
+        Commands::Assignees => commands::assignees(&workspace_path, cli.format),
+        Commands::Graph { style } => graph::graph(&workspace_path, style),
+        Commands::Reindex => commands::reindex(&workspace_path, cli.format),
+        Commands::Purge {
+            older_than,
             state,
-            assignee,
-            label,
-        } => commands::list(&workspace_path, state, assignee, label, cli.format),
-        Commands::Get { uid, comments } => {
-            commands::get(&workspace_path, &uid, comments, cli.format)
+            force,
+        } => commands::purge(
+            &workspace_path,
+            older_than,
+            &state,
+            force,
+            cli.dry_run,
+            cli.format,
+        ),
+        Commands::Doctor { fix } => commands::doctor(&workspace_path, fix, cli.format),
+        Commands::Compact { uid, all } => {
+            commands::compact(&workspace_path, uid.as_deref(), all, cli.format)
+        }
+        Commands::Export { output, kind } => {
+            commands::export(&workspace_path, output.as_deref(), kind, cli.format)
+        }
+        Commands::Import {
+            source,
+            bundle,
+            mode,
+        } => match (source, bundle) {
+            #[cfg(feature = "github")]
+            (Some(ImportSource::Github { repo, token }), _) => {
+                commands::import_github(&workspace_path, &repo, token.as_deref(), cli.format)
+            }
+            (Some(ImportSource::Jira { csv, mapping }), _) => {
+                commands::import_jira(&workspace_path, &csv, mapping.as_deref(), cli.format)
+            }
+            (_, Some(bundle)) => commands::import(&workspace_path, &bundle, mode, cli.format),
+            (_, None) => anyhow::bail!("a bundle file is required"),
+        },
+        Commands::Get {
+            uid,
+            comments,
+            events,
+            notes,
+        } => commands::get(&workspace_path, &uid, comments, events, notes, cli.format),
+        Commands::Set {
+            uid,
+            assignments,
+            stdin,
+            also,
+            force,
+        } => {
+            let uids: Vec<String> = std::iter::once(uid).chain(also).collect();
+            commands::set(
+                &workspace_path,
+                &uids,
+                &assignments,
+                stdin,
+                force,
+                cli.dry_run,
+                cli.format,
+            )
+        }
+        Commands::Edit { uid } => commands::edit(&workspace_path, &uid, cli.dry_run, cli.format),
+        Commands::SyncNotes { uid } => {
+            commands::sync_notes(&workspace_path, &uid, cli.dry_run, cli.format)
+        }
+        Commands::Patch {
+            uid,
+            merge,
+            json_patch,
+        } => match (merge, json_patch) {
+            (Some(merge), _) => {
+                commands::patch(&workspace_path, &uid, &merge, cli.dry_run, cli.format)
+            }
+            (None, Some(ops)) => {
+                commands::json_patch(&workspace_path, &uid, &ops, cli.dry_run, cli.format)
+            }
+            (None, None) => unreachable!("clap requires --merge or --json-patch"),
+        },
+        Commands::Events {
+            uid,
+            since,
+            until,
+            types,
+            follow,
+        } => {
+            if follow {
+                watch::follow(&workspace_path, &uid, since, &types, cli.format)
+            } else {
+                commands::events(&workspace_path, &uid, since, until, &types, cli.format)
+            }
+        }
+        Commands::Blame { uid } => commands::blame(&workspace_path, &uid, cli.format),
+        Commands::Feed { since, limit } => {
+            commands::feed(&workspace_path, since, limit, cli.format)
+        }
+        Commands::Audit { since } => commands::audit(&workspace_path, since, cli.format),
+        Commands::Assign { uid, person, me } => {
+            commands::assign(&workspace_path, &uid, person.as_deref(), me, cli.format)
+        }
+        Commands::Clone { uid, title } => {
+            commands::clone_item(&workspace_path, &uid, &title, cli.format)
+        }
+        Commands::Transfer { uid, to } => {
+            commands::transfer(&workspace_path, &uid, &to, cli.format)
+        }
+        Commands::Unassign { uid } => commands::unassign(&workspace_path, &uid, cli.format),
+        Commands::Link { uid, blocked_by } => {
+            commands::link(&workspace_path, &uid, &blocked_by, cli.format)
+        }
+        Commands::Check { uid, action } => {
+            let edit = match action {
+                CheckAction::Add { text } => ChecklistEdit::Add(text),
+                CheckAction::Done { number } => ChecklistEdit::Done(number),
+                CheckAction::Undone { number } => ChecklistEdit::Undone(number),
+                CheckAction::Rm { number } => ChecklistEdit::Remove(number),
+            };
+            commands::check(&workspace_path, &uid, edit, cli.format)
+        }
+        Commands::Time {
+            uid,
+            add,
+            note,
+            report: _,
+        } => match add {
+            Some(minutes) => {
+                commands::log_time(&workspace_path, &uid, minutes, note.as_deref(), cli.format)
+            }
+            None => commands::time_report(&workspace_path, &uid, cli.format),
+        },
+        Commands::Log {
+            uid,
+            stdin,
+            message,
+            from_file,
+            edit,
+        } => {
+            let from_file = from_file.or_else(|| stdin.map(std::path::PathBuf::from));
+            let message = commands::read_message(message, from_file.as_deref())?;
+            match edit {
+                Some(event_id) => {
+                    commands::edit_comment(&workspace_path, &uid, &event_id, &message, cli.format)
+                }
+                None => commands::log(&workspace_path, &uid, &message, cli.format),
+            }
+        }
+        Commands::Attach { uid, file } => {
+            commands::attach(&workspace_path, &uid, &file, cli.format)
+        }
+        Commands::Artifacts { uid } => commands::artifacts(&workspace_path, &uid, cli.format),
+        Commands::Advance { uid, force } => {
+            commands::advance(&workspace_path, &uid, force, cli.dry_run, cli.format)
+        }
+        Commands::Revert { uid } => {
+            commands::revert(&workspace_path, &uid, cli.dry_run, cli.format)
+        }
+        Commands::Move { uid, before, after } => {
+            let placement = match (before, after) {
+                (Some(before), _) => Placement::Before(before),
+                (None, Some(after)) => Placement::After(after),
+                (None, None) => anyhow::bail!("pass --before or --after"),
+            };
+            commands::move_item(&workspace_path, &uid, &placement, cli.format)
+        }
+        Commands::Undo { uid } => commands::undo(&workspace_path, &uid, cli.format),
+        Commands::Reopen { uid, state } => {
+            commands::reopen(&workspace_path, &uid, &state, cli.format)
+        }
+        Commands::Tool(ToolCommands::Serve {
+            port,
+            host,
+            rate_limit,
+            max_body_bytes,
+            metrics,
+            idempotency_window,
+            cors_origins,
+        }) => {
+            let limits = worky_toolserver::Limits {
+                requests_per_second: rate_limit,
+                max_body_bytes,
+                idempotency_window: std::time::Duration::from_secs(idempotency_window),
+            };
+            commands::tool_serve(&workspace_path, &host, port, limits, metrics, &cors_origins)
+        }
+        Commands::Config(ConfigCommands::Get { key }) => {
+            commands::config_get(&workspace_path, &key, cli.format)
         }
-        Commands::Set { uid, assignments } => {
-            commands::set(&workspace_path, &uid, &assignments, cli.format)
+        Commands::Config(ConfigCommands::Set { key, value }) => {
+            commands::config_set(&workspace_path, &key, &value, cli.format)
         }
-        Commands::Patch { uid, merge } => {
-            commands::patch(&workspace_path, &uid, &merge, cli.format)
+        Commands::Config(ConfigCommands::List) => {
+            commands::config_list(&workspace_path, cli.format)
         }
-        Commands::Events { uid, since } => {
-            commands::events(&workspace_path, &uid, since, cli.format)
+        Commands::Config(ConfigCommands::Migrate) => {
+            commands::config_migrate(&workspace_path, cli.format)
         }
-        Commands::Log { uid, message } => {
-            commands::log(&workspace_path, &uid, &message, cli.format)
+        Commands::Filter(FilterCommands::Save { name, args }) => {
+            SavedFilter::parse(&name, &args)?;
+            commands::filter_save(&workspace_path, &name, &args, cli.format)
         }
-        Commands::Advance { uid } => commands::advance(&workspace_path, &uid, cli.format),
-        Commands::Revert { uid } => commands::revert(&workspace_path, &uid, cli.format),
-        Commands::Tool(ToolCommands::Serve { port, host }) => {
-            commands::tool_serve(&workspace_path, &host, port)
+        Commands::Filter(FilterCommands::List) => {
+            commands::filter_list(&workspace_path, cli.format)
         }
+        Commands::Template(TemplateCommands::Render { name, vars }) => commands::template_render(
+            &workspace_path,
+            &name,
+            &vars.into_iter().collect(),
+            cli.format,
+        ),
         Commands::Mcp(McpCommands::Serve) => mcp::serve(&workspace_path),
+        Commands::Watch { state } => watch::watch(&workspace_path, state.as_deref(), cli.format),
         Commands::Board { port, host } => board::serve(&workspace_path, &host, port),
     }
 }
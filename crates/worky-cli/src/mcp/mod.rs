@@ -1,15 +1,18 @@
 //! MCP (Model Context Protocol) server for Claude Code integration.
 //!
 //! This module implements an MCP server that exposes worky operations
-//! as tools that Claude Code can use directly.
+//! as tools that Claude Code can use directly, and each work item as a
+//! readable resource.
 
 mod protocol;
+mod resources;
 mod tools;
 
 use anyhow::{Context, Result};
 use protocol::{
-    InitializeParams, InitializeResult, JsonRpcRequest, JsonRpcResponse, ServerCapabilities,
-    ServerInfo, ToolCallParams, ToolsCapability, ToolsListResult,
+    InitializeParams, InitializeResult, JsonRpcRequest, JsonRpcResponse, ResourceReadParams,
+    ResourceReadResult, ResourcesCapability, ResourcesListResult, ServerCapabilities, ServerInfo,
+    ToolCallParams, ToolsCapability, ToolsListResult,
 };
 use serde_json::json;
 use std::io::{self, BufRead, Write};
@@ -72,6 +75,8 @@ fn handle_request(workspace_path: &Path, request: &JsonRpcRequest) -> Option<Jso
         }
         "tools/list" => Some(handle_tools_list(request)),
         "tools/call" => Some(handle_tools_call(workspace_path, request)),
+        "resources/list" => Some(handle_resources_list(workspace_path, request)),
+        "resources/read" => Some(handle_resources_read(workspace_path, request)),
         "ping" => Some(JsonRpcResponse::success(request.id.clone(), json!({}))),
         "notifications/cancelled" => {
             // Notification - no response expected
@@ -115,6 +120,10 @@ fn handle_initialize(request: &JsonRpcRequest) -> JsonRpcResponse {
         protocol_version: PROTOCOL_VERSION.to_string(),
         capabilities: ServerCapabilities {
             tools: ToolsCapability { list_changed: false },
+            resources: ResourcesCapability {
+                subscribe: false,
+                list_changed: false,
+            },
         },
         server_info: ServerInfo {
             name: SERVER_NAME.to_string(),
@@ -154,3 +163,44 @@ fn handle_tools_call(workspace_path: &Path, request: &JsonRpcRequest) -> JsonRpc
 
     JsonRpcResponse::success(request.id.clone(), serde_json::to_value(result).unwrap())
 }
+
+fn handle_resources_list(workspace_path: &Path, request: &JsonRpcRequest) -> JsonRpcResponse {
+    match resources::list(workspace_path) {
+        Ok(resources) => {
+            let result = ResourcesListResult { resources };
+            JsonRpcResponse::success(request.id.clone(), serde_json::to_value(result).unwrap())
+        }
+        Err(e) => JsonRpcResponse::error(request.id.clone(), -32603, format!("{e:#}")),
+    }
+}
+
+fn handle_resources_read(workspace_path: &Path, request: &JsonRpcRequest) -> JsonRpcResponse {
+    let params: ResourceReadParams = match &request.params {
+        Some(params) => match serde_json::from_value(params.clone()) {
+            Ok(p) => p,
+            Err(e) => {
+                return JsonRpcResponse::error(
+                    request.id.clone(),
+                    -32602,
+                    format!("Invalid params: {e}"),
+                )
+            }
+        },
+        None => {
+            return JsonRpcResponse::error(request.id.clone(), -32602, "Missing params")
+        }
+    };
+
+    debug!("Resource read: {}", params.uri);
+
+    match resources::read(workspace_path, &params.uri) {
+        Ok(contents) => {
+            let result = ResourceReadResult {
+                contents: vec![contents],
+            };
+            JsonRpcResponse::success(request.id.clone(), serde_json::to_value(result).unwrap())
+        }
+        // -32002 is the MCP code for an unknown resource
+        Err(e) => JsonRpcResponse::error(request.id.clone(), -32002, format!("{e:#}")),
+    }
+}
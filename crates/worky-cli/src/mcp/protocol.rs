@@ -5,6 +5,7 @@ use serde_json::Value;
 
 /// JSON-RPC request message.
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct JsonRpcRequest {
     pub jsonrpc: String,
     pub id: Option<Value>,
@@ -60,6 +61,7 @@ pub struct JsonRpcError {
 
 /// MCP Initialize request params.
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeParams {
     pub protocol_version: String,
@@ -68,6 +70,7 @@ pub struct InitializeParams {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClientCapabilities {
     #[serde(default)]
     pub roots: Option<Value>,
@@ -76,6 +79,7 @@ pub struct ClientCapabilities {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct ClientInfo {
     pub name: String,
     pub version: String,
@@ -93,6 +97,7 @@ pub struct InitializeResult {
 #[derive(Debug, Serialize)]
 pub struct ServerCapabilities {
     pub tools: ToolsCapability,
+    pub resources: ResourcesCapability,
 }
 
 #[derive(Debug, Serialize)]
@@ -101,6 +106,13 @@ pub struct ToolsCapability {
     pub list_changed: bool,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesCapability {
+    pub subscribe: bool,
+    pub list_changed: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ServerInfo {
     pub name: String,
@@ -162,3 +174,41 @@ impl ToolCallResult {
         }
     }
 }
+
+/// Resource entry for resources/list response.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub mime_type: String,
+}
+
+/// Resources list response.
+#[derive(Debug, Serialize)]
+pub struct ResourcesListResult {
+    pub resources: Vec<Resource>,
+}
+
+/// Resource read request params.
+#[derive(Debug, Deserialize)]
+pub struct ResourceReadParams {
+    pub uri: String,
+}
+
+/// Resource read response.
+#[derive(Debug, Serialize)]
+pub struct ResourceReadResult {
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Text contents of a resource.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    pub uri: String,
+    pub mime_type: String,
+    pub text: String,
+}
@@ -0,0 +1,55 @@
+//! MCP resources: each work item as a readable document.
+
+use super::protocol::{Resource, ResourceContents};
+use crate::output::HumanDisplay;
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use worky_fs::Workspace;
+
+/// URI prefix for item resources, followed by the item UID.
+const ITEM_URI_PREFIX: &str = "worky://item/";
+/// Items are rendered as the CLI's human view, which is plain text.
+const MIME_TYPE: &str = "text/plain";
+
+/// One resource per work item.
+pub fn list(workspace_path: &Path) -> Result<Vec<Resource>> {
+    let ws = Workspace::open(workspace_path).context("Failed to open workspace")?;
+    let items = ws.list_items(None).context("Failed to list items")?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| Resource {
+            uri: format!("{ITEM_URI_PREFIX}{}", item.uid),
+            description: Some(format!("{} [{}]", item.uid, item.state)),
+            name: item.title,
+            mime_type: MIME_TYPE.to_string(),
+        })
+        .collect())
+}
+
+/// Render the item behind `uri` followed by its notes.
+pub fn read(workspace_path: &Path, uri: &str) -> Result<ResourceContents> {
+    let uid = uri
+        .strip_prefix(ITEM_URI_PREFIX)
+        .ok_or_else(|| anyhow!("Resource not found: {uri}"))?;
+
+    let ws = Workspace::open(workspace_path).context("Failed to open workspace")?;
+    let item = ws
+        .get_item(uid)
+        .with_context(|| format!("Resource not found: {uri}"))?;
+
+    // Clients expect plain text even when colours are forced on
+    let mut text = console::strip_ansi_codes(&item.human_display()).into_owned();
+    if let Some(notes) = ws.read_notes(uid)?
+        && !notes.text.trim().is_empty()
+    {
+        text.push('\n');
+        text.push_str(&notes.text);
+    }
+
+    Ok(ResourceContents {
+        uri: uri.to_string(),
+        mime_type: MIME_TYPE.to_string(),
+        text,
+    })
+}
@@ -3,30 +3,79 @@
 use super::protocol::{ToolCallResult, ToolDefinition};
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::fmt::Write;
 use std::path::Path;
-use worky_core::SetOperation;
-use worky_fs::{workspace::ItemFilter, Workspace};
+use worky_core::{display::format_payload, SetOperation, Workflow};
+use worky_fs::{
+    workspace::{FilterQuery, ItemFilter, NewItemFields},
+    AiAudit, Workspace,
+};
+
+/// Longest argument string kept in an `AI_ACTION` event's details.
+const MAX_DETAIL_LEN: usize = 200;
 
 /// Get all available tool definitions.
+#[allow(clippy::too_many_lines)]
 pub fn get_tool_definitions() -> Vec<ToolDefinition> {
     vec![
         ToolDefinition {
             name: "worky_list".to_string(),
-            description: "List work items in the workspace. Returns a summary of all items with optional filtering by state, assignee, or label.".to_string(),
+            description: "List work items in the workspace. Returns a summary of all items with optional filtering by state, assignee, label, or text in the title and description. All given filters must match.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "state": {
-                        "type": "string",
-                        "description": "Filter by state (e.g., TODO, IN_PROGRESS, DONE)"
+                        "type": ["string", "array"],
+                        "items": { "type": "string" },
+                        "description": "Filter by state (e.g., TODO, IN_PROGRESS, DONE); a list matches any of them"
                     },
                     "assignee": {
                         "type": "string",
                         "description": "Filter by assignee name"
                     },
+                    "no_assignee": {
+                        "type": "boolean",
+                        "description": "Only unassigned items"
+                    },
+                    "not_assignee": {
+                        "type": "string",
+                        "description": "Exclude items assigned to this person"
+                    },
                     "label": {
                         "type": "string",
                         "description": "Filter by label"
+                    },
+                    "not_label": {
+                        "type": ["string", "array"],
+                        "items": { "type": "string" },
+                        "description": "Exclude items with this label (or any of these labels)"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Only items whose title or description contains this text, ignoring case"
+                    },
+                    "regex": {
+                        "type": "boolean",
+                        "description": "Treat query as a regular expression"
+                    }
+                },
+                "additionalProperties": false
+            }),
+        },
+        ToolDefinition {
+            name: "worky_mine".to_string(),
+            description: "Report a person's workload: their open work items, highest priority and earliest due first. Defaults to the user running the server.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "assignee": {
+                        "type": "string",
+                        "description": "Whose workload to report (default: the current user)"
+                    },
+                    "state": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only items in these states"
                     }
                 },
                 "additionalProperties": false
@@ -157,7 +206,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "worky_advance".to_string(),
-            description: "Advance a work item to the next state in the workflow: TODO → IN_PROGRESS → IN_REVIEW → DONE".to_string(),
+            description: "Advance a work item to the next state in the workspace's workflow (by default TODO → IN_PROGRESS → IN_REVIEW → DONE)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -172,7 +221,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "worky_revert".to_string(),
-            description: "Move a work item back to the previous state in the workflow: DONE → IN_REVIEW → IN_PROGRESS → TODO".to_string(),
+            description: "Move a work item back to the previous state in the workspace's workflow (by default DONE → IN_REVIEW → IN_PROGRESS → TODO)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -185,55 +234,107 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 "additionalProperties": false
             }),
         },
+        ToolDefinition {
+            name: "worky_patch".to_string(),
+            description: "Apply a JSON merge patch to a work item to change several fields in one call. Keys set to null are removed; nested objects such as `fields` are merged.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "uid": {
+                        "type": "string",
+                        "description": "The work item UID"
+                    },
+                    "patch": {
+                        "type": "object",
+                        "description": "JSON merge patch (e.g., {\"state\": \"IN_REVIEW\", \"fields\": {\"estimate\": 3}})",
+                        "additionalProperties": true
+                    }
+                },
+                "required": ["uid", "patch"],
+                "additionalProperties": false
+            }),
+        },
+        ToolDefinition {
+            name: "worky_delete".to_string(),
+            description: "Permanently delete a work item with its history, notes, and artifacts. This cannot be undone; pass confirm: true to proceed.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "uid": {
+                        "type": "string",
+                        "description": "The work item UID"
+                    },
+                    "confirm": {
+                        "type": "boolean",
+                        "description": "Must be true; guards against accidental deletion"
+                    }
+                },
+                "required": ["uid", "confirm"],
+                "additionalProperties": false
+            }),
+        },
     ]
 }
 
-/// State workflow order.
-const STATE_WORKFLOW: &[&str] = &["TODO", "IN_PROGRESS", "IN_REVIEW", "DONE"];
-
 /// Handle a tool call and return the result.
 pub fn handle_tool_call(workspace_path: &Path, name: &str, arguments: Option<Value>) -> ToolCallResult {
-    let args = arguments.unwrap_or(json!({}));
+    let args = arguments.unwrap_or_else(|| json!({}));
+
+    // Every write made by a tool call is logged with the call that caused it
+    let audit = AiAudit::new("worky-mcp").with_details(json!({
+        "tool": name,
+        "arguments": summarize(&args),
+    }));
 
     match name {
         "worky_list" => handle_list(workspace_path, args),
+        "worky_mine" => handle_mine(workspace_path, args),
         "worky_get" => handle_get(workspace_path, args),
-        "worky_create" => handle_create(workspace_path, args),
-        "worky_set" => handle_set(workspace_path, args),
-        "worky_log" => handle_log(workspace_path, args),
+        "worky_create" => handle_create(workspace_path, args, audit),
+        "worky_set" => handle_set(workspace_path, args, audit),
+        "worky_log" => handle_log(workspace_path, args, audit),
         "worky_events" => handle_events(workspace_path, args),
-        "worky_advance" => handle_advance(workspace_path, args),
-        "worky_revert" => handle_revert(workspace_path, args),
+        "worky_advance" => handle_advance(workspace_path, args, audit),
+        "worky_revert" => handle_revert(workspace_path, args, audit),
+        "worky_patch" => handle_patch(workspace_path, args, audit),
+        "worky_delete" => handle_delete(workspace_path, args, audit),
         _ => ToolCallResult::error(format!("Unknown tool: {name}")),
     }
 }
 
-#[derive(Deserialize, Default)]
-struct ListArgs {
-    state: Option<String>,
-    assignee: Option<String>,
-    label: Option<String>,
+/// `args` with long strings shortened, for event details.
+fn summarize(args: &Value) -> Value {
+    match args {
+        Value::String(s) if s.chars().count() > MAX_DETAIL_LEN => {
+            let head: String = s.chars().take(MAX_DETAIL_LEN).collect();
+            Value::String(format!("{head}…"))
+        }
+        Value::Array(values) => Value::Array(values.iter().map(summarize).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), summarize(value)))
+                .collect(),
+        ),
+        _ => args.clone(),
+    }
 }
 
 fn handle_list(workspace_path: &Path, args: Value) -> ToolCallResult {
-    let args: ListArgs = serde_json::from_value(args).unwrap_or_default();
+    let args: FilterQuery = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
+    };
 
     let ws = match Workspace::open(workspace_path) {
         Ok(ws) => ws,
         Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
     };
 
-    let filter = if args.state.is_some() || args.assignee.is_some() || args.label.is_some() {
-        Some(ItemFilter {
-            state: args.state,
-            assignee: args.assignee,
-            label: args.label,
-        })
-    } else {
-        None
+    let filter = match ItemFilter::try_from(args) {
+        Ok(filter) => filter,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
     };
-
-    let items = match ws.list_items(filter.as_ref()) {
+    let items = match ws.list_items(Some(&filter)) {
         Ok(items) => items,
         Err(e) => return ToolCallResult::error(format!("Failed to list items: {e}")),
     };
@@ -243,14 +344,60 @@ fn handle_list(workspace_path: &Path, args: Value) -> ToolCallResult {
     }
 
     let mut output = String::new();
-    output.push_str(&format!("Found {} work item(s):\n\n", items.len()));
+    writeln!(output, "Found {} work item(s):\n", items.len()).unwrap();
 
     for item in &items {
         let assignee = item.assignee.as_deref().unwrap_or("-");
-        output.push_str(&format!(
-            "• {} [{}] @{}\n  {}\n\n",
+        writeln!(
+            output,
+            "• {} [{}] @{}\n  {}\n",
             item.uid, item.state, assignee, item.title
-        ));
+        )
+        .unwrap();
+    }
+
+    ToolCallResult::text(output)
+}
+
+#[derive(Deserialize)]
+struct MineArgs {
+    assignee: Option<String>,
+    #[serde(default)]
+    state: Vec<String>,
+}
+
+fn handle_mine(workspace_path: &Path, args: Value) -> ToolCallResult {
+    let args: MineArgs = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
+    };
+
+    let ws = match Workspace::open(workspace_path) {
+        Ok(ws) => ws,
+        Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
+    };
+    let assignee = args.assignee.unwrap_or_else(|| ws.actor().to_string());
+
+    let items = match ws.workload(&assignee, &args.state) {
+        Ok(items) => items,
+        Err(e) => return ToolCallResult::error(format!("Failed to list items: {e}")),
+    };
+
+    if items.is_empty() {
+        return ToolCallResult::text(format!("{assignee} has no open work items."));
+    }
+
+    let mut output = String::new();
+    writeln!(output, "{assignee} has {} open work item(s):\n", items.len()).unwrap();
+
+    for item in &items {
+        let priority = item.priority.map_or_else(|| "-".to_string(), |p| p.to_string());
+        writeln!(
+            output,
+            "• {} [{}] priority {}\n  {}\n",
+            item.uid, item.state, priority, item.title
+        )
+        .unwrap();
     }
 
     ToolCallResult::text(output)
@@ -263,7 +410,7 @@ struct GetArgs {
     comments: usize,
 }
 
-fn default_comments() -> usize {
+const fn default_comments() -> usize {
     10
 }
 
@@ -284,46 +431,45 @@ fn handle_get(workspace_path: &Path, args: Value) -> ToolCallResult {
     };
 
     let mut output = String::new();
-    output.push_str(&format!("UID: {}\n", item.uid));
-    output.push_str(&format!("Title: {}\n", item.title));
-    output.push_str(&format!("State: {}\n", item.state));
+    writeln!(output, "UID: {}", item.uid).unwrap();
+    writeln!(output, "Title: {}", item.title).unwrap();
+    writeln!(output, "State: {}", item.state).unwrap();
 
     if let Some(assignee) = &item.assignee {
-        output.push_str(&format!("Assignee: {assignee}\n"));
+        writeln!(output, "Assignee: {assignee}").unwrap();
     }
 
     if !item.labels.is_empty() {
-        output.push_str(&format!("Labels: {}\n", item.labels.join(", ")));
+        writeln!(output, "Labels: {}", item.labels.join(", ")).unwrap();
     }
 
-    output.push_str(&format!("Created: {}\n", item.created_at.format("%Y-%m-%d %H:%M UTC")));
-    output.push_str(&format!("Updated: {}\n", item.updated_at.format("%Y-%m-%d %H:%M UTC")));
+    writeln!(output, "Created: {}", item.created_at.format("%Y-%m-%d %H:%M UTC")).unwrap();
+    writeln!(output, "Updated: {}", item.updated_at.format("%Y-%m-%d %H:%M UTC")).unwrap();
 
     if !item.fields.is_empty() {
         output.push_str("\nCustom Fields:\n");
         for (key, value) in &item.fields {
-            output.push_str(&format!("  {key}: {value}\n"));
+            writeln!(output, "  {key}: {value}").unwrap();
         }
     }
 
     // Get comments
-    if args.comments > 0 {
-        if let Ok(events) = ws.read_events(&args.uid, None) {
-            let comments: Vec<_> = events
-                .into_iter()
-                .filter(|e| matches!(e.event_type, worky_core::EventType::CommentAdded))
-                .collect();
-
-            let recent: Vec<_> = comments.into_iter().rev().take(args.comments).collect();
-
-            if !recent.is_empty() {
-                output.push_str("\nRecent Comments:\n");
-                for event in recent.into_iter().rev() {
-                    let actor = event.actor.as_deref().unwrap_or("user");
-                    let time = event.timestamp.format("%Y-%m-%d %H:%M");
-                    if let worky_core::EventPayload::Comment(p) = &event.payload {
-                        output.push_str(&format!("  [{time}] {actor}: {}\n", p.message));
-                    }
+    if args.comments > 0
+        && let Ok(events) = ws.comments(&args.uid)
+    {
+        let recent: Vec<_> = events
+            .into_iter()
+            .rev()
+            .take(args.comments)
+            .collect();
+
+        if !recent.is_empty() {
+            output.push_str("\nRecent Comments:\n");
+            for event in recent.into_iter().rev() {
+                let actor = event.actor.as_deref().unwrap_or("user");
+                let time = event.timestamp.format("%Y-%m-%d %H:%M");
+                if let worky_core::EventPayload::Comment(p) = &event.payload {
+                    writeln!(output, "  [{time}] {actor}: {}", p.message).unwrap();
                 }
             }
         }
@@ -341,7 +487,7 @@ struct CreateArgs {
     description: Option<String>,
 }
 
-fn handle_create(workspace_path: &Path, args: Value) -> ToolCallResult {
+fn handle_create(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
     let args: CreateArgs = match serde_json::from_value(args) {
         Ok(a) => a,
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
@@ -352,42 +498,17 @@ fn handle_create(workspace_path: &Path, args: Value) -> ToolCallResult {
         Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
     };
 
-    let item = match ws.create_item(&args.title) {
-        Ok(item) => item,
-        Err(e) => return ToolCallResult::error(format!("Failed to create item: {e}")),
+    let fields = NewItemFields {
+        state: args.state,
+        assignee: args.assignee,
+        labels: args.labels.unwrap_or_default(),
+        description: args.description,
+        ..NewItemFields::default()
     };
 
-    // Apply additional fields
-    let mut operations = Vec::new();
-
-    if let Some(state) = args.state {
-        operations.push(SetOperation::new("state", state));
-    }
-
-    if let Some(assignee) = args.assignee {
-        operations.push(SetOperation::new("assignee", assignee));
-    }
-
-    if let Some(labels) = args.labels {
-        if !labels.is_empty() {
-            operations.push(SetOperation::new(
-                "labels",
-                serde_json::Value::Array(labels.into_iter().map(serde_json::Value::String).collect()),
-            ));
-        }
-    }
-
-    if let Some(description) = args.description {
-        operations.push(SetOperation::new("fields.description", description));
-    }
-
-    let final_item = if operations.is_empty() {
-        item
-    } else {
-        match ws.update_item(&item.uid, &operations) {
-            Ok(item) => item,
-            Err(e) => return ToolCallResult::error(format!("Created item but failed to set fields: {e}")),
-        }
+    let final_item = match ws.audited(audit).create_item_with(&args.title, &fields) {
+        Ok(item) => item,
+        Err(e) => return ToolCallResult::error(format!("Failed to create item: {e}")),
     };
 
     ToolCallResult::text(format!(
@@ -405,7 +526,7 @@ struct SetArgs {
     fields: Option<serde_json::Map<String, Value>>,
 }
 
-fn handle_set(workspace_path: &Path, args: Value) -> ToolCallResult {
+fn handle_set(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
     let args: SetArgs = match serde_json::from_value(args) {
         Ok(a) => a,
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
@@ -423,11 +544,7 @@ fn handle_set(workspace_path: &Path, args: Value) -> ToolCallResult {
     }
 
     if let Some(assignee) = args.assignee {
-        if assignee.is_empty() {
-            operations.push(SetOperation::new("assignee", Value::Null));
-        } else {
-            operations.push(SetOperation::new("assignee", assignee));
-        }
+        operations.push(SetOperation::assignee(Some(&assignee)));
     }
 
     if let Some(labels) = args.labels {
@@ -447,7 +564,7 @@ fn handle_set(workspace_path: &Path, args: Value) -> ToolCallResult {
         return ToolCallResult::error("No fields to update. Specify at least one of: state, assignee, labels, or fields.");
     }
 
-    let item = match ws.update_item(&args.uid, &operations) {
+    let item = match ws.audited(audit).update_item(&args.uid, &operations) {
         Ok(item) => item,
         Err(e) => return ToolCallResult::error(format!("Failed to update item: {e}")),
     };
@@ -466,7 +583,7 @@ struct LogArgs {
     message: String,
 }
 
-fn handle_log(workspace_path: &Path, args: Value) -> ToolCallResult {
+fn handle_log(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
     let args: LogArgs = match serde_json::from_value(args) {
         Ok(a) => a,
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
@@ -477,8 +594,8 @@ fn handle_log(workspace_path: &Path, args: Value) -> ToolCallResult {
         Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
     };
 
-    match ws.add_comment(&args.uid, &args.message) {
-        Ok(_) => ToolCallResult::text(format!("Added comment to {}", args.uid)),
+    match ws.audited(audit).add_comment(&args.uid, &args.message) {
+        Ok(()) => ToolCallResult::text(format!("Added comment to {}", args.uid)),
         Err(e) => ToolCallResult::error(format!("Failed to add comment: {e}")),
     }
 }
@@ -504,7 +621,7 @@ fn handle_events(workspace_path: &Path, args: Value) -> ToolCallResult {
         chrono::Utc::now() - chrono::Duration::days(i64::from(days))
     });
 
-    let events = match ws.read_events(&args.uid, since) {
+    let events = match ws.read_events(&args.uid, since, None, None) {
         Ok(events) => events,
         Err(e) => return ToolCallResult::error(format!("Failed to read events: {e}")),
     };
@@ -514,13 +631,13 @@ fn handle_events(workspace_path: &Path, args: Value) -> ToolCallResult {
     }
 
     let mut output = String::new();
-    output.push_str(&format!("Event history for {} ({} events):\n\n", args.uid, events.len()));
+    writeln!(output, "Event history for {} ({} events):\n", args.uid, events.len()).unwrap();
 
     for event in &events {
         let actor = event.actor.as_deref().unwrap_or("system");
         let time = event.timestamp.format("%Y-%m-%d %H:%M");
         let payload = format_payload(&event.payload);
-        output.push_str(&format!("[{time}] {}: {} - {}\n", event.event_type, actor, payload));
+        writeln!(output, "[{time}] {}: {} - {}", event.event_type, actor, payload).unwrap();
     }
 
     ToolCallResult::text(output)
@@ -531,7 +648,7 @@ struct AdvanceArgs {
     uid: String,
 }
 
-fn handle_advance(workspace_path: &Path, args: Value) -> ToolCallResult {
+fn handle_advance(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
     let args: AdvanceArgs = match serde_json::from_value(args) {
         Ok(a) => a,
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
@@ -548,23 +665,16 @@ fn handle_advance(workspace_path: &Path, args: Value) -> ToolCallResult {
     };
 
     let current_state = item.state.as_str();
-    let current_idx = STATE_WORKFLOW
-        .iter()
-        .position(|&s| s.eq_ignore_ascii_case(current_state));
-
-    let next_state = match current_idx {
-        Some(idx) if idx < STATE_WORKFLOW.len() - 1 => STATE_WORKFLOW[idx + 1],
-        Some(_) => {
-            return ToolCallResult::text(format!(
-                "{} is already at final state ({})",
-                args.uid, current_state
-            ));
-        }
-        None => "IN_PROGRESS", // Unknown state, move to IN_PROGRESS
+    let steps = ws.config().workflow.steps();
+    let Some(next_state) = Workflow::new(&steps).next(current_state) else {
+        return ToolCallResult::text(format!(
+            "{} is already at final state ({})",
+            args.uid, current_state
+        ));
     };
 
     let operations = vec![SetOperation::new("state", next_state)];
-    match ws.update_item(&args.uid, &operations) {
+    match ws.audited(audit).update_item(&args.uid, &operations) {
         Ok(_) => ToolCallResult::text(format!(
             "Advanced {}: {} → {}",
             args.uid, current_state, next_state
@@ -573,7 +683,7 @@ fn handle_advance(workspace_path: &Path, args: Value) -> ToolCallResult {
     }
 }
 
-fn handle_revert(workspace_path: &Path, args: Value) -> ToolCallResult {
+fn handle_revert(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
     let args: AdvanceArgs = match serde_json::from_value(args) {
         Ok(a) => a,
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
@@ -590,23 +700,16 @@ fn handle_revert(workspace_path: &Path, args: Value) -> ToolCallResult {
     };
 
     let current_state = item.state.as_str();
-    let current_idx = STATE_WORKFLOW
-        .iter()
-        .position(|&s| s.eq_ignore_ascii_case(current_state));
-
-    let prev_state = match current_idx {
-        Some(idx) if idx > 0 => STATE_WORKFLOW[idx - 1],
-        Some(_) => {
-            return ToolCallResult::text(format!(
-                "{} is already at initial state ({})",
-                args.uid, current_state
-            ));
-        }
-        None => "TODO", // Unknown state, move to TODO
+    let steps = ws.config().workflow.steps();
+    let Some(prev_state) = Workflow::new(&steps).previous(current_state) else {
+        return ToolCallResult::text(format!(
+            "{} is already at initial state ({})",
+            args.uid, current_state
+        ));
     };
 
     let operations = vec![SetOperation::new("state", prev_state)];
-    match ws.update_item(&args.uid, &operations) {
+    match ws.audited(audit).update_item(&args.uid, &operations) {
         Ok(_) => ToolCallResult::text(format!(
             "Reverted {}: {} → {}",
             args.uid, current_state, prev_state
@@ -615,26 +718,67 @@ fn handle_revert(workspace_path: &Path, args: Value) -> ToolCallResult {
     }
 }
 
-fn format_payload(payload: &worky_core::EventPayload) -> String {
-    match payload {
-        worky_core::EventPayload::StateChange(p) => format!("{} → {}", p.from, p.to),
-        worky_core::EventPayload::FieldChange(p) => format!("{} = {}", p.path, p.new_value),
-        worky_core::EventPayload::AssigneeChange(p) => {
-            format!(
-                "{} → {}",
-                p.from.as_deref().unwrap_or("(none)"),
-                p.to.as_deref().unwrap_or("(none)")
-            )
-        }
-        worky_core::EventPayload::Label(p) => p.label.clone(),
-        worky_core::EventPayload::Comment(p) => {
-            if p.message.len() > 50 {
-                format!("{}...", &p.message[..47])
-            } else {
-                p.message.clone()
-            }
-        }
-        worky_core::EventPayload::AiAction(p) => format!("{}: {}", p.tool, p.action),
-        worky_core::EventPayload::Generic(v) => v.to_string(),
+
+#[derive(Deserialize)]
+struct PatchArgs {
+    uid: String,
+    patch: Value,
+}
+
+fn handle_patch(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
+    let args: PatchArgs = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
+    };
+
+    if !args.patch.is_object() {
+        return ToolCallResult::error("The patch must be a JSON object.");
+    }
+
+    let ws = match Workspace::open(workspace_path) {
+        Ok(ws) => ws,
+        Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
+    };
+
+    let item = match ws.audited(audit).patch_item(&args.uid, &args.patch) {
+        Ok(item) => item,
+        Err(e) => return ToolCallResult::error(format!("Failed to patch item: {e}")),
+    };
+
+    ToolCallResult::text(format!(
+        "Patched work item: {}\nState: {}\nAssignee: {}",
+        item.uid,
+        item.state,
+        item.assignee.as_deref().unwrap_or("-")
+    ))
+}
+
+#[derive(Deserialize)]
+struct DeleteArgs {
+    uid: String,
+    confirm: bool,
+}
+
+fn handle_delete(workspace_path: &Path, args: Value, audit: AiAudit) -> ToolCallResult {
+    let args: DeleteArgs = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {e}")),
+    };
+
+    if !args.confirm {
+        return ToolCallResult::error(format!(
+            "Not deleting {}: set confirm to true to delete it permanently.",
+            args.uid
+        ));
+    }
+
+    let ws = match Workspace::open(workspace_path) {
+        Ok(ws) => ws,
+        Err(e) => return ToolCallResult::error(format!("Failed to open workspace: {e}")),
+    };
+
+    match ws.audited(audit).delete_item(&args.uid) {
+        Ok(uid) => ToolCallResult::text(format!("Deleted work item: {uid}")),
+        Err(e) => ToolCallResult::error(format!("Failed to delete item: {e}")),
     }
 }
@@ -1,9 +1,22 @@
 //! Output formatting for the CLI.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use comfy_table::{
+    presets::UTF8_FULL_CONDENSED, ColumnConstraint, ContentArrangement, Table, Width,
+};
+use console::{Style, Term};
 use serde::Serialize;
+use serde_json::Value;
 use std::fmt::Write;
-use worky_core::{WorkEvent, WorkItem};
+use std::sync::OnceLock;
+use worky_core::{display::format_payload, Priority, TimeFormat, WorkEvent, WorkItem};
+use worky_fs::actor::Actor;
+use worky_fs::config::LabelSettings;
+use worky_fs::workspace::{TimeEntry, UpdatePlan};
+use worky_fs::{
+    Artifact, CompactSummary, DoctorReport, FieldProvenance, ImportSummary, ItemTemplate,
+    WorkspaceStats,
+};
 
 /// Output format for CLI responses.
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
@@ -13,31 +26,60 @@ pub enum OutputFormat {
     Human,
     /// JSON output
     Json,
+    /// Newline-delimited JSON, one compact value per line
+    Ndjson,
     /// YAML output
     Yaml,
+    /// Comma-separated values (RFC 4180)
+    Csv,
+    /// Tab-separated values
+    Tsv,
+    /// Bordered table fitted to the terminal, wrapping long text; single
+    /// records print as `human`
+    Table,
+}
+
+impl OutputFormat {
+    /// Field delimiter for tabular formats, `None` for structured ones.
+    const fn delimiter(self) -> Option<char> {
+        match self {
+            Self::Csv => Some(','),
+            Self::Tsv => Some('\t'),
+            Self::Human | Self::Json | Self::Ndjson | Self::Yaml | Self::Table => None,
+        }
+    }
+
+    /// Whether the output is meant for people rather than programs.
+    pub const fn is_human(self) -> bool {
+        matches!(self, Self::Human | Self::Table)
+    }
 }
 
 /// Print output in the specified format.
-pub fn print<T: Serialize + HumanDisplay>(value: &T, format: OutputFormat) {
+pub fn print<T: Serialize + HumanDisplay + TableRow>(value: &T, format: OutputFormat) {
     match format {
-        OutputFormat::Human => println!("{}", value.human_display()),
+        OutputFormat::Human | OutputFormat::Table => println!("{}", value.human_display()),
         OutputFormat::Json => {
             println!(
                 "{}",
                 serde_json::to_string_pretty(value).expect("Failed to serialize to JSON")
             );
         }
+        OutputFormat::Ndjson => print_ndjson(std::slice::from_ref(value)),
         OutputFormat::Yaml => {
             println!(
                 "{}",
                 serde_yaml::to_string(value).expect("Failed to serialize to YAML")
             );
         }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            print_delimited(std::slice::from_ref(value), format);
+        }
     }
 }
 
 /// Print a list in the specified format.
-pub fn print_list<T: Serialize + HumanDisplay>(values: &[T], format: OutputFormat) {
+pub fn print_list<T: Serialize + HumanDisplay + TableRow>(values: &[T], format: OutputFormat) {
     match format {
         OutputFormat::Human => {
             for value in values {
@@ -50,12 +92,18 @@ pub fn print_list<T: Serialize + HumanDisplay>(values: &[T], format: OutputForma
                 serde_json::to_string_pretty(values).expect("Failed to serialize to JSON")
             );
         }
+        OutputFormat::Ndjson => print_ndjson(values),
         OutputFormat::Yaml => {
             println!(
                 "{}",
                 serde_yaml::to_string(values).expect("Failed to serialize to YAML")
             );
         }
+        OutputFormat::Csv | OutputFormat::Tsv => print_delimited(values, format),
+        OutputFormat::Table => {
+            let rows = values.iter().map(TableRow::row).collect();
+            println!("{}", table(T::headers(), rows));
+        }
     }
 }
 
@@ -73,33 +121,41 @@ pub fn print_item_list(items: &[WorkItemSummary], format: OutputFormat) {
             let state_width = items.iter().map(|i| i.state.len()).max().unwrap_or(5).max(5);
             let assignee_width = items
                 .iter()
-                .map(|i| i.assignee.as_ref().map(|a| a.len()).unwrap_or(1))
+                .map(|i| i.assignee.as_ref().map_or(1, std::string::String::len))
                 .max()
                 .unwrap_or(1)
                 .max(8);
 
             // Print header
             println!(
-                "{:<uid_w$}  {:<state_w$}  {:<assignee_w$}  {}",
+                "{:<uid_w$}  {:<state_w$}  {:<assignee_w$}  {:<UPDATED_WIDTH$}  TITLE",
                 "UID",
                 "STATE",
                 "ASSIGNEE",
-                "TITLE",
+                "UPDATED",
                 uid_w = uid_width,
                 state_w = state_width,
                 assignee_w = assignee_width
             );
-            let total_width = uid_width + state_width + assignee_width + 20;
+            let total_width = uid_width + state_width + assignee_width + UPDATED_WIDTH + 22;
             println!("{}", "-".repeat(total_width));
 
+            let now = Utc::now();
             for item in items {
                 let assignee = item.assignee.as_deref().unwrap_or("-");
+                let updated = format_elapsed(now - item.updated_at);
+                let (row, state) = if item.closed {
+                    (Style::new().dim(), state_style(&item.state).dim())
+                } else {
+                    (Style::new(), state_style(&item.state))
+                };
                 println!(
-                    "{:<uid_w$}  {:<state_w$}  {:<assignee_w$}  {}",
-                    item.uid,
-                    item.state,
-                    assignee,
-                    item.title,
+                    "{:<uid_w$}  {:<state_w$}  {:<assignee_w$}  {:<UPDATED_WIDTH$}  {}",
+                    row.apply_to(&item.uid),
+                    state.apply_to(&item.state),
+                    row.apply_to(assignee),
+                    row.apply_to(updated),
+                    row.apply_to(&item.title),
                     uid_w = uid_width,
                     state_w = state_width,
                     assignee_w = assignee_width
@@ -112,25 +168,172 @@ pub fn print_item_list(items: &[WorkItemSummary], format: OutputFormat) {
                 serde_json::to_string_pretty(items).expect("Failed to serialize to JSON")
             );
         }
+        OutputFormat::Ndjson => print_ndjson(items),
         OutputFormat::Yaml => {
             println!(
                 "{}",
                 serde_yaml::to_string(items).expect("Failed to serialize to YAML")
             );
         }
+        OutputFormat::Csv | OutputFormat::Tsv => print_delimited(items, format),
+        OutputFormat::Table => {
+            if items.is_empty() {
+                println!("No work items found.");
+                return;
+            }
+
+            let now = Utc::now();
+            let rows = items
+                .iter()
+                .map(|item| {
+                    vec![
+                        item.uid.clone(),
+                        item.state.clone(),
+                        item.assignee.clone().unwrap_or_else(|| "-".to_string()),
+                        format_elapsed(now - item.updated_at),
+                        item.title.clone(),
+                    ]
+                })
+                .collect();
+            let mut table = table(&["UID", "STATE", "ASSIGNEE", "UPDATED", "TITLE"], rows);
+
+            // State, assignee, and age stay on one line; long UIDs and titles
+            // wrap, with the title kept wide enough to read
+            for column in table.column_iter_mut().skip(1).take(3) {
+                column.set_constraint(ColumnConstraint::ContentWidth);
+            }
+            if let Some(title) = table.column_mut(4) {
+                title.set_constraint(ColumnConstraint::LowerBoundary(Width::Fixed(20)));
+            }
+            println!("{table}");
+        }
+    }
+}
+
+/// A bordered table with a header row, as wide as the terminal if stdout
+/// is one. Columns that don't fit are wrapped.
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> Table {
+    let mut table = Table::new();
+    table
+        .load_style(UTF8_FULL_CONDENSED.with_rounded_corners())
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(headers.to_vec())
+        .add_rows(rows);
+
+    if let Some((_, width)) = Term::stdout().size_checked() {
+        table.set_width(width);
+    }
+    table
+}
+
+/// Print rows as delimiter-separated values with a header row.
+fn print_delimited<T: TableRow>(values: &[T], format: OutputFormat) {
+    let delimiter = format.delimiter().unwrap_or(',');
+    let header: Vec<String> = T::headers().iter().map(ToString::to_string).collect();
+    println!("{}", join_record(&header, delimiter));
+    for value in values {
+        println!("{}", join_record(&value.row(), delimiter));
+    }
+}
+
+/// Join fields into a single record, quoting where needed.
+fn join_record(fields: &[String], delimiter: char) -> String {
+    fields
+        .iter()
+        .map(|f| escape_field(f, delimiter))
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string())
+}
+
+/// Quote a field if it contains the delimiter, quotes, or line breaks (RFC 4180).
+fn escape_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Print one compact JSON value per line.
+fn print_ndjson<T: Serialize>(values: &[T]) {
+    for value in values {
+        println!(
+            "{}",
+            serde_json::to_string(value).expect("Failed to serialize to JSON")
+        );
     }
 }
 
 /// Print a success message.
+///
+/// Human formats print nothing with `--quiet`.
 pub fn print_success(message: &str, format: OutputFormat) {
     match format {
-        OutputFormat::Human => println!("{message}"),
-        OutputFormat::Json => {
+        OutputFormat::Human | OutputFormat::Table if is_quiet() => {}
+        OutputFormat::Human | OutputFormat::Table => println!("{message}"),
+        OutputFormat::Json | OutputFormat::Ndjson => {
             println!(r#"{{"status": "ok", "message": "{message}"}}"#);
         }
         OutputFormat::Yaml => {
             println!("status: ok\nmessage: {message}");
         }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let delimiter = format.delimiter().unwrap_or(',');
+            println!("{}", join_record(&["status".into(), "message".into()], delimiter));
+            println!("{}", join_record(&["ok".into(), message.to_string()], delimiter));
+        }
+    }
+}
+
+/// A decorative line for people, e.g. `TODO → IN_PROGRESS` above an
+/// updated item. Printed only for human formats without `--quiet`.
+pub fn print_note(note: impl std::fmt::Display, format: OutputFormat) {
+    if format.is_human() && !is_quiet() {
+        println!("{note}");
+    }
+}
+
+/// Print the events a `--dry-run` update would append.
+pub fn print_dry_run(uid: &str, plan: &UpdatePlan, format: OutputFormat) {
+    match format {
+        OutputFormat::Human | OutputFormat::Table => {
+            print_note(
+                Style::new()
+                    .yellow()
+                    .bold()
+                    .apply_to("dry run — no changes written"),
+                format,
+            );
+            if plan.events.is_empty() {
+                println!("{uid}: nothing would change");
+            }
+            for event in &plan.events {
+                println!("{uid} {}", event.human_display());
+            }
+        }
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let report = serde_json::json!({
+                "dry_run": true,
+                "uid": uid,
+                "events": plan.events,
+                "item": plan.item,
+            });
+            if matches!(format, OutputFormat::Json) {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).expect("Failed to serialize to JSON")
+                );
+            } else {
+                println!(
+                    "{}",
+                    serde_yaml::to_string(&report).expect("Failed to serialize to YAML")
+                );
+            }
+        }
+        // One record per event, as `events` prints them
+        OutputFormat::Ndjson | OutputFormat::Csv | OutputFormat::Tsv => {
+            print_list(&plan.events, format);
+        }
     }
 }
 
@@ -139,20 +342,314 @@ pub trait HumanDisplay {
     fn human_display(&self) -> String;
 }
 
+/// Trait for flat tabular (CSV/TSV) display.
+pub trait TableRow {
+    /// Column names for the header row.
+    fn headers() -> &'static [&'static str];
+    /// Field values in header order.
+    fn row(&self) -> Vec<String>;
+}
+
+/// Width of the human listing's UPDATED column.
+const UPDATED_WIDTH: usize = 7;
+
+/// Columns used for work items in tabular output.
+const ITEM_COLUMNS: &[&str] = &[
+    "uid",
+    "title",
+    "state",
+    "assignee",
+    "labels",
+    "created_at",
+    "updated_at",
+];
+
+impl TableRow for WorkItem {
+    fn headers() -> &'static [&'static str] {
+        ITEM_COLUMNS
+    }
+
+    fn row(&self) -> Vec<String> {
+        // Same columns as the summary, so list and show exports line up
+        WorkItemSummary::from(self).row()
+    }
+}
+
+impl TableRow for WorkEvent {
+    fn headers() -> &'static [&'static str] {
+        &["id", "type", "timestamp", "actor", "payload"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.event_type.to_string(),
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.actor.clone().unwrap_or_default(),
+            format_payload(&self.payload),
+        ]
+    }
+}
+
+impl HumanDisplay for FeedEntry {
+    fn human_display(&self) -> String {
+        let actor = self.event.actor.as_deref().unwrap_or("system");
+        let time = format_time(&self.event.timestamp);
+
+        format!(
+            "[{time}] {uid} {actor}: {type} - {payload}",
+            uid = self.uid,
+            type = self.event.event_type,
+            payload = format_payload(&self.event.payload)
+        )
+    }
+}
+
+impl HumanDisplay for BlameEntry {
+    fn human_display(&self) -> String {
+        let actor = self.provenance.actor.as_deref().unwrap_or("system");
+        format!("{}: {actor} @ {}", self.path, format_time(&self.provenance.at))
+    }
+}
+
+impl TableRow for BlameEntry {
+    fn headers() -> &'static [&'static str] {
+        &["path", "actor", "at"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.path.clone(),
+            self.provenance.actor.clone().unwrap_or_default(),
+            self.provenance.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ]
+    }
+}
+
+impl HumanDisplay for TimeEntry {
+    fn human_display(&self) -> String {
+        let mut line = format!(
+            "{}  {:>7}  {}",
+            format_time(&self.at),
+            format_minutes(self.minutes),
+            self.actor.as_deref().unwrap_or("system")
+        );
+        if let Some(note) = &self.note {
+            write!(line, "  {note}").unwrap();
+        }
+        line
+    }
+}
+
+impl TableRow for TimeEntry {
+    fn headers() -> &'static [&'static str] {
+        &["at", "minutes", "actor", "note"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.minutes.to_string(),
+            self.actor.clone().unwrap_or_default(),
+            self.note.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl HumanDisplay for CountEntry {
+    fn human_display(&self) -> String {
+        format!("{:>5}  {}", self.count, self.name)
+    }
+}
+
+impl TableRow for CountEntry {
+    fn headers() -> &'static [&'static str] {
+        &["name", "count"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.count.to_string()]
+    }
+}
+
+impl HumanDisplay for ItemCount {
+    fn human_display(&self) -> String {
+        self.count.to_string()
+    }
+}
+
+impl TableRow for ItemCount {
+    fn headers() -> &'static [&'static str] {
+        &["count"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.count.to_string()]
+    }
+}
+
+impl HumanDisplay for ConfigEntry {
+    fn human_display(&self) -> String {
+        format!("{} = {}", self.key, self.value)
+    }
+}
+
+impl TableRow for ConfigEntry {
+    fn headers() -> &'static [&'static str] {
+        &["key", "value"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let value = match &self.value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        vec![self.key.clone(), value]
+    }
+}
+
+impl HumanDisplay for CompactSummary {
+    fn human_display(&self) -> String {
+        format!("{}: {} → {} events", self.uid, self.before, self.after)
+    }
+}
+
+impl TableRow for CompactSummary {
+    fn headers() -> &'static [&'static str] {
+        &["uid", "before", "after"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.uid.clone(), self.before.to_string(), self.after.to_string()]
+    }
+}
+
+impl HumanDisplay for Actor {
+    fn human_display(&self) -> String {
+        format!("{} (from {})", self.name, self.source)
+    }
+}
+
+impl TableRow for Actor {
+    fn headers() -> &'static [&'static str] {
+        &["actor", "source"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.source.to_string()]
+    }
+}
+
+impl HumanDisplay for ItemTemplate {
+    fn human_display(&self) -> String {
+        let mut out = String::new();
+        if let Some(title) = &self.title {
+            writeln!(out, "Title:     {title}").unwrap();
+        }
+        if let Some(state) = &self.state {
+            writeln!(out, "State:     {state}").unwrap();
+        }
+        if !self.labels.is_empty() {
+            writeln!(out, "Labels:    {}", self.labels.join(", ")).unwrap();
+        }
+        if let Some(description) = &self.description {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(description);
+        }
+        out.trim_end().to_string()
+    }
+}
+
+impl TableRow for ItemTemplate {
+    fn headers() -> &'static [&'static str] {
+        &["title", "state", "labels", "description"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.title.clone().unwrap_or_default(),
+            self.state.clone().unwrap_or_default(),
+            self.labels.join(", "),
+            self.description.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl HumanDisplay for SavedFilterEntry {
+    fn human_display(&self) -> String {
+        format!("{}: {}", self.name, self.command_line())
+    }
+}
+
+impl TableRow for SavedFilterEntry {
+    fn headers() -> &'static [&'static str] {
+        &["name", "args"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.command_line()]
+    }
+}
+
+impl TableRow for FeedEntry {
+    fn headers() -> &'static [&'static str] {
+        &["uid", "id", "type", "timestamp", "actor", "payload"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let mut row = vec![self.uid.clone()];
+        row.extend(self.event.row());
+        row
+    }
+}
+
 impl HumanDisplay for WorkItem {
     fn human_display(&self) -> String {
         let mut out = String::new();
 
         writeln!(out, "UID:       {}", self.uid).unwrap();
         writeln!(out, "Title:     {}", self.title).unwrap();
-        writeln!(out, "State:     {}", self.state).unwrap();
+
+        if let Some(external_id) = &self.external_id {
+            writeln!(out, "External:  {external_id}").unwrap();
+        }
+
+        writeln!(out, "State:     {}", state_style(&self.state).apply_to(&self.state)).unwrap();
+
+        if let Some(priority) = self.priority {
+            writeln!(out, "Priority:  {}", priority_style(priority).apply_to(priority)).unwrap();
+        }
 
         if let Some(assignee) = &self.assignee {
             writeln!(out, "Assignee:  {assignee}").unwrap();
         }
 
         if !self.labels.is_empty() {
-            writeln!(out, "Labels:    {}", self.labels.join(", ")).unwrap();
+            let labels: Vec<String> = self
+                .labels
+                .iter()
+                .map(|label| label_style(label).apply_to(label).to_string())
+                .collect();
+            writeln!(out, "Labels:    {}", labels.join(", ")).unwrap();
+        }
+
+        if !self.blocked_by.is_empty() {
+            writeln!(out, "Blocked:   {}", self.blocked_by.join(", ")).unwrap();
+        }
+
+        if let Some(minutes) = self.time_spent_minutes {
+            writeln!(out, "Time:      {}", format_minutes(minutes)).unwrap();
+        }
+
+        if !self.checklist.is_empty() {
+            let (done, total) = self.checklist_progress();
+            writeln!(out, "Checklist: {done}/{total}").unwrap();
+            for (i, step) in self.checklist.iter().enumerate() {
+                let mark = if step.done { 'x' } else { ' ' };
+                writeln!(out, "  {}. [{mark}] {}", i + 1, step.text).unwrap();
+            }
         }
 
         writeln!(out, "Created:   {}", format_time(&self.created_at)).unwrap();
@@ -182,27 +679,345 @@ impl HumanDisplay for WorkEvent {
     }
 }
 
-fn format_time(dt: &DateTime<Utc>) -> String {
-    dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+impl HumanDisplay for ImportSummary {
+    fn human_display(&self) -> String {
+        format!(
+            "Imported {} item(s): {} skipped, {} failed",
+            self.created, self.skipped, self.failed
+        )
+    }
+}
+
+impl TableRow for ImportSummary {
+    fn headers() -> &'static [&'static str] {
+        &["created", "skipped", "failed"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.created.to_string(),
+            self.skipped.to_string(),
+            self.failed.to_string(),
+        ]
+    }
+}
+
+impl HumanDisplay for DoctorReport {
+    fn human_display(&self) -> String {
+        let mut out = String::new();
+
+        for finding in &self.findings {
+            let status = if finding.fixed { " (fixed)" } else { "" };
+            writeln!(out, "fs:{}: {}{status}", finding.slug, finding.problem).unwrap();
+        }
+
+        let fixed = self.findings.len() - self.remaining();
+        write!(
+            out,
+            "Checked {} item(s): {} problem(s), {fixed} fixed",
+            self.checked,
+            self.findings.len()
+        )
+        .unwrap();
+
+        out
+    }
+}
+
+impl TableRow for DoctorReport {
+    fn headers() -> &'static [&'static str] {
+        &["checked", "problems", "remaining"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.checked.to_string(),
+            self.findings.len().to_string(),
+            self.remaining().to_string(),
+        ]
+    }
+}
+
+impl HumanDisplay for Artifact {
+    fn human_display(&self) -> String {
+        format!("{}  ({} bytes)", self.name, self.size)
+    }
 }
 
-fn format_payload(payload: &worky_core::EventPayload) -> String {
-    match payload {
-        worky_core::EventPayload::StateChange(p) => format!("{} → {}", p.from, p.to),
-        worky_core::EventPayload::FieldChange(p) => {
-            format!("{} = {}", p.path, p.new_value)
+impl TableRow for Artifact {
+    fn headers() -> &'static [&'static str] {
+        &["name", "size"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.size.to_string()]
+    }
+}
+
+impl HumanDisplay for WorkspaceStats {
+    fn human_display(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "Items:     {} ({} open)", self.total, self.open).unwrap();
+        if let Some(days) = self.average_open_age_days {
+            writeln!(out, "Open age:  {days:.1} days average").unwrap();
         }
-        worky_core::EventPayload::AssigneeChange(p) => {
-            format!(
-                "{} → {}",
-                p.from.as_deref().unwrap_or("(none)"),
-                p.to.as_deref().unwrap_or("(none)")
-            )
+
+        let mut assignees: Vec<(&str, usize)> = self
+            .by_assignee
+            .iter()
+            .map(|(k, v)| (k.as_str(), *v))
+            .collect();
+        if self.unassigned > 0 {
+            assignees.push(("-", self.unassigned));
         }
-        worky_core::EventPayload::Label(p) => p.label.clone(),
-        worky_core::EventPayload::Comment(p) => p.message.clone(),
-        worky_core::EventPayload::AiAction(p) => format!("{}: {}", p.tool, p.action),
-        worky_core::EventPayload::Generic(v) => v.to_string(),
+
+        let states: Vec<(&str, usize)> = self.by_state.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        let labels: Vec<(&str, usize)> = self.by_label.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+
+        write_count_table(&mut out, "STATE", &states);
+        write_count_table(&mut out, "ASSIGNEE", &assignees);
+        write_count_table(&mut out, "LABEL", &labels);
+
+        out
+    }
+}
+
+/// Append a two-column name/count table with aligned columns.
+fn write_count_table(out: &mut String, heading: &str, rows: &[(&str, usize)]) {
+    if rows.is_empty() {
+        return;
+    }
+
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(heading.len());
+
+    writeln!(out).unwrap();
+    writeln!(out, "{heading:<width$}  COUNT").unwrap();
+    for (name, count) in rows {
+        writeln!(out, "{name:<width$}  {count:>5}").unwrap();
+    }
+}
+
+impl TableRow for WorkspaceStats {
+    fn headers() -> &'static [&'static str] {
+        &["total", "open", "unassigned", "average_open_age_days"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.total.to_string(),
+            self.open.to_string(),
+            self.unassigned.to_string(),
+            self.average_open_age_days
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+/// Color for a state, matching the board palette.
+///
+/// `console` turns styling off for non-TTY stdout and when `NO_COLOR` is set,
+/// so this is only ever visible in an interactive terminal.
+fn state_style(state: &str) -> Style {
+    match state.to_ascii_uppercase().as_str() {
+        "TODO" => Style::new().blue(),
+        "IN_PROGRESS" => Style::new().yellow(),
+        "IN_REVIEW" => Style::new().magenta(),
+        "BLOCKED" => Style::new().red(),
+        "DONE" => Style::new().green(),
+        _ => Style::new(),
+    }
+}
+
+fn priority_style(priority: Priority) -> Style {
+    match priority {
+        Priority::Critical => Style::new().red().bold(),
+        Priority::High => Style::new().red(),
+        Priority::Medium => Style::new().yellow(),
+        Priority::Low => Style::new().dim(),
+    }
+}
+
+/// A label in its configured color, approximated in the 256-color palette.
+fn label_style(label: &str) -> Style {
+    // Nearest of the six levels per channel in the 6x6x6 color cube
+    let level = |channel: u8| match channel {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (channel - 35) / 40,
+    };
+    label_colors().rgb(label).map_or_else(Style::new, |[r, g, b]| {
+        Style::new().color256(16 + 36 * level(r) + 6 * level(g) + level(b))
+    })
+}
+
+/// Label colors from the workspace config, set once at startup.
+static LABEL_COLORS: OnceLock<LabelSettings> = OnceLock::new();
+
+/// Color labels with `labels` from now on. Only the first call counts.
+pub fn set_label_colors(labels: LabelSettings) {
+    let _ = LABEL_COLORS.set(labels);
+}
+
+fn label_colors() -> &'static LabelSettings {
+    LABEL_COLORS.get_or_init(LabelSettings::default)
+}
+
+/// Display zone and pattern for timestamps, set once at startup.
+static TIME_FORMAT: OnceLock<TimeFormat> = OnceLock::new();
+
+/// Show timestamps with `format` from now on. Only the first call counts.
+pub fn set_time_format(format: TimeFormat) {
+    let _ = TIME_FORMAT.set(format);
+}
+
+/// The timestamp format set at startup, or UTC if none was set.
+pub fn time_format() -> &'static TimeFormat {
+    TIME_FORMAT.get_or_init(TimeFormat::default)
+}
+
+/// Whether `--quiet` was given, set once at startup.
+static QUIET: OnceLock<bool> = OnceLock::new();
+
+/// Leave out decorative lines from now on. Only the first call counts.
+pub fn set_quiet(quiet: bool) {
+    let _ = QUIET.set(quiet);
+}
+
+/// Whether decorative lines are left out.
+pub fn is_quiet() -> bool {
+    QUIET.get().copied().unwrap_or(false)
+}
+
+fn format_time(dt: &DateTime<Utc>) -> String {
+    time_format().format(dt)
+}
+
+/// Logged time as hours and minutes, e.g. `1h 30m`, `2h`, `45m`.
+pub fn format_minutes(minutes: u64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+/// Compact elapsed time in its largest whole unit, e.g. `7d`, `3h`, `12m`.
+fn format_elapsed(elapsed: Duration) -> String {
+    if elapsed.num_days() > 0 {
+        format!("{}d", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}m", elapsed.num_minutes().max(0))
+    }
+}
+
+/// An event in the workspace-wide activity feed.
+#[derive(Debug, Serialize)]
+pub struct FeedEntry {
+    /// UID of the item the event belongs to.
+    pub uid: String,
+    pub event: WorkEvent,
+}
+
+/// The last change to one field, for `worky blame`.
+#[derive(Debug, Serialize)]
+pub struct BlameEntry {
+    /// Field path, e.g. `state` or `fields.estimate`.
+    pub path: String,
+    #[serde(flatten)]
+    pub provenance: FieldProvenance,
+}
+
+/// A label or assignee and how many items have it, for `worky labels` and
+/// `worky assignees`.
+#[derive(Debug, Serialize)]
+pub struct CountEntry {
+    pub name: String,
+    pub count: usize,
+}
+
+/// How many items matched, for `worky list --count`.
+#[derive(Debug, Serialize)]
+pub struct ItemCount {
+    pub count: usize,
+}
+
+/// One workspace setting, for `worky config`.
+#[derive(Debug, Serialize)]
+pub struct ConfigEntry {
+    /// Dotted key, e.g. `defaults.state`.
+    pub key: String,
+    pub value: Value,
+}
+
+/// A named filter, for `worky filter list`.
+#[derive(Debug, Serialize)]
+pub struct SavedFilterEntry {
+    pub name: String,
+    /// `worky list` flags, as saved.
+    pub args: Vec<String>,
+}
+
+impl SavedFilterEntry {
+    /// The flags as they would be typed, quoting ones with spaces.
+    fn command_line(&self) -> String {
+        self.args
+            .iter()
+            .map(|arg| {
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("'{}'", arg.replace('\'', "'\\''"))
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// What `advance` or `revert` did: the state change and the updated item.
+#[derive(Debug, Serialize)]
+pub struct TransitionResult {
+    pub old_state: String,
+    pub new_state: String,
+    pub item: WorkItem,
+    /// Moved back a state; the arrow is yellow rather than green.
+    #[serde(skip)]
+    pub reverted: bool,
+}
+
+impl HumanDisplay for TransitionResult {
+    fn human_display(&self) -> String {
+        let item = self.item.human_display();
+        if is_quiet() {
+            return item;
+        }
+        let color = if self.reverted { Style::new().yellow() } else { Style::new().green() };
+        let arrow = format!("  {} → {}", self.old_state, self.new_state);
+        format!("{}\n{item}", color.bold().apply_to(arrow))
+    }
+}
+
+impl TableRow for TransitionResult {
+    fn headers() -> &'static [&'static str] {
+        &["uid", "title", "old_state", "new_state"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.item.uid.clone(),
+            self.item.title.clone(),
+            self.old_state.clone(),
+            self.new_state.clone(),
+        ]
     }
 }
 
@@ -213,6 +1028,12 @@ pub struct WorkItemSummary {
     pub title: String,
     pub state: String,
     pub assignee: Option<String>,
+    pub labels: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// In a terminal state; dimmed in human output.
+    #[serde(skip)]
+    pub closed: bool,
 }
 
 impl From<&WorkItem> for WorkItemSummary {
@@ -222,10 +1043,32 @@ impl From<&WorkItem> for WorkItemSummary {
             title: item.title.clone(),
             state: item.state.clone(),
             assignee: item.assignee.clone(),
+            labels: item.labels.clone(),
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+            closed: false,
         }
     }
 }
 
+impl TableRow for WorkItemSummary {
+    fn headers() -> &'static [&'static str] {
+        ITEM_COLUMNS
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.uid.clone(),
+            self.title.clone(),
+            self.state.clone(),
+            self.assignee.clone().unwrap_or_default(),
+            self.labels.join(";"),
+            self.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ]
+    }
+}
+
 impl HumanDisplay for WorkItemSummary {
     fn human_display(&self) -> String {
         let assignee = self.assignee.as_deref().unwrap_or("-");
@@ -236,50 +1079,87 @@ impl HumanDisplay for WorkItemSummary {
     }
 }
 
-/// Print a work item with its comments.
-pub fn print_item_with_comments(
+/// Print one comment for human output, with its ID for `log --edit`.
+fn print_comment(comment: &WorkEvent) {
+    let time = format_time(&comment.timestamp);
+    let actor = comment.actor.as_deref().unwrap_or("user");
+    if let worky_core::EventPayload::Comment(p) = &comment.payload {
+        let edited = if p.replaces.is_some() { ", edited" } else { "" };
+        let meta = Style::new()
+            .dim()
+            .apply_to(format!("({}{edited})", comment.id));
+        // Handle multi-line comments with proper indentation
+        let lines: Vec<&str> = p.message.lines().collect();
+        if lines.len() == 1 {
+            println!("  [{time}] {actor} {meta}: {}", p.message);
+        } else {
+            println!("  [{time}] {actor} {meta}:");
+            for line in lines {
+                println!("    {line}");
+            }
+        }
+    }
+}
+
+/// Print a work item with its comments, plus its full event timeline and
+/// notes when given.
+pub fn print_item_detail(
     item: &WorkItem,
     comments: &[WorkEvent],
+    events: Option<&[WorkEvent]>,
+    notes: Option<&str>,
     format: OutputFormat,
 ) {
     match format {
-        OutputFormat::Human => {
+        OutputFormat::Human | OutputFormat::Table => {
             println!("{}", item.human_display());
 
             if !comments.is_empty() {
                 println!("Comments:");
                 println!("{}", "-".repeat(60));
                 for comment in comments {
-                    let time = format_time(&comment.timestamp);
-                    let actor = comment.actor.as_deref().unwrap_or("user");
-                    if let worky_core::EventPayload::Comment(p) = &comment.payload {
-                        // Handle multi-line comments with proper indentation
-                        let lines: Vec<&str> = p.message.lines().collect();
-                        if lines.len() == 1 {
-                            println!("  [{time}] {actor}: {}", p.message);
-                        } else {
-                            println!("  [{time}] {actor}:");
-                            for line in lines {
-                                println!("    {line}");
-                            }
-                        }
-                    }
+                    print_comment(comment);
+                }
+            }
+
+            if let Some(events) = events {
+                println!("Events:");
+                println!("{}", "-".repeat(60));
+                for event in events {
+                    println!("  {}", event.human_display());
+                }
+            }
+
+            if let Some(notes) = notes {
+                println!("Notes:");
+                println!("{}", "-".repeat(60));
+                if notes.trim().is_empty() {
+                    println!("  (empty)");
+                } else {
+                    println!("{}", notes.trim_end());
                 }
             }
         }
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Yaml => {
             #[derive(serde::Serialize)]
             struct ItemWithComments<'a> {
                 #[serde(flatten)]
                 item: &'a WorkItem,
                 comments: Vec<CommentView<'a>>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                events: Option<&'a [WorkEvent]>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                notes: Option<&'a str>,
             }
 
             #[derive(serde::Serialize)]
             struct CommentView<'a> {
+                id: &'a str,
                 timestamp: &'a DateTime<Utc>,
                 actor: Option<&'a str>,
                 message: &'a str,
+                #[serde(skip_serializing_if = "std::ops::Not::not")]
+                edited: bool,
             }
 
             let comment_views: Vec<CommentView> = comments
@@ -287,9 +1167,11 @@ pub fn print_item_with_comments(
                 .filter_map(|e| {
                     if let worky_core::EventPayload::Comment(p) = &e.payload {
                         Some(CommentView {
+                            id: &e.id,
                             timestamp: &e.timestamp,
                             actor: e.actor.as_deref(),
                             message: &p.message,
+                            edited: p.replaces.is_some(),
                         })
                     } else {
                         None
@@ -300,39 +1182,28 @@ pub fn print_item_with_comments(
             let output = ItemWithComments {
                 item,
                 comments: comment_views,
+                events,
+                notes,
             };
 
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&output).expect("Failed to serialize to JSON")
-            );
-        }
-        OutputFormat::Yaml => {
-            // For YAML, just print item then comments section
-            println!(
-                "{}",
-                serde_yaml::to_string(item).expect("Failed to serialize to YAML")
-            );
-            if !comments.is_empty() {
-                println!("comments:");
-                for comment in comments {
-                    if let worky_core::EventPayload::Comment(p) = &comment.payload {
-                        let time = format_time(&comment.timestamp);
-                        let actor = comment.actor.as_deref().unwrap_or("user");
-                        println!("  - timestamp: {time}");
-                        println!("    actor: {actor}");
-                        // Handle multi-line in YAML
-                        if p.message.contains('\n') {
-                            println!("    message: |");
-                            for line in p.message.lines() {
-                                println!("      {line}");
-                            }
-                        } else {
-                            println!("    message: {}", p.message);
-                        }
-                    }
-                }
+            match format {
+                OutputFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&output).expect("Failed to serialize to JSON")
+                ),
+                OutputFormat::Ndjson => println!(
+                    "{}",
+                    serde_json::to_string(&output).expect("Failed to serialize to JSON")
+                ),
+                _ => print!(
+                    "{}",
+                    serde_yaml::to_string(&output).expect("Failed to serialize to YAML")
+                ),
             }
         }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            // Comments don't fit a flat row; fall back to the item alone
+            print_delimited(std::slice::from_ref(item), format);
+        }
     }
 }
@@ -0,0 +1,264 @@
+//! Live terminal view of item changes.
+
+use crate::output::{self, OutputFormat};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use console::style;
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+use worky_core::{display::format_payload, EventPayload, EventType, WorkEvent};
+use worky_fs::Workspace;
+
+/// Events file name within an item directory.
+const EVENTS_FILE: &str = "events.ndjson";
+/// Meta file name within an item directory.
+const META_FILE: &str = "meta.yml";
+
+/// Print a line for every event appended to an item until Ctrl+C.
+pub fn watch(path: &Path, state: Option<&str>, format: OutputFormat) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(watch_async(&ws, state, format))
+}
+
+async fn watch_async(ws: &Workspace, state: Option<&str>, format: OutputFormat) -> Result<()> {
+    let items_dir = ws.items_dir();
+    let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        match res {
+            // Reads (including our own) are not changes
+            Ok(event) if !event.kind.is_access() => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "File watcher error"),
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    watcher
+        .watch(&items_dir, RecursiveMode::Recursive)
+        .context("Failed to watch items directory")?;
+
+    // Only report events appended from now on
+    let mut offsets: HashMap<String, u64> = HashMap::new();
+    for entry in std::fs::read_dir(&items_dir)?.flatten() {
+        let events_path = entry.path().join(EVENTS_FILE);
+        if let Ok(metadata) = std::fs::metadata(&events_path) {
+            offsets.insert(entry.file_name().to_string_lossy().to_string(), metadata.len());
+        }
+    }
+
+    if format.is_human() {
+        println!(
+            "{}",
+            style(format!("Watching {} (Ctrl+C to stop)", items_dir.display())).dim()
+        );
+    }
+
+    let shutdown = worky_toolserver::shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        let changed = tokio::select! {
+            () = &mut shutdown => break,
+            changed = rx.recv() => changed,
+        };
+        let Some(changed) = changed else { break };
+
+        let Some(slug) = changed_slug(&changed) else {
+            continue;
+        };
+
+        let offset = offsets.entry(slug.clone()).or_insert(0);
+        let events_path = items_dir.join(&slug).join(EVENTS_FILE);
+        let events = match read_appended(&events_path, offset) {
+            Ok(events) => events,
+            Err(e) => {
+                debug!(slug = %slug, error = %e, "Skipping unreadable events file");
+                continue;
+            }
+        };
+
+        let uid = format!("fs:{slug}");
+        let current_state = state.and_then(|_| ws.get_item(&uid).ok()).map(|i| i.state);
+
+        for event in events {
+            if let Some(wanted) = state
+                && !involves_state(&event, current_state.as_deref(), wanted)
+            {
+                continue;
+            }
+            print_change(&uid, &event, format);
+        }
+    }
+
+    if format.is_human() {
+        println!("{}", style("Stopped watching").dim());
+    }
+    Ok(())
+}
+
+/// Print an item's events, then each new one as it is appended, until
+/// Ctrl+C.
+///
+/// Human output matches `worky events`; other formats print one JSON event
+/// per line so the stream can be piped.
+pub fn follow(
+    path: &Path,
+    uid: &str,
+    since: Option<DateTime<Utc>>,
+    types: &[EventType],
+    format: OutputFormat,
+) -> Result<()> {
+    let ws = Workspace::open(path).context("Failed to open workspace")?;
+    let item = ws.get_item(uid).context("Failed to get item")?;
+    let events_path = ws.events_path(&item.uid)?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(follow_async(&events_path, since, types, format))
+}
+
+async fn follow_async(
+    events_path: &Path,
+    since: Option<DateTime<Utc>>,
+    types: &[EventType],
+    format: OutputFormat,
+) -> Result<()> {
+    let item_dir = events_path.parent().context("Event log has no item directory")?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        match res {
+            Ok(event) if !event.kind.is_access() => {
+                let _ = tx.send(());
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "File watcher error"),
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    // The item directory, not the file, so a log that is replaced or
+    // created later is still seen
+    watcher
+        .watch(item_dir, RecursiveMode::NonRecursive)
+        .context("Failed to watch item directory")?;
+
+    let format = if format.is_human() {
+        OutputFormat::Human
+    } else {
+        OutputFormat::Ndjson
+    };
+    let wanted = |event: &WorkEvent| {
+        since.is_none_or(|s| event.timestamp >= s)
+            && (types.is_empty() || types.contains(&event.event_type))
+    };
+
+    // Read from the start through the same offset the updates use, so no
+    // event is shown twice or missed in between
+    let mut offset = 0;
+    let print_appended = |offset: &mut u64| match read_appended(events_path, offset) {
+        Ok(events) => {
+            let events: Vec<WorkEvent> = events.into_iter().filter(|e| wanted(e)).collect();
+            if !events.is_empty() {
+                output::print_list(&events, format);
+            }
+        }
+        Err(e) => debug!(error = %e, "Skipping unreadable events file"),
+    };
+    print_appended(&mut offset);
+
+    let shutdown = worky_toolserver::shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            () = &mut shutdown => break,
+            changed = rx.recv() => {
+                if changed.is_none() {
+                    break;
+                }
+                print_appended(&mut offset);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Slug of the item whose meta or events file `path` is.
+fn changed_slug(path: &Path) -> Option<String> {
+    let name = path.file_name()?;
+    if name != EVENTS_FILE && name != META_FILE {
+        return None;
+    }
+
+    let slug = path.parent()?.file_name()?;
+    Some(slug.to_string_lossy().to_string())
+}
+
+/// Parse complete lines appended since `offset`, advancing it past them.
+fn read_appended(path: &Path, offset: &mut u64) -> Result<Vec<WorkEvent>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    // Rewritten (e.g. by an import with overwrite); start again from the new end
+    if len < *offset {
+        *offset = len;
+        return Ok(Vec::new());
+    }
+
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut appended = String::new();
+    file.read_to_string(&mut appended)?;
+
+    // A partially written last line is picked up on the next change
+    let Some(end) = appended.rfind('\n') else {
+        return Ok(Vec::new());
+    };
+    *offset += end as u64 + 1;
+
+    Ok(appended[..end]
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Whether an event belongs in a `--state` filtered view: the item is in that
+/// state now, or the event moved it into or out of it.
+fn involves_state(event: &WorkEvent, current: Option<&str>, wanted: &str) -> bool {
+    if current.is_some_and(|s| s.eq_ignore_ascii_case(wanted)) {
+        return true;
+    }
+
+    matches!(
+        &event.payload,
+        EventPayload::StateChange(p)
+            if p.from.eq_ignore_ascii_case(wanted) || p.to.eq_ignore_ascii_case(wanted)
+    )
+}
+
+fn print_change(uid: &str, event: &WorkEvent, format: OutputFormat) {
+    if format.is_human() {
+        let time = event.timestamp.with_timezone(&output::time_format().zone());
+        println!(
+            "{} {}: {}",
+            style(time.format("%H:%M:%S")).dim(),
+            style(uid).bold(),
+            format_payload(&event.payload)
+        );
+    } else {
+        let line = serde_json::json!({ "uid": uid, "event": event });
+        println!("{line}");
+    }
+}
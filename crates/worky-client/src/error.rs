@@ -0,0 +1,100 @@
+//! Error types for the tool server client.
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Result type alias for client operations.
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// The JSON error body the tool server returns with a failed request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiError {
+    /// Stable machine-readable code (e.g. `item_not_found`).
+    pub code: String,
+    /// Human-readable message.
+    #[serde(rename = "error")]
+    pub message: String,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+/// Errors returned by [`crate::Client`].
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// 400: the request was malformed or failed validation.
+    #[error("bad request: {0}")]
+    BadRequest(ApiError),
+
+    /// 401 or 403: the token is missing or not accepted.
+    #[error("unauthorized: {0}")]
+    Unauthorized(ApiError),
+
+    /// 404: no such item, comment, or artifact.
+    #[error("not found: {0}")]
+    NotFound(ApiError),
+
+    /// 409: the change conflicts with the workspace (e.g. a dependency cycle).
+    #[error("conflict: {0}")]
+    Conflict(ApiError),
+
+    /// 413: the request body exceeds the server's limit.
+    #[error("payload too large: {0}")]
+    PayloadTooLarge(ApiError),
+
+    /// 429: too many requests; retry after the given number of seconds.
+    #[error("rate limited: {error}")]
+    RateLimited {
+        error: ApiError,
+        retry_after: Option<u64>,
+    },
+
+    /// Any other unsuccessful status.
+    #[error("server returned {status}: {error}")]
+    Status { status: u16, error: ApiError },
+
+    /// The base URL can't have paths appended to it.
+    #[error("invalid base URL: {0}")]
+    InvalidUrl(String),
+
+    /// The request could not be sent or the response could not be read.
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+impl ClientError {
+    /// Map an unsuccessful status and its error body to a variant.
+    pub(crate) const fn from_status(
+        status: u16,
+        error: ApiError,
+        retry_after: Option<u64>,
+    ) -> Self {
+        match status {
+            400 => Self::BadRequest(error),
+            401 | 403 => Self::Unauthorized(error),
+            404 => Self::NotFound(error),
+            409 => Self::Conflict(error),
+            413 => Self::PayloadTooLarge(error),
+            429 => Self::RateLimited { error, retry_after },
+            _ => Self::Status { status, error },
+        }
+    }
+
+    /// The server's error body, if the request reached it.
+    #[must_use]
+    pub const fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::BadRequest(error)
+            | Self::Unauthorized(error)
+            | Self::NotFound(error)
+            | Self::Conflict(error)
+            | Self::PayloadTooLarge(error)
+            | Self::RateLimited { error, .. }
+            | Self::Status { error, .. } => Some(error),
+            Self::InvalidUrl(_) | Self::Http(_) => None,
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Typed async client for the worky tool server.
+//!
+//! Wraps the HTTP API served by `worky tool serve` and returns the same
+//! `worky_core` types the server is built on.
+
+pub mod error;
+
+pub use error::{ApiError, ClientError, Result};
+
+use chrono::{DateTime, Utc};
+use reqwest::{header, Method, RequestBuilder, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use worky_core::{EventType, WorkEvent, WorkItem};
+use worky_fs::workspace::FilterQuery;
+
+/// Event filters for [`Client::get_events`].
+#[derive(Debug, Clone, Default)]
+pub struct EventsQuery {
+    /// Only events of these types; all types if empty.
+    pub types: Vec<EventType>,
+    /// Only events at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only events before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A connection to a running tool server.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base: Url,
+    token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    items: Vec<WorkItem>,
+}
+
+#[derive(Serialize)]
+struct SetFieldsRequest<'a> {
+    assignments: &'a [&'a str],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actor: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct AddEventRequest<'a> {
+    #[serde(rename = "type")]
+    event_type: EventType,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actor: Option<&'a str>,
+}
+
+impl Client {
+    /// Create a client for the server at `base_url`, e.g. `http://127.0.0.1:3000`.
+    ///
+    /// # Errors
+    /// Returns `ClientError::InvalidUrl` if `base_url` is not an HTTP(S) URL.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let base = Url::parse(base_url)
+            .map_err(|e| ClientError::InvalidUrl(format!("{base_url}: {e}")))?;
+
+        if base.cannot_be_a_base() || !matches!(base.scheme(), "http" | "https") {
+            return Err(ClientError::InvalidUrl(base_url.to_string()));
+        }
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base,
+            token: None,
+        })
+    }
+
+    /// Send `token` as a bearer token with every request.
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Items matching `query`.
+    ///
+    /// # Errors
+    /// Returns error if the request fails or the server rejects it.
+    pub async fn search(&self, query: &FilterQuery) -> Result<Vec<WorkItem>> {
+        let request = self.request(Method::POST, &["search"])?.json(query);
+        let response: SearchResponse = Self::send(request).await?;
+        Ok(response.items)
+    }
+
+    /// The item with `uid`.
+    ///
+    /// # Errors
+    /// Returns `ClientError::NotFound` if there is no such item, or error if
+    /// the request fails.
+    pub async fn get_item(&self, uid: &str) -> Result<WorkItem> {
+        Self::send(self.request(Method::GET, &["items", uid])?).await
+    }
+
+    /// Apply `key=value` assignments to an item and return the updated item.
+    ///
+    /// With an `actor`, the server also records who made the change.
+    ///
+    /// # Errors
+    /// Returns `ClientError::BadRequest` if an assignment is invalid,
+    /// `ClientError::NotFound` if there is no such item, or error if the
+    /// request fails.
+    pub async fn set_fields(
+        &self,
+        uid: &str,
+        assignments: &[&str],
+        actor: Option<&str>,
+    ) -> Result<WorkItem> {
+        let body = SetFieldsRequest { assignments, actor };
+        let request = self.request(Method::POST, &["items", uid, "set"])?.json(&body);
+        Self::send(request).await
+    }
+
+    /// An item's events, oldest first.
+    ///
+    /// # Errors
+    /// Returns `ClientError::NotFound` if there is no such item, or error if
+    /// the request fails.
+    pub async fn get_events(&self, uid: &str, query: &EventsQuery) -> Result<Vec<WorkEvent>> {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if !query.types.is_empty() {
+            let types: Vec<String> = query.types.iter().map(ToString::to_string).collect();
+            params.push(("type", types.join(",")));
+        }
+        if let Some(since) = query.since {
+            params.push(("since", since.to_rfc3339()));
+        }
+        if let Some(until) = query.until {
+            params.push(("until", until.to_rfc3339()));
+        }
+
+        let request = self
+            .request(Method::GET, &["items", uid, "events"])?
+            .query(&params);
+        Self::send(request).await
+    }
+
+    /// Add a comment to an item's event log.
+    ///
+    /// # Errors
+    /// Returns `ClientError::NotFound` if there is no such item, or error if
+    /// the request fails.
+    pub async fn add_event(&self, uid: &str, message: &str, actor: Option<&str>) -> Result<()> {
+        let body = AddEventRequest {
+            event_type: EventType::CommentAdded,
+            message,
+            actor,
+        };
+        let request = self
+            .request(Method::POST, &["items", uid, "events"])?
+            .json(&body);
+        Self::send::<serde::de::IgnoredAny>(request).await?;
+        Ok(())
+    }
+
+    /// A request to the base URL with `segments` appended, each percent-encoded.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder> {
+        let mut url = self.base.clone();
+        url.path_segments_mut()
+            .map_err(|()| ClientError::InvalidUrl(self.base.to_string()))?
+            .pop_if_empty()
+            .extend(segments);
+
+        let mut request = self.http.request(method, url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        Ok(request)
+    }
+
+    /// Send a request and decode a successful JSON response.
+    async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let retry_after = response
+            .headers()
+            .get(header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+
+        // Errors from outside the handlers (e.g. a proxy) may not be JSON
+        let body = response.text().await.unwrap_or_default();
+        let error = serde_json::from_str(&body).unwrap_or_else(|_| ApiError {
+            code: "unknown".to_string(),
+            message: body,
+        });
+
+        Err(ClientError::from_status(status.as_u16(), error, retry_after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::net::TcpListener;
+    use worky_fs::Workspace;
+    use worky_toolserver::Limits;
+
+    /// Start a tool server on a free port and return a client for it.
+    async fn start() -> (TempDir, Client) {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        ws.create_item("Fix login").unwrap();
+        ws.create_item("Write docs").unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let path = tmp.path().to_path_buf();
+        tokio::spawn(async move {
+            worky_toolserver::serve_on(listener, &path, Limits::default(), false, None)
+                .await
+                .unwrap();
+        });
+
+        let client = Client::new(&format!("http://{addr}"))
+            .unwrap()
+            .with_token("secret");
+        (tmp, client)
+    }
+
+    #[tokio::test]
+    async fn test_client_end_to_end() {
+        let (_tmp, client) = start().await;
+
+        let item = client.get_item("fs:fix-login").await.unwrap();
+        assert_eq!(item.title, "Fix login");
+
+        let item = client
+            .set_fields("fs:fix-login", &["state=IN_PROGRESS"], Some("bot"))
+            .await
+            .unwrap();
+        assert_eq!(item.state, "IN_PROGRESS");
+
+        let query = FilterQuery {
+            state: vec!["IN_PROGRESS".to_string()],
+            ..FilterQuery::default()
+        };
+        let items = client.search(&query).await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].uid, "fs:fix-login");
+
+        client
+            .add_event("fs:fix-login", "Looking into it", None)
+            .await
+            .unwrap();
+        let query = EventsQuery {
+            types: vec![EventType::CommentAdded, EventType::StateChanged],
+            ..EventsQuery::default()
+        };
+        let events = client.get_events("fs:fix-login", &query).await.unwrap();
+        assert_eq!(events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors() {
+        let (_tmp, client) = start().await;
+
+        let err = client.get_item("fs:missing").await.unwrap_err();
+        assert!(matches!(err, ClientError::NotFound(_)));
+        assert_eq!(err.api_error().unwrap().code, "item_not_found");
+
+        let err = client
+            .set_fields("fs:fix-login", &["no-equals-sign"], None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::BadRequest(_)));
+
+        assert!(matches!(
+            Client::new("not a url"),
+            Err(ClientError::InvalidUrl(_))
+        ));
+    }
+}
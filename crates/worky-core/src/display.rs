@@ -0,0 +1,154 @@
+//! Plain-text rendering shared by every front end.
+//!
+//! The CLI, MCP tools, and `watch` all describe events the same way; keeping
+//! the wording here stops them from drifting apart.
+
+use crate::error::{CoreError, Result};
+use crate::event::EventPayload;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+/// Time zone and `strftime` pattern for showing timestamps.
+///
+/// Timestamps are always stored in UTC; this only changes how they print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeFormat {
+    zone: Tz,
+    pattern: Option<String>,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self {
+            zone: Tz::UTC,
+            pattern: None,
+        }
+    }
+}
+
+impl TimeFormat {
+    /// Pattern used when none is configured, e.g. `2025-01-15 09:30:00 UTC`.
+    pub const DEFAULT_PATTERN: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+    /// Show times in the IANA zone `zone` (e.g. `America/New_York`), with
+    /// `pattern` in place of [`Self::DEFAULT_PATTERN`] if given.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if the zone is unknown or the pattern
+    /// has an invalid specifier.
+    pub fn new(zone: &str, pattern: Option<&str>) -> Result<Self> {
+        let zone = zone
+            .trim()
+            .parse::<Tz>()
+            .map_err(|_| CoreError::Validation(format!("unknown time zone '{zone}'")))?;
+
+        if let Some(pattern) = pattern
+            && StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error))
+        {
+            return Err(CoreError::Validation(format!(
+                "invalid time format '{pattern}'"
+            )));
+        }
+
+        Ok(Self {
+            zone,
+            pattern: pattern.map(String::from),
+        })
+    }
+
+    /// The display time zone.
+    #[must_use]
+    pub const fn zone(&self) -> Tz {
+        self.zone
+    }
+
+    /// `dt` in the display zone, with the configured pattern.
+    #[must_use]
+    pub fn format(&self, dt: &DateTime<Utc>) -> String {
+        self.format_or(dt, Self::DEFAULT_PATTERN)
+    }
+
+    /// `dt` in the display zone, with the configured pattern or `fallback`
+    /// if none is set. For views with their own compact default.
+    #[must_use]
+    pub fn format_or(&self, dt: &DateTime<Utc>, fallback: &str) -> String {
+        let pattern = self.pattern.as_deref().unwrap_or(fallback);
+        dt.with_timezone(&self.zone).format(pattern).to_string()
+    }
+}
+
+/// One-line description of an event payload.
+#[must_use]
+pub fn format_payload(payload: &EventPayload) -> String {
+    match payload {
+        EventPayload::StateChange(p) => format!("{} → {}", p.from, p.to),
+        EventPayload::FieldChange(p) => format!("{} = {}", p.path, p.new_value),
+        EventPayload::AssigneeChange(p) => format!(
+            "{} → {}",
+            p.from.as_deref().unwrap_or("(none)"),
+            p.to.as_deref().unwrap_or("(none)")
+        ),
+        EventPayload::Label(p) => p.label.clone(),
+        EventPayload::Comment(p) => p.message.clone(),
+        EventPayload::Created(p) => p.cloned_from.as_ref().map_or_else(
+            || format!("Created: {}", p.title),
+            |source| format!("Created: {} (cloned from {source})", p.title),
+        ),
+        EventPayload::AiAction(p) => format!("{}: {}", p.tool, p.action),
+        EventPayload::Generic(v) => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::WorkEvent;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn test_time_format_zone() {
+        let winter = Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2025, 7, 15, 14, 30, 0).unwrap();
+
+        assert_eq!(TimeFormat::default().format(&winter), "2025-01-15 14:30:00 UTC");
+
+        let new_york = TimeFormat::new("America/New_York", None).unwrap();
+        assert_eq!(new_york.format(&winter), "2025-01-15 09:30:00 EST");
+        assert_eq!(new_york.format(&summer), "2025-07-15 10:30:00 EDT");
+        assert_eq!(new_york.format_or(&winter, "%H:%M"), "09:30");
+
+        let custom = TimeFormat::new("America/New_York", Some("%d.%m.%Y %H:%M")).unwrap();
+        assert_eq!(custom.format(&winter), "15.01.2025 09:30");
+        assert_eq!(custom.format_or(&winter, "%H:%M"), "15.01.2025 09:30");
+
+        assert!(TimeFormat::new("Mars/Olympus_Mons", None).is_err());
+        assert!(TimeFormat::new("UTC", Some("%Y-%Q")).is_err());
+    }
+
+    #[test]
+    fn test_format_payload() {
+        let cases = [
+            (WorkEvent::state_changed("TODO", "DONE"), "TODO → DONE"),
+            (
+                WorkEvent::field_changed("fields.estimate", None, json!(3)),
+                "fields.estimate = 3",
+            ),
+            (
+                WorkEvent::assigned(None, Some("alice".to_string())),
+                "(none) → alice",
+            ),
+            (WorkEvent::label_added("bug"), "bug"),
+            (WorkEvent::ai_action("claude", "triage"), "claude: triage"),
+        ];
+        for (event, expected) in cases {
+            assert_eq!(format_payload(&event.payload), expected);
+        }
+
+        // Comments are never shortened, however long
+        let message = "é".repeat(80);
+        let event = WorkEvent::comment(message.clone());
+        assert_eq!(format_payload(&event.payload), message);
+    }
+}
@@ -0,0 +1,65 @@
+//! Durations of logged time, written the way people type them.
+
+use crate::error::{CoreError, Result};
+
+/// Parse a duration into minutes: `30m`, `2h`, `1h30m`, or a bare number
+/// of minutes.
+///
+/// # Errors
+/// Returns `CoreError::Validation` if the input isn't a duration or
+/// overflows.
+pub fn parse_minutes(input: &str) -> Result<u64> {
+    let input = input.trim();
+    let invalid = || {
+        CoreError::Validation(format!(
+            "expected a duration like 30m, 2h, or 1h30m, got '{input}'"
+        ))
+    };
+
+    if let Ok(minutes) = input.parse::<u64>() {
+        return Ok(minutes);
+    }
+    if input.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total: u64 = 0;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let amount: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        let minutes = match rest[digits..].chars().next() {
+            Some('h') => amount.checked_mul(60).ok_or_else(invalid)?,
+            Some('m') => amount,
+            _ => return Err(invalid()),
+        };
+        total = total.checked_add(minutes).ok_or_else(invalid)?;
+        rest = rest[digits + 1..].trim_start();
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minutes() {
+        let cases = [
+            ("45", 45),
+            ("30m", 30),
+            ("2h", 120),
+            ("1h30m", 90),
+            (" 1h 15m ", 75),
+            ("0m", 0),
+        ];
+        for (input, minutes) in cases {
+            assert_eq!(parse_minutes(input).unwrap(), minutes, "{input}");
+        }
+
+        for input in ["", "h", "1.5h", "2d", "1h30", "-5", "99999999999999999999h"] {
+            assert!(parse_minutes(input).is_err(), "{input}");
+        }
+    }
+}
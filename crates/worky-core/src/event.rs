@@ -1,5 +1,6 @@
 //! Work event model for append-only change tracking.
 
+use crate::error::CoreError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -7,6 +8,7 @@ use uuid::Uuid;
 
 /// Type of event that occurred on a work item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventType {
     /// Item was created.
@@ -25,6 +27,18 @@ pub enum EventType {
     Assigned,
     /// Action performed by AI tool.
     AiAction,
+    /// File attached to the item's artifacts.
+    ArtifactAdded,
+    /// File removed from the item's artifacts.
+    ArtifactRemoved,
+    /// Checklist step added, checked off, or removed.
+    ChecklistUpdated,
+    /// Time spent on the item logged.
+    TimeLogged,
+    /// Item moved between workspaces.
+    Transferred,
+    /// Events undone by a failed transaction; written to the audit log only.
+    RolledBack,
 }
 
 impl std::fmt::Display for EventType {
@@ -38,12 +52,46 @@ impl std::fmt::Display for EventType {
             Self::LabelRemoved => write!(f, "LABEL_REMOVED"),
             Self::Assigned => write!(f, "ASSIGNED"),
             Self::AiAction => write!(f, "AI_ACTION"),
+            Self::ArtifactAdded => write!(f, "ARTIFACT_ADDED"),
+            Self::ArtifactRemoved => write!(f, "ARTIFACT_REMOVED"),
+            Self::ChecklistUpdated => write!(f, "CHECKLIST_UPDATED"),
+            Self::TimeLogged => write!(f, "TIME_LOGGED"),
+            Self::Transferred => write!(f, "TRANSFERRED"),
+            Self::RolledBack => write!(f, "ROLLED_BACK"),
+        }
+    }
+}
+
+impl std::str::FromStr for EventType {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "CREATED" => Ok(Self::Created),
+            "STATE_CHANGED" => Ok(Self::StateChanged),
+            "FIELD_CHANGED" => Ok(Self::FieldChanged),
+            "COMMENT_ADDED" => Ok(Self::CommentAdded),
+            "LABEL_ADDED" => Ok(Self::LabelAdded),
+            "LABEL_REMOVED" => Ok(Self::LabelRemoved),
+            "ASSIGNED" => Ok(Self::Assigned),
+            "AI_ACTION" => Ok(Self::AiAction),
+            "ARTIFACT_ADDED" => Ok(Self::ArtifactAdded),
+            "ARTIFACT_REMOVED" => Ok(Self::ArtifactRemoved),
+            "CHECKLIST_UPDATED" => Ok(Self::ChecklistUpdated),
+            "TIME_LOGGED" => Ok(Self::TimeLogged),
+            "TRANSFERRED" => Ok(Self::Transferred),
+            "ROLLED_BACK" => Ok(Self::RolledBack),
+            _ => Err(CoreError::Validation(format!(
+                "unknown event type '{}'",
+                s.trim()
+            ))),
         }
     }
 }
 
 /// State change payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct StateChangePayload {
     pub from: String,
@@ -51,7 +99,8 @@ pub struct StateChangePayload {
 }
 
 /// Field change payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct FieldChangePayload {
     pub path: String,
@@ -60,7 +109,8 @@ pub struct FieldChangePayload {
 }
 
 /// Assignee change payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct AssigneeChangePayload {
     pub from: Option<String>,
@@ -68,21 +118,45 @@ pub struct AssigneeChangePayload {
 }
 
 /// Label payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct LabelPayload {
     pub label: String,
 }
 
 /// Comment payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct CommentPayload {
     pub message: String,
+    /// ID of the comment this one corrects, for edits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
+}
+
+/// Item creation payload data.
+///
+/// Logs written before this existed record creation as a [`CommentPayload`]
+/// reading `Created: <title>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CreatedPayload {
+    /// Title the item was created with.
+    pub title: String,
+    /// Who created the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<String>,
+    /// UID of the item this one was copied from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloned_from: Option<String>,
 }
 
 /// AI action payload data.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct AiActionPayload {
     pub tool: String,
@@ -94,17 +168,20 @@ pub struct AiActionPayload {
 ///
 /// Note: With `#[serde(untagged)]`, variants are tried in order during deserialization.
 /// Each payload struct uses `deny_unknown_fields` to ensure precise matching.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum EventPayload {
     /// State change payload.
     StateChange(StateChangePayload),
     /// Field change payload.
     FieldChange(FieldChangePayload),
-    /// Label operation payload (must come before AssigneeChange which has optional fields).
+    /// Label operation payload (must come before `AssigneeChange` which has optional fields).
     Label(LabelPayload),
-    /// Comment payload (must come before AssigneeChange which has optional fields).
+    /// Comment payload (must come before `AssigneeChange` which has optional fields).
     Comment(CommentPayload),
+    /// Item creation payload (must come before `AssigneeChange` which has optional fields).
+    Created(CreatedPayload),
     /// AI action payload.
     AiAction(AiActionPayload),
     /// Assignee change payload (has optional fields, so must come last among structs).
@@ -114,7 +191,8 @@ pub enum EventPayload {
 }
 
 /// A single event in the work item's history.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WorkEvent {
     /// Unique event identifier.
     pub id: String,
@@ -154,18 +232,33 @@ impl WorkEvent {
         self
     }
 
-    /// Create a CREATED event.
+    /// Create a CREATED event for an item `reporter` created.
     #[must_use]
-    pub fn created(title: &str) -> Self {
+    pub fn created(title: &str, reporter: &str) -> Self {
         Self::new(
             EventType::Created,
-            EventPayload::Comment(CommentPayload {
-                message: format!("Created: {title}"),
+            EventPayload::Created(CreatedPayload {
+                title: title.to_string(),
+                reporter: Some(reporter.to_string()),
+                cloned_from: None,
+            }),
+        )
+    }
+
+    /// Create a CREATED event for an item copied from `source_uid`.
+    #[must_use]
+    pub fn cloned(title: &str, reporter: &str, source_uid: &str) -> Self {
+        Self::new(
+            EventType::Created,
+            EventPayload::Created(CreatedPayload {
+                title: title.to_string(),
+                reporter: Some(reporter.to_string()),
+                cloned_from: Some(source_uid.to_string()),
             }),
         )
     }
 
-    /// Create a STATE_CHANGED event.
+    /// Create a `STATE_CHANGED` event.
     #[must_use]
     pub fn state_changed(from: impl Into<String>, to: impl Into<String>) -> Self {
         Self::new(
@@ -177,7 +270,7 @@ impl WorkEvent {
         )
     }
 
-    /// Create a FIELD_CHANGED event.
+    /// Create a `FIELD_CHANGED` event.
     #[must_use]
     pub fn field_changed(
         path: impl Into<String>,
@@ -203,7 +296,7 @@ impl WorkEvent {
         )
     }
 
-    /// Create a LABEL_ADDED event.
+    /// Create a `LABEL_ADDED` event.
     #[must_use]
     pub fn label_added(label: impl Into<String>) -> Self {
         Self::new(
@@ -214,7 +307,7 @@ impl WorkEvent {
         )
     }
 
-    /// Create a LABEL_REMOVED event.
+    /// Create a `LABEL_REMOVED` event.
     #[must_use]
     pub fn label_removed(label: impl Into<String>) -> Self {
         Self::new(
@@ -225,18 +318,31 @@ impl WorkEvent {
         )
     }
 
-    /// Create a COMMENT_ADDED event.
+    /// Create a `COMMENT_ADDED` event.
     #[must_use]
     pub fn comment(message: impl Into<String>) -> Self {
         Self::new(
             EventType::CommentAdded,
             EventPayload::Comment(CommentPayload {
                 message: message.into(),
+                replaces: None,
             }),
         )
     }
 
-    /// Create an AI_ACTION event.
+    /// Create a `COMMENT_ADDED` event that supersedes the comment `replaces`.
+    #[must_use]
+    pub fn comment_edit(replaces: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(
+            EventType::CommentAdded,
+            EventPayload::Comment(CommentPayload {
+                message: message.into(),
+                replaces: Some(replaces.into()),
+            }),
+        )
+    }
+
+    /// Create an `AI_ACTION` event.
     #[must_use]
     pub fn ai_action(tool: impl Into<String>, action: impl Into<String>) -> Self {
         Self::new(
@@ -248,12 +354,107 @@ impl WorkEvent {
             }),
         )
     }
+
+    /// Create an `ARTIFACT_ADDED` event.
+    #[must_use]
+    pub fn artifact_added(name: impl Into<String>, sha256: impl Into<String>) -> Self {
+        Self::new(
+            EventType::ArtifactAdded,
+            EventPayload::Generic(serde_json::json!({
+                "name": name.into(),
+                "sha256": sha256.into(),
+            })),
+        )
+    }
+
+    /// Create an `ARTIFACT_REMOVED` event.
+    #[must_use]
+    pub fn artifact_removed(name: impl Into<String>) -> Self {
+        Self::new(
+            EventType::ArtifactRemoved,
+            EventPayload::Generic(serde_json::json!({ "name": name.into() })),
+        )
+    }
+
+    /// Create a `CHECKLIST_UPDATED` event. `action` is `add`, `done`,
+    /// `undone`, or `remove`; `number` is the step's 1-based position.
+    #[must_use]
+    pub fn checklist_updated(action: &str, number: usize, text: impl Into<String>) -> Self {
+        Self::new(
+            EventType::ChecklistUpdated,
+            EventPayload::Generic(serde_json::json!({
+                "action": action,
+                "number": number,
+                "text": text.into(),
+            })),
+        )
+    }
+
+    /// Create a `TIME_LOGGED` event for `minutes` of work, with an optional note.
+    #[must_use]
+    pub fn time_logged(minutes: u64, note: Option<&str>) -> Self {
+        let mut payload = serde_json::json!({ "minutes": minutes });
+        if let Some(note) = note {
+            payload["note"] = note.into();
+        }
+        Self::new(EventType::TimeLogged, EventPayload::Generic(payload))
+    }
+
+    /// Create a `TRANSFERRED` event for an item moved here from `uid` in the
+    /// workspace at `workspace`.
+    #[must_use]
+    pub fn transferred_from(workspace: &str, uid: &str) -> Self {
+        Self::new(
+            EventType::Transferred,
+            EventPayload::Generic(serde_json::json!({ "from": workspace, "uid": uid })),
+        )
+    }
+
+    /// Create a `TRANSFERRED` event for an item moved to `uid` in the
+    /// workspace at `workspace`.
+    #[must_use]
+    pub fn transferred_to(workspace: &str, uid: &str) -> Self {
+        Self::new(
+            EventType::Transferred,
+            EventPayload::Generic(serde_json::json!({ "to": workspace, "uid": uid })),
+        )
+    }
+
+    /// Create a `ROLLED_BACK` event for the events with IDs `events`, which
+    /// a failed transaction removed again.
+    #[must_use]
+    pub fn rolled_back(events: &[String]) -> Self {
+        Self::new(
+            EventType::RolledBack,
+            EventPayload::Generic(serde_json::json!({ "events": events })),
+        )
+    }
+
+    /// Attach details to an `AI_ACTION` event. Other events are returned unchanged.
+    #[must_use]
+    pub fn with_details(mut self, details: Value) -> Self {
+        if let EventPayload::AiAction(payload) = &mut self.payload {
+            payload.details = Some(details);
+        }
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_event_type_parsing() {
+        assert_eq!("state_changed".parse::<EventType>().unwrap(), EventType::StateChanged);
+        assert_eq!(" COMMENT_ADDED ".parse::<EventType>().unwrap(), EventType::CommentAdded);
+        assert!("COMMENTED".parse::<EventType>().is_err());
+
+        for ty in [EventType::Created, EventType::AiAction, EventType::LabelRemoved] {
+            assert_eq!(ty.to_string().parse::<EventType>().unwrap(), ty);
+        }
+    }
+
     #[test]
     fn test_state_changed_event() {
         let event = WorkEvent::state_changed("TODO", "IN_PROGRESS").with_actor("alice");
@@ -279,6 +480,32 @@ mod tests {
         assert!(json.contains(r#""to":"DONE""#));
     }
 
+    #[test]
+    fn test_created_event_round_trip() {
+        let event = WorkEvent::cloned("Fix signup", "alice", "fs:fix-login");
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""payload":{"title":"Fix signup","reporter":"alice","#));
+        let parsed: WorkEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, event);
+
+        // Older logs recorded creation as a comment
+        let old = r#"{"id":"evt_1","type":"CREATED","timestamp":"2025-01-01T00:00:00Z",
+            "payload":{"message":"Created: Fix login"}}"#;
+        let parsed: WorkEvent = serde_json::from_str(old).unwrap();
+        assert!(matches!(parsed.payload, EventPayload::Comment(_)));
+    }
+
+    #[test]
+    fn test_artifact_event_round_trip() {
+        let event = WorkEvent::artifact_added("trace.log", "abc123");
+        let json = serde_json::to_string(&event).unwrap();
+        let parsed: WorkEvent = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.event_type, EventType::ArtifactAdded);
+        assert!(matches!(parsed.payload, EventPayload::Generic(_)));
+        assert_eq!(parsed, event);
+    }
+
     #[test]
     fn test_ai_action_event() {
         let event = WorkEvent::ai_action("worky-tool", "set_state").with_actor("claude");
@@ -1,12 +1,84 @@
 //! Work item model.
 
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use crate::error::CoreError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Priority of a work item, ordered from lowest to highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(CoreError::Validation(format!(
+                "unknown priority '{s}' (expected low, medium, high, or critical)"
+            ))),
+        }
+    }
+}
+
+// Accept any casing so `priority=HIGH` and `priority=high` both work.
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// One step in a work item's checklist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChecklistItem {
+    /// What needs doing.
+    pub text: String,
+
+    /// Whether it has been done.
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    /// An unchecked step.
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            done: false,
+        }
+    }
+}
 
 /// A work item representing a task, bug, feature, or other trackable unit of work.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WorkItem {
     /// Unique identifier (e.g., "fs:implement-auth-redirect").
     pub uid: String,
@@ -14,13 +86,26 @@ pub struct WorkItem {
     /// Human-readable title.
     pub title: String,
 
-    /// Current state (e.g., "TODO", "IN_PROGRESS", "DONE").
+    /// Key of the item in another system (e.g. a Jira key), unique within
+    /// the workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+
+    /// Current state (e.g., `TODO`, `IN_PROGRESS`, `DONE`).
     pub state: String,
 
     /// Assigned person (optional).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub assignee: Option<String>,
 
+    /// Priority (optional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+
+    /// Manual position within its state column; lower ranks come first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<f64>,
+
     /// Categorization labels.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub labels: Vec<String>,
@@ -31,9 +116,23 @@ pub struct WorkItem {
     /// Last update timestamp (ISO 8601 UTC).
     pub updated_at: DateTime<Utc>,
 
-    /// Custom fields as nested key-value pairs.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub fields: HashMap<String, Value>,
+    /// UIDs of items that must be finished before this one can start.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+
+    /// Subtasks too small to be items of their own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<ChecklistItem>,
+
+    /// Total time logged with `worky time`, in minutes. A cache of the
+    /// `TIME_LOGGED` events, which are the record.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_spent_minutes: Option<u64>,
+
+    /// Custom fields as nested key-value pairs, kept sorted so `meta.yml`
+    /// is written the same way every time.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Value>,
 }
 
 impl WorkItem {
@@ -44,12 +143,18 @@ impl WorkItem {
         Self {
             uid: uid.into(),
             title: title.into(),
+            external_id: None,
             state: "TODO".to_string(),
             assignee: None,
+            priority: None,
+            rank: None,
             labels: Vec::new(),
             created_at: now,
             updated_at: now,
-            fields: HashMap::new(),
+            blocked_by: Vec::new(),
+            checklist: Vec::new(),
+            time_spent_minutes: None,
+            fields: BTreeMap::new(),
         }
     }
 
@@ -67,6 +172,13 @@ impl WorkItem {
         self
     }
 
+    /// Set the priority of the work item.
+    #[must_use]
+    pub const fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
     /// Add labels to the work item.
     #[must_use]
     pub fn with_labels(mut self, labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
@@ -81,11 +193,30 @@ impl WorkItem {
         self
     }
 
-    /// Touch the updated_at timestamp.
+    /// Touch the `updated_at` timestamp.
     pub fn touch(&mut self) {
         self.updated_at = Utc::now();
     }
 
+    /// Time since the item was created.
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        Utc::now() - self.created_at
+    }
+
+    /// Time since the item was last updated.
+    #[must_use]
+    pub fn staleness(&self) -> Duration {
+        Utc::now() - self.updated_at
+    }
+
+    /// Checklist steps done, and in total.
+    #[must_use]
+    pub fn checklist_progress(&self) -> (usize, usize) {
+        let done = self.checklist.iter().filter(|c| c.done).count();
+        (done, self.checklist.len())
+    }
+
     /// Check if the item has a specific label.
     #[must_use]
     pub fn has_label(&self, label: &str) -> bool {
@@ -105,11 +236,11 @@ impl WorkItem {
     pub fn remove_label(&mut self, label: &str) -> bool {
         let initial_len = self.labels.len();
         self.labels.retain(|l| !l.eq_ignore_ascii_case(label));
-        if self.labels.len() != initial_len {
+        if self.labels.len() == initial_len {
+            false
+        } else {
             self.touch();
             true
-        } else {
-            false
         }
     }
 }
@@ -136,6 +267,19 @@ mod tests {
         assert!(item.fields.is_empty());
     }
 
+    #[test]
+    fn test_age_and_staleness() {
+        let mut item = WorkItem::new("fs:test", "Test");
+        item.created_at = Utc::now() - Duration::days(10);
+        item.updated_at = Utc::now() - Duration::days(3);
+
+        assert_eq!(item.age().num_days(), 10);
+        assert_eq!(item.staleness().num_days(), 3);
+
+        item.touch();
+        assert_eq!(item.staleness().num_days(), 0);
+    }
+
     #[test]
     fn test_builder_pattern() {
         let item = WorkItem::new("fs:test", "Test")
@@ -150,6 +294,28 @@ mod tests {
         assert_eq!(item.fields.get("priority"), Some(&Value::from("high")));
     }
 
+    #[test]
+    fn test_priority_parsing_and_order() {
+        assert_eq!("high".parse::<Priority>().unwrap(), Priority::High);
+        assert_eq!("CRITICAL".parse::<Priority>().unwrap(), Priority::Critical);
+        assert!("urgent".parse::<Priority>().is_err());
+
+        assert!(Priority::Critical > Priority::High);
+        assert!(Priority::Medium > Priority::Low);
+
+        let item: WorkItem = serde_json::from_value(serde_json::json!({
+            "uid": "fs:test",
+            "title": "Test",
+            "state": "TODO",
+            "priority": "High",
+            "created_at": "2025-01-31T10:00:00Z",
+            "updated_at": "2025-01-31T10:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(item.priority, Some(Priority::High));
+        assert_eq!(serde_json::to_value(item.priority).unwrap(), "high");
+    }
+
     #[test]
     fn test_label_operations() {
         let mut item = WorkItem::new("fs:test", "Test");
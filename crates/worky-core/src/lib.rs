@@ -4,16 +4,27 @@
 //! - `WorkItem`: The core work item model with normalized and custom fields
 //! - `WorkEvent`: Append-only event log entries for tracking changes
 //! - Patch operations for applying updates via JSON merge patch
+//! - `Workflow`: The state order used to advance and revert items
+//! - `parse_minutes`: Logged time as typed, e.g. `1h30m`
 
+pub mod display;
+pub mod duration;
 pub mod error;
 pub mod event;
 pub mod item;
 pub mod patch;
+pub mod workflow;
 
 pub use error::{CoreError, Result};
 pub use event::{
-    AiActionPayload, AssigneeChangePayload, CommentPayload, EventPayload, EventType,
-    FieldChangePayload, LabelPayload, StateChangePayload, WorkEvent,
+    AiActionPayload, AssigneeChangePayload, CommentPayload, CreatedPayload, EventPayload,
+    EventType, FieldChangePayload, LabelPayload, StateChangePayload, WorkEvent,
 };
-pub use item::WorkItem;
-pub use patch::{apply_merge_patch, apply_set_operation, diff_values, resolve_path, SetOperation};
+pub use item::{ChecklistItem, Priority, WorkItem};
+pub use patch::{
+    apply_json_patch, apply_merge_patch, apply_set_operation, diff_values, resolve_path, PatchOp,
+    SetOperation,
+};
+pub use display::TimeFormat;
+pub use duration::parse_minutes;
+pub use workflow::Workflow;
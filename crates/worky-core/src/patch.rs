@@ -3,13 +3,14 @@
 //! Supports:
 //! - Path-based set operations (e.g., `state=IN_PROGRESS`, `fields.priority=high`)
 //! - JSON Merge Patch (RFC 7396) for complex updates
+//! - JSON Patch (RFC 6902) for array edits and guarded updates
 
 use crate::error::{CoreError, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 /// A single set operation (path = value).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SetOperation {
     /// Dot-separated path (e.g., "fields.priority" or "state").
     pub path: String,
@@ -27,7 +28,37 @@ impl SetOperation {
         }
     }
 
-    /// Parse a "key=value" string into a SetOperation.
+    /// Set or clear the assignee. A blank name clears it.
+    #[must_use]
+    pub fn assignee(name: Option<&str>) -> Self {
+        let name = name.map(str::trim).filter(|n| !n.is_empty());
+        Self::new("assignee", name.map_or(Value::Null, Value::from))
+    }
+
+    /// Canonical form of this operation, so `assignee=` from the CLI and an
+    /// empty assignee from a tool both unassign. Likewise a blank
+    /// `external_id` clears it, and a numeric one (`external_id=1234`) is
+    /// kept as a string.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        match (self.path.as_str(), &self.value) {
+            ("assignee", Value::String(name)) => Self::assignee(Some(name)),
+            ("external_id", Value::String(id)) if id.trim().is_empty() => {
+                Self::new("external_id", Value::Null)
+            }
+            ("external_id", Value::Number(id)) => Self::new("external_id", id.to_string()),
+            _ => self,
+        }
+    }
+
+    /// Parse a "key=value" string into a `SetOperation`.
+    ///
+    /// The value is read in this order:
+    /// - `@text` is the literal string `text` (`@null`, `@1.0`, `@@x`)
+    /// - `'text'` is the literal string `text`
+    /// - valid JSON is taken as JSON: `null`, `true`, `false`, numbers,
+    ///   `"quoted strings"`, arrays, and objects
+    /// - anything else is a string
     ///
     /// # Errors
     /// Returns `CoreError::InvalidPath` if the format is invalid.
@@ -36,16 +67,81 @@ impl SetOperation {
             .split_once('=')
             .ok_or_else(|| CoreError::InvalidPath(format!("expected 'key=value', got '{input}'")))?;
 
-        let path = path.trim();
-        let value_str = value.trim();
+        Ok(Self::new(path.trim(), parse_value(value.trim())))
+    }
 
-        // Try to parse as JSON first, fall back to string
-        let value = serde_json::from_str(value_str).unwrap_or_else(|_| Value::String(value_str.to_string()));
+    /// One operation per top-level key of the JSON object `input`, with
+    /// nested objects flattened into dotted paths, so
+    /// `{"state": "DONE", "fields": {"size": {"points": 3}}}` becomes
+    /// `state=DONE` and `fields.size.points=3`. An empty object is set as is.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if `input` is not an object, and
+    /// `CoreError::InvalidPath` if a key is empty or contains a `.`.
+    pub fn from_object(input: Value) -> Result<Vec<Self>> {
+        let Value::Object(object) = input else {
+            return Err(CoreError::Validation(format!(
+                "expected a JSON object of fields, got {}",
+                json_type(&input)
+            )));
+        };
 
-        Ok(Self::new(path, value))
+        let mut operations = Vec::new();
+        flatten_into("", object, &mut operations)?;
+        Ok(operations)
     }
 }
 
+fn flatten_into(
+    prefix: &str,
+    object: Map<String, Value>,
+    out: &mut Vec<SetOperation>,
+) -> Result<()> {
+    for (key, value) in object {
+        if key.is_empty() || key.contains('.') {
+            return Err(CoreError::InvalidPath(format!(
+                "key '{prefix}{key}' must be non-empty and not contain '.'"
+            )));
+        }
+        let path = format!("{prefix}{key}");
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                flatten_into(&format!("{path}."), nested, out)?;
+            }
+            value => out.push(SetOperation::new(path, value)),
+        }
+    }
+    Ok(())
+}
+
+/// Name of the JSON type of `value`, for error messages.
+const fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Interpret the value half of a "key=value" assignment.
+fn parse_value(input: &str) -> Value {
+    if let Some(literal) = input.strip_prefix('@') {
+        return Value::String(literal.to_string());
+    }
+
+    if let Some(literal) = input
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        return Value::String(literal.to_string());
+    }
+
+    serde_json::from_str(input).unwrap_or_else(|_| Value::String(input.to_string()))
+}
+
 /// Resolve a dot-separated path to a JSON pointer.
 ///
 /// Examples:
@@ -123,17 +219,18 @@ pub fn apply_set_operation(root: &mut Value, op: &SetOperation) -> Result<Option
 ///   - null values in patch remove keys from target
 ///   - other values replace/add keys
 pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
-    if !patch.is_object() {
+    let Some(patch_obj) = patch.as_object() else {
         *target = patch.clone();
         return;
-    }
+    };
 
     if !target.is_object() {
         *target = Value::Object(Map::new());
     }
 
-    let target_obj = target.as_object_mut().unwrap();
-    let patch_obj = patch.as_object().unwrap();
+    let Some(target_obj) = target.as_object_mut() else {
+        return;
+    };
 
     for (key, value) in patch_obj {
         if value.is_null() {
@@ -150,6 +247,159 @@ pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
     }
 }
 
+/// A single JSON Patch (RFC 6902) operation.
+///
+/// Paths are JSON Pointers (RFC 6901), e.g. `/labels/0` or `/fields/priority`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Insert a value, or replace an existing object member.
+    Add { path: String, value: Value },
+    /// Remove an existing value.
+    Remove { path: String },
+    /// Replace an existing value.
+    Replace { path: String, value: Value },
+    /// Remove the value at `from` and add it at `path`.
+    Move { from: String, path: String },
+    /// Add a copy of the value at `from` at `path`.
+    Copy { from: String, path: String },
+    /// Fail unless the value at `path` equals `value`.
+    Test { path: String, value: Value },
+}
+
+/// Apply a JSON Patch (RFC 6902) to a value.
+///
+/// Operations are applied in order to a copy of `target`, which is only
+/// updated if all of them succeed.
+///
+/// # Errors
+/// Returns `CoreError::InvalidPath` if a pointer is malformed or doesn't
+/// resolve, or `CoreError::Validation` if a `test` operation fails.
+pub fn apply_json_patch(target: &mut Value, ops: &[PatchOp]) -> Result<()> {
+    let mut doc = target.clone();
+
+    for op in ops {
+        match op {
+            PatchOp::Add { path, value } => pointer_add(&mut doc, path, value.clone())?,
+            PatchOp::Remove { path } => {
+                pointer_remove(&mut doc, path)?;
+            }
+            PatchOp::Replace { path, value } => {
+                *pointer_get_mut(&mut doc, path)? = value.clone();
+            }
+            PatchOp::Move { from, path } => {
+                if path.starts_with(&format!("{from}/")) {
+                    return Err(CoreError::InvalidPath(format!(
+                        "cannot move '{from}' into its own child '{path}'"
+                    )));
+                }
+                let value = pointer_remove(&mut doc, from)?;
+                pointer_add(&mut doc, path, value)?;
+            }
+            PatchOp::Copy { from, path } => {
+                let value = pointer_get(&doc, from)?.clone();
+                pointer_add(&mut doc, path, value)?;
+            }
+            PatchOp::Test { path, value } => {
+                let actual = pointer_get(&doc, path)?;
+                if actual != value {
+                    return Err(CoreError::Validation(format!(
+                        "test failed at '{path}': expected {value}, found {actual}"
+                    )));
+                }
+            }
+        }
+    }
+
+    *target = doc;
+    Ok(())
+}
+
+/// Split a JSON Pointer into its parent pointer and unescaped last token.
+fn split_pointer(path: &str) -> Result<(&str, String)> {
+    if !path.starts_with('/') {
+        return Err(CoreError::InvalidPath(format!(
+            "JSON pointer must start with '/': '{path}'"
+        )));
+    }
+
+    let (parent, last) = path.rsplit_once('/').unwrap_or(("", path));
+    Ok((parent, last.replace("~1", "/").replace("~0", "~")))
+}
+
+fn pointer_get<'a>(doc: &'a Value, path: &str) -> Result<&'a Value> {
+    doc.pointer(path)
+        .ok_or_else(|| CoreError::InvalidPath(format!("'{path}' does not exist")))
+}
+
+fn pointer_get_mut<'a>(doc: &'a mut Value, path: &str) -> Result<&'a mut Value> {
+    doc.pointer_mut(path)
+        .ok_or_else(|| CoreError::InvalidPath(format!("'{path}' does not exist")))
+}
+
+/// Parse an array index token, allowing `len` when `append` is set.
+fn array_index(token: &str, len: usize, append: bool, path: &str) -> Result<usize> {
+    let index = if token == "-" && append {
+        Some(len)
+    } else if token == "0" || !token.starts_with('0') {
+        token.parse::<usize>().ok()
+    } else {
+        None
+    };
+
+    match index {
+        Some(i) if i < len || (append && i == len) => Ok(i),
+        _ => Err(CoreError::InvalidPath(format!(
+            "'{path}' is not a valid array index"
+        ))),
+    }
+}
+
+fn pointer_add(doc: &mut Value, path: &str, value: Value) -> Result<()> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+
+    let (parent, key) = split_pointer(path)?;
+    match pointer_get_mut(doc, parent)? {
+        Value::Object(obj) => {
+            obj.insert(key, value);
+        }
+        Value::Array(arr) => {
+            let index = array_index(&key, arr.len(), true, path)?;
+            arr.insert(index, value);
+        }
+        _ => {
+            return Err(CoreError::InvalidPath(format!(
+                "parent of '{path}' is not an object or array"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn pointer_remove(doc: &mut Value, path: &str) -> Result<Value> {
+    let (parent, key) = split_pointer(path)?;
+    let removed = match pointer_get_mut(doc, parent)? {
+        Value::Object(obj) => obj.remove(&key),
+        Value::Array(arr) => {
+            let index = array_index(&key, arr.len(), false, path)?;
+            Some(arr.remove(index))
+        }
+        _ => None,
+    };
+
+    removed.ok_or_else(|| CoreError::InvalidPath(format!("'{path}' does not exist")))
+}
+
+/// Array paths compared as unordered collections.
+///
+/// Instead of one change for the whole array, these produce a change per
+/// element: `(path, Some(element), Null)` for a removal and
+/// `(path, None, element)` for an addition. Reordering is not a change.
+const SET_PATHS: &[&str] = &["labels"];
+
 /// Detect changes between two values and return the differences.
 #[must_use]
 pub fn diff_values(old: &Value, new: &Value) -> Vec<(String, Option<Value>, Value)> {
@@ -168,44 +418,78 @@ fn diff_recursive(
         return;
     }
 
-    match (old, new) {
-        (Value::Object(old_obj), Value::Object(new_obj)) => {
-            // Check for changed and added keys
-            for (key, new_val) in new_obj {
+    if SET_PATHS.contains(&path.as_str())
+        && let (Some(old_items), Some(new_items)) = (set_elements(old), set_elements(new))
+    {
+        diff_elements(old_items, new_items, &path, changes);
+        return;
+    }
+
+    if let (Value::Object(old_obj), Value::Object(new_obj)) = (old, new) {
+        // Check for changed and added keys
+        for (key, new_val) in new_obj {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+
+            let old_val = old_obj.get(key).unwrap_or(&Value::Null);
+            diff_recursive(old_val, new_val, child_path, changes);
+        }
+
+        // Check for removed keys
+        for (key, old_val) in old_obj {
+            if !new_obj.contains_key(key) {
                 let child_path = if path.is_empty() {
                     key.clone()
                 } else {
                     format!("{path}.{key}")
                 };
-
-                match old_obj.get(key) {
-                    Some(old_val) => diff_recursive(old_val, new_val, child_path, changes),
-                    None => changes.push((child_path, None, new_val.clone())),
-                }
-            }
-
-            // Check for removed keys
-            for (key, old_val) in old_obj {
-                if !new_obj.contains_key(key) {
-                    let child_path = if path.is_empty() {
-                        key.clone()
-                    } else {
-                        format!("{path}.{key}")
-                    };
-                    changes.push((child_path, Some(old_val.clone()), Value::Null));
-                }
+                diff_recursive(old_val, &Value::Null, child_path, changes);
             }
         }
-        _ => {
-            // Leaf value changed
-            let old_val = if *old == Value::Null {
-                None
-            } else {
-                Some(old.clone())
-            };
-            changes.push((path, old_val, new.clone()));
+    } else {
+        // Leaf value changed
+        let old_val = if *old == Value::Null {
+            None
+        } else {
+            Some(old.clone())
+        };
+        changes.push((path, old_val, new.clone()));
+    }
+}
+
+/// Elements of a set-like array; a missing array is empty.
+fn set_elements(value: &Value) -> Option<&[Value]> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Null => Some(&[]),
+        _ => None,
+    }
+}
+
+/// Per-element removals then additions, counting duplicates: `[a, a]` to
+/// `[a]` removes one `a`.
+fn diff_elements(
+    old: &[Value],
+    new: &[Value],
+    path: &str,
+    changes: &mut Vec<(String, Option<Value>, Value)>,
+) {
+    let mut unmatched: Vec<&Value> = new.iter().collect();
+
+    for old_item in old {
+        if let Some(pos) = unmatched.iter().position(|v| *v == old_item) {
+            unmatched.remove(pos);
+        } else {
+            changes.push((path.to_string(), Some(old_item.clone()), Value::Null));
         }
     }
+
+    for new_item in unmatched {
+        changes.push((path.to_string(), None, new_item.clone()));
+    }
 }
 
 #[cfg(test)]
@@ -224,6 +508,31 @@ mod tests {
         assert_eq!(resolve_path(""), "");
     }
 
+    #[test]
+    fn test_set_operation_from_object() {
+        let input = json!({
+            "state": "DONE",
+            "labels": ["bug"],
+            "fields": {"size": {"points": 3}, "meta": {}}
+        });
+        let ops = SetOperation::from_object(input).unwrap();
+        assert_eq!(
+            ops,
+            [
+                SetOperation::new("fields.meta", json!({})),
+                SetOperation::new("fields.size.points", 3),
+                SetOperation::new("labels", json!(["bug"])),
+                SetOperation::new("state", "DONE"),
+            ]
+        );
+
+        assert!(matches!(
+            SetOperation::from_object(json!(["state", "DONE"])),
+            Err(CoreError::Validation(_))
+        ));
+        assert!(SetOperation::from_object(json!({"fields": {"a.b": 1}})).is_err());
+    }
+
     #[test]
     fn test_set_operation_parse() {
         let op = SetOperation::parse("state=IN_PROGRESS").unwrap();
@@ -242,6 +551,46 @@ mod tests {
         assert_eq!(op.value, json!(true));
     }
 
+    #[test]
+    fn test_set_operation_parse_precedence() {
+        let value = |input: &str| SetOperation::parse(input).unwrap().value;
+
+        // Bare JSON literals
+        assert_eq!(value("assignee=null"), Value::Null);
+        assert_eq!(value("done=false"), json!(false));
+        assert_eq!(value("version=1.0"), json!(1.0));
+        assert_eq!(value("labels=[\"a\",\"b\"]"), json!(["a", "b"]));
+
+        // `@` forces a string, and only the first one is stripped
+        assert_eq!(value("assignee=@null"), json!("null"));
+        assert_eq!(value("version=@1.0"), json!("1.0"));
+        assert_eq!(value("handle=@@alice"), json!("@alice"));
+        assert_eq!(value("title=@"), json!(""));
+
+        // Quotes force a string too
+        assert_eq!(value("version=\"1.0\""), json!("1.0"));
+        assert_eq!(value("version='1.0'"), json!("1.0"));
+        assert_eq!(value("title='it''s'"), json!("it''s"));
+
+        // Anything else stays a string
+        assert_eq!(value("title=Fix the bug"), json!("Fix the bug"));
+        assert_eq!(value("title='unterminated"), json!("'unterminated"));
+        assert_eq!(value("assignee="), json!(""));
+    }
+
+    #[test]
+    fn test_assignee_operation() {
+        assert_eq!(SetOperation::assignee(Some(" alice ")).value, json!("alice"));
+        assert_eq!(SetOperation::assignee(Some("")).value, Value::Null);
+        assert_eq!(SetOperation::assignee(None).value, Value::Null);
+
+        let op = SetOperation::parse("assignee=").unwrap().normalized();
+        assert_eq!(op, SetOperation::assignee(None));
+
+        let op = SetOperation::parse("title=").unwrap().normalized();
+        assert_eq!(op.value, json!(""));
+    }
+
     #[test]
     fn test_apply_set_operation() {
         let mut value = json!({
@@ -336,4 +685,98 @@ mod tests {
         assert!(changes.iter().any(|(p, _, v)| p == "fields.priority" && *v == json!("high")));
         assert!(changes.iter().any(|(p, _, v)| p == "fields.blocked" && *v == json!(true)));
     }
+
+    #[test]
+    fn test_diff_labels_per_element() {
+        let changes = diff_values(&json!({"labels": ["a", "b"]}), &json!({"labels": ["a", "c"]}));
+        assert_eq!(
+            changes,
+            vec![
+                ("labels".to_string(), Some(json!("b")), Value::Null),
+                ("labels".to_string(), None, json!("c")),
+            ]
+        );
+
+        // Reordering is not a change
+        let changes = diff_values(&json!({"labels": ["a", "b"]}), &json!({"labels": ["b", "a"]}));
+        assert!(changes.is_empty());
+
+        // Duplicates are counted
+        let changes = diff_values(&json!({"labels": ["a", "a"]}), &json!({"labels": ["a"]}));
+        assert_eq!(changes, vec![("labels".to_string(), Some(json!("a")), Value::Null)]);
+
+        // A missing array is empty
+        let changes = diff_values(&json!({}), &json!({"labels": ["x", "y"]}));
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|(p, old, _)| p == "labels" && old.is_none()));
+
+        // Other arrays are still compared whole
+        let changes = diff_values(&json!({"steps": [1, 2]}), &json!({"steps": [2, 1]}));
+        assert_eq!(changes, vec![("steps".to_string(), Some(json!([1, 2])), json!([2, 1]))]);
+    }
+
+    #[test]
+    fn test_json_patch_ops() {
+        let mut doc = json!({
+            "state": "TODO",
+            "labels": ["bug", "ui"],
+            "fields": {"a/b": 1}
+        });
+
+        let ops: Vec<PatchOp> = serde_json::from_value(json!([
+            {"op": "test", "path": "/state", "value": "TODO"},
+            {"op": "replace", "path": "/state", "value": "DONE"},
+            {"op": "add", "path": "/labels/-", "value": "api"},
+            {"op": "add", "path": "/labels/0", "value": "p1"},
+            {"op": "remove", "path": "/labels/2"},
+            {"op": "move", "from": "/fields/a~1b", "path": "/fields/weight"},
+            {"op": "copy", "from": "/state", "path": "/fields/last_state"}
+        ]))
+        .unwrap();
+
+        apply_json_patch(&mut doc, &ops).unwrap();
+
+        assert_eq!(
+            doc,
+            json!({
+                "state": "DONE",
+                "labels": ["p1", "bug", "api"],
+                "fields": {"weight": 1, "last_state": "DONE"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_patch_is_atomic() {
+        let original = json!({"state": "TODO", "labels": ["bug"]});
+        let mut doc = original.clone();
+
+        let ops = vec![
+            PatchOp::Replace {
+                path: "/state".into(),
+                value: json!("DONE"),
+            },
+            PatchOp::Test {
+                path: "/labels/0".into(),
+                value: json!("ui"),
+            },
+        ];
+        let err = apply_json_patch(&mut doc, &ops).unwrap_err();
+        assert!(matches!(err, CoreError::Validation(_)));
+        assert_eq!(doc, original);
+
+        let bad_paths: Vec<PatchOp> = serde_json::from_value(json!([
+            {"op": "remove", "path": "/missing"},
+            {"op": "remove", "path": "/labels/1"},
+            {"op": "add", "path": "/labels/5", "value": "x"},
+            {"op": "replace", "path": "state", "value": "x"},
+            {"op": "move", "from": "/labels", "path": "/labels/0"}
+        ]))
+        .unwrap();
+        for op in bad_paths {
+            let err = apply_json_patch(&mut doc, std::slice::from_ref(&op)).unwrap_err();
+            assert!(matches!(err, CoreError::InvalidPath(_)), "{op:?}");
+        }
+        assert_eq!(doc, original);
+    }
 }
@@ -0,0 +1,76 @@
+//! The linear state workflow behind `advance` and `revert`.
+
+/// An ordered list of states that items move through one step at a time.
+///
+/// States are matched case-insensitively. A state outside the workflow
+/// (e.g. `BLOCKED`) advances to the second state and reverts to the first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Workflow<'a> {
+    states: &'a [&'a str],
+}
+
+impl Workflow<'static> {
+    /// `TODO` → `IN_PROGRESS` → `IN_REVIEW` → `DONE`.
+    pub const DEFAULT: Self = Self::new(&["TODO", "IN_PROGRESS", "IN_REVIEW", "DONE"]);
+}
+
+impl Default for Workflow<'static> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<'a> Workflow<'a> {
+    /// A workflow through `states`, first to last.
+    #[must_use]
+    pub const fn new(states: &'a [&'a str]) -> Self {
+        Self { states }
+    }
+
+    /// The states in order.
+    #[must_use]
+    pub const fn states(&self) -> &'a [&'a str] {
+        self.states
+    }
+
+    /// The state after `current`, or `None` if `current` is the last one.
+    #[must_use]
+    pub fn next(&self, current: &str) -> Option<&'a str> {
+        let idx = self.position(current).map_or(1, |idx| idx + 1);
+        self.states.get(idx).copied()
+    }
+
+    /// The state before `current`, or `None` if `current` is the first one.
+    #[must_use]
+    pub fn previous(&self, current: &str) -> Option<&'a str> {
+        let idx = self.position(current).map_or(Some(0), |idx| idx.checked_sub(1));
+        idx.and_then(|idx| self.states.get(idx).copied())
+    }
+
+    fn position(&self, state: &str) -> Option<usize> {
+        self.states
+            .iter()
+            .position(|s| s.eq_ignore_ascii_case(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_workflow() {
+        let workflow = Workflow::default();
+
+        assert_eq!(workflow.next("TODO"), Some("IN_PROGRESS"));
+        assert_eq!(workflow.next("in_review"), Some("DONE"));
+        assert_eq!(workflow.next("DONE"), None);
+
+        assert_eq!(workflow.previous("DONE"), Some("IN_REVIEW"));
+        assert_eq!(workflow.previous("TODO"), None);
+
+        // Unknown states rejoin the workflow near the start
+        assert_eq!(workflow.next("BLOCKED"), Some("IN_PROGRESS"));
+        assert_eq!(workflow.previous("BLOCKED"), Some("TODO"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Who changes are attributed to.
+//!
+//! Every event a workspace writes carries an actor, resolved the same way
+//! for the CLI, the MCP server, and the tool server: an explicit name
+//! (`--actor`, or a tool server request's `actor`), then `$WORKY_ACTOR`,
+//! then the login name from `$USER` (or `%USERNAME%`), then `user`.
+
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Environment variable naming the actor.
+pub const ACTOR_ENV: &str = "WORKY_ACTOR";
+
+/// Actor used when nothing else names one.
+pub const DEFAULT_ACTOR: &str = "user";
+
+/// Actor set for the whole process, e.g. from `--actor`.
+static EXPLICIT: OnceLock<String> = OnceLock::new();
+
+/// Where a resolved actor's name came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorSource {
+    /// Passed in, e.g. with `--actor`.
+    Explicit,
+    /// `$WORKY_ACTOR`.
+    Env,
+    /// `$USER` or `%USERNAME%`.
+    Login,
+    /// None of the above; [`DEFAULT_ACTOR`].
+    Default,
+}
+
+impl fmt::Display for ActorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Explicit => "--actor",
+            Self::Env => "$WORKY_ACTOR",
+            Self::Login => "login name",
+            Self::Default => "default",
+        })
+    }
+}
+
+/// A resolved actor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Actor {
+    pub name: String,
+    pub source: ActorSource,
+}
+
+/// Attribute changes made by this process to `name`, as `--actor` does.
+/// Only the first call has an effect.
+pub fn set_explicit(name: impl Into<String>) {
+    // Later calls are ignored, as documented
+    let _ = EXPLICIT.set(name.into());
+}
+
+/// Resolve the actor, preferring `explicit`, then one set with
+/// [`set_explicit`]. Blank names are skipped.
+#[must_use]
+pub fn resolve(explicit: Option<&str>) -> Actor {
+    let named = |name: &str| Some(name.trim()).filter(|n| !n.is_empty()).map(String::from);
+    let env = |var: &str| std::env::var(var).ok().as_deref().and_then(named);
+
+    let found = explicit
+        .or_else(|| EXPLICIT.get().map(String::as_str))
+        .and_then(named)
+        .map(|name| (name, ActorSource::Explicit))
+        .or_else(|| env(ACTOR_ENV).map(|name| (name, ActorSource::Env)))
+        .or_else(|| {
+            ["USER", "USERNAME"]
+                .into_iter()
+                .find_map(env)
+                .map(|name| (name, ActorSource::Login))
+        });
+
+    let (name, source) =
+        found.unwrap_or_else(|| (DEFAULT_ACTOR.to_string(), ActorSource::Default));
+    Actor { name, source }
+}
+
+/// The name of the actor [`resolve`] finds without an explicit one.
+#[must_use]
+pub fn current() -> String {
+    resolve(None).name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_explicit() {
+        let actor = resolve(Some("  alice "));
+        assert_eq!(actor.name, "alice");
+        assert_eq!(actor.source, ActorSource::Explicit);
+
+        // A blank name falls through to the environment or the default
+        let actor = resolve(Some(" "));
+        assert_ne!(actor.source, ActorSource::Explicit);
+        assert!(!actor.name.trim().is_empty());
+    }
+}
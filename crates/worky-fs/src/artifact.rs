@@ -0,0 +1,63 @@
+//! Files attached to an item in its `artifacts/` directory.
+
+use crate::error::{FsError, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// An attached file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Artifact {
+    /// File name within the item's `artifacts/` directory.
+    pub name: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// Reject names that are empty, hidden, or could point outside the directory.
+pub(crate) fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\', '\0']);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(FsError::InvalidArtifactName(name.to_string()))
+    }
+}
+
+/// Hex-encoded SHA-256 of `data`.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// `name` with `-n` inserted before the extension, e.g. `log-2.txt`.
+pub(crate) fn numbered_name(name: &str, n: usize) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => format!("{stem}-{n}.{ext}"),
+        None => format!("{name}-{n}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("screenshot.png").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("..").is_err());
+        assert!(validate_name(".hidden").is_err());
+        assert!(validate_name("../meta.yml").is_err());
+        assert!(validate_name("a\\b").is_err());
+    }
+
+    #[test]
+    fn test_numbered_name() {
+        assert_eq!(numbered_name("trace.log", 1), "trace-1.log");
+        assert_eq!(numbered_name("archive.tar.gz", 2), "archive.tar-2.gz");
+        assert_eq!(numbered_name("README", 3), "README-3");
+    }
+}
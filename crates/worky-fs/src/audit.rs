@@ -0,0 +1,270 @@
+//! Attribution for automated changes.
+//!
+//! Writes made through [`Audited`] are each followed by an `AI_ACTION` event
+//! naming the tool, the operation, and whatever details the caller supplies,
+//! so changes made by agents can be told apart from edits by people.
+//!
+//! The change is what matters to the caller: if it was made but its event
+//! can't be written, a warning is logged and the write still succeeds.
+
+use crate::audit_log;
+use crate::error::{FsError, Result};
+use crate::workspace::{NewItemFields, Workspace};
+use serde_json::Value;
+use tracing::{debug, warn};
+use worky_core::{SetOperation, WorkEvent, WorkItem};
+
+/// Who is making automated changes, and why.
+#[derive(Debug, Clone)]
+pub struct AiAudit {
+    tool: String,
+    actor: Option<String>,
+    details: Option<Value>,
+}
+
+impl AiAudit {
+    /// Credit changes to `tool`, e.g. `worky-mcp`.
+    #[must_use]
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            actor: None,
+            details: None,
+        }
+    }
+
+    /// Record `actor` as the author of each `AI_ACTION` event.
+    #[must_use]
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Attach `details` (e.g. the request that caused the change) to each event.
+    #[must_use]
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    fn event(&self, action: &str) -> WorkEvent {
+        let mut event = WorkEvent::ai_action(&self.tool, action);
+        if let Some(details) = &self.details {
+            event = event.with_details(details.clone());
+        }
+        if let Some(actor) = &self.actor {
+            event = event.with_actor(actor);
+        }
+        event
+    }
+}
+
+/// A workspace whose writes are each logged as an `AI_ACTION` event.
+///
+/// Created with [`Workspace::audited`]. Reads go through [`Self::workspace`].
+#[derive(Debug)]
+pub struct Audited<'a> {
+    ws: &'a Workspace,
+    audit: AiAudit,
+}
+
+impl Workspace {
+    /// Wrap this workspace so that writes are attributed to `audit`.
+    #[must_use]
+    pub const fn audited(&self, audit: AiAudit) -> Audited<'_> {
+        Audited { ws: self, audit }
+    }
+}
+
+impl Audited<'_> {
+    /// The underlying workspace, for reads.
+    #[must_use]
+    pub const fn workspace(&self) -> &Workspace {
+        self.ws
+    }
+
+    /// See [`Workspace::create_item_with`].
+    ///
+    /// # Errors
+    /// Returns error if the item cannot be created.
+    pub fn create_item_with(&self, title: &str, fields: &NewItemFields) -> Result<WorkItem> {
+        let item = self.ws.create_item_with(title, fields)?;
+        self.record(&item.uid, "create_item");
+        Ok(item)
+    }
+
+    /// See [`Workspace::update_item`].
+    ///
+    /// # Errors
+    /// Returns error if the update fails.
+    pub fn update_item(&self, uid: &str, operations: &[SetOperation]) -> Result<WorkItem> {
+        self.update_item_if(uid, operations, None)
+    }
+
+    /// See [`Workspace::update_item_if`].
+    ///
+    /// # Errors
+    /// Returns error if the item has changed or the update fails.
+    pub fn update_item_if(
+        &self,
+        uid: &str,
+        operations: &[SetOperation],
+        expected_version: Option<&str>,
+    ) -> Result<WorkItem> {
+        let item = self.ws.update_item_if(uid, operations, expected_version)?;
+        self.record(uid, "update_item");
+        Ok(item)
+    }
+
+    /// See [`Workspace::patch_item`].
+    ///
+    /// # Errors
+    /// Returns error if the patch fails.
+    pub fn patch_item(&self, uid: &str, patch: &Value) -> Result<WorkItem> {
+        let item = self.ws.patch_item(uid, patch)?;
+        self.record(uid, "patch_item");
+        Ok(item)
+    }
+
+    /// See [`Workspace::add_comment`].
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or the comment cannot be written.
+    pub fn add_comment(&self, uid: &str, message: impl Into<String>) -> Result<()> {
+        self.ws.add_comment(uid, message)?;
+        self.record(uid, "add_comment");
+        Ok(())
+    }
+
+    /// See [`Workspace::add_blocker`].
+    ///
+    /// # Errors
+    /// Returns error if the link is rejected.
+    pub fn add_blocker(&self, uid: &str, blocker_uid: &str) -> Result<WorkItem> {
+        let item = self.ws.add_blocker(uid, blocker_uid)?;
+        self.record(uid, "add_blocker");
+        Ok(item)
+    }
+
+    /// See [`Workspace::delete_item`].
+    ///
+    /// The item's own log goes with it, so the `AI_ACTION` event is written
+    /// to the workspace audit log instead, before anything is removed, and
+    /// whether or not `audit.enabled` is set.
+    ///
+    /// # Errors
+    /// Returns error if no item has exactly that UID, the event cannot be
+    /// written, or the item cannot be removed.
+    pub fn delete_item(&self, uid: &str) -> Result<String> {
+        let slug = self
+            .ws
+            .exact_slug(uid)
+            .ok_or_else(|| FsError::ItemNotFound(uid.to_string()))?;
+        let uid = format!("fs:{slug}");
+
+        let mut event = self.audit.event("delete_item");
+        if event.actor.is_none() {
+            event = event.with_actor(self.ws.actor());
+        }
+        audit_log::append(&self.ws.audit_path(), &uid, &[event])?;
+        debug!(uid = %uid, tool = %self.audit.tool, "Recorded AI deletion");
+
+        self.ws.delete_item(&uid)
+    }
+
+    /// Log `action` on the item just written, warning if that fails.
+    fn record(&self, uid: &str, action: &str) {
+        let result = self
+            .ws
+            .resolve_slug(uid)
+            .and_then(|slug| self.ws.append_event(&slug, &self.audit.event(action)));
+        match result {
+            Ok(()) => debug!(uid = %uid, tool = %self.audit.tool, action, "Recorded AI action"),
+            Err(e) => warn!(
+                uid = %uid,
+                tool = %self.audit.tool,
+                action,
+                error = %e,
+                "Change was made but its AI action could not be recorded"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use worky_core::{EventPayload, EventType};
+
+    #[test]
+    fn test_audited_writes_record_ai_actions() {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+
+        let audit = AiAudit::new("worky-mcp")
+            .with_actor("claude")
+            .with_details(json!({ "tool": "worky_set" }));
+        let audited = ws.audited(audit);
+
+        let item = audited
+            .create_item_with("Fix login", &NewItemFields::default())
+            .unwrap();
+        audited
+            .update_item(&item.uid, &[SetOperation::new("state", "DONE")])
+            .unwrap();
+        audited.add_comment(&item.uid, "Done").unwrap();
+
+        // Plain writes stay unattributed
+        ws.add_comment(&item.uid, "Thanks").unwrap();
+
+        let events = ws.read_events(&item.uid, None, None, None).unwrap();
+        let actions: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match &e.payload {
+                EventPayload::AiAction(p) => Some(p.action.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(actions, ["create_item", "update_item", "add_comment"]);
+
+        let last = events
+            .iter()
+            .rfind(|e| e.event_type == EventType::AiAction)
+            .unwrap();
+        assert_eq!(last.actor.as_deref(), Some("claude"));
+        let EventPayload::AiAction(payload) = &last.payload else {
+            panic!("expected an AI action payload");
+        };
+        assert_eq!(payload.tool, "worky-mcp");
+        assert_eq!(payload.details, Some(json!({ "tool": "worky_set" })));
+
+        // A deleted item's trace outlives it in the audit log
+        assert!(audited.delete_item("fix").is_err());
+        assert_eq!(audited.delete_item(&item.uid).unwrap(), item.uid);
+        let entries = audit_log::read(&ws.audit_path(), None).unwrap();
+        let [entry] = entries.as_slice() else {
+            panic!("expected one audit entry, got {entries:?}");
+        };
+        assert_eq!(entry.uid, item.uid);
+        assert_eq!(entry.event.actor.as_deref(), Some("claude"));
+        assert!(matches!(ws.get_item(&item.uid), Err(FsError::ItemNotFound(_))));
+    }
+
+    #[test]
+    fn test_audited_update_can_be_undone() {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        let item = ws.create_item("Fix login").unwrap();
+
+        let audited = ws.audited(AiAudit::new("worky-mcp").with_actor("claude"));
+        audited
+            .update_item(&item.uid, &[SetOperation::new("state", "IN_PROGRESS")])
+            .unwrap();
+
+        // The AI_ACTION after the update doesn't hide it from undo
+        let undone = ws.undo_last(&item.uid).unwrap();
+        assert_eq!(undone.state, "TODO");
+    }
+}
@@ -0,0 +1,118 @@
+//! `.worky/audit.ndjson`: every event in the workspace, in one file.
+//!
+//! When `audit.enabled` is set, each event appended to an item's log is
+//! also appended here with the item's UID, giving a single chronological
+//! trail to tail or ship elsewhere. The item logs stay the source of truth;
+//! a failed audit write is logged and otherwise ignored.
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use tracing::warn;
+use worky_core::WorkEvent;
+
+/// File name of the audit log, inside `.worky`.
+pub const AUDIT_FILE: &str = "audit.ndjson";
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// UID of the item the event belongs to.
+    pub uid: String,
+    /// The event, as written to the item's log.
+    pub event: WorkEvent,
+}
+
+/// Append `events` on `uid` to the audit log at `path`.
+///
+/// # Errors
+/// Returns error if an entry cannot be serialized or the log written.
+pub fn append(path: &Path, uid: &str, events: &[WorkEvent]) -> Result<()> {
+    // One write for all lines, so concurrent writers don't interleave
+    let mut lines = String::new();
+    for event in events {
+        let entry = AuditEntry {
+            uid: uid.to_string(),
+            event: event.clone(),
+        };
+        lines.push_str(&serde_json::to_string(&entry)?);
+        lines.push('\n');
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+/// Read the audit log at `path`, oldest first, keeping entries at or after
+/// `since`. A missing log reads as empty; unreadable lines are skipped with
+/// a warning.
+///
+/// # Errors
+/// Returns error if the file exists but cannot be read.
+pub fn read(path: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for line in fs::read_to_string(path)?.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditEntry>(line) {
+            Ok(entry) if since.is_none_or(|s| entry.event.timestamp >= s) => entries.push(entry),
+            Ok(_) => {}
+            Err(_) => skipped += 1,
+        }
+    }
+
+    if skipped > 0 {
+        warn!(skipped, "Skipped unreadable audit log lines");
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Workspace;
+    use chrono::Duration;
+    use std::fs;
+    use tempfile::TempDir;
+    use worky_core::EventType;
+
+    #[test]
+    fn test_audit_log() {
+        let tmp = TempDir::new().unwrap();
+        let mut ws = Workspace::init(tmp.path()).unwrap();
+
+        // Off by default
+        ws.create_item("Before").unwrap();
+        assert!(ws.read_audit(None).unwrap().is_empty());
+
+        ws.set_config("audit.enabled", &true.into()).unwrap();
+        let item = ws.create_item("Fix login").unwrap();
+        ws.add_comment(&item.uid, "On it").unwrap();
+
+        let entries = ws.read_audit(None).unwrap();
+        let types: Vec<EventType> = entries.iter().map(|e| e.event.event_type).collect();
+        assert_eq!(types, [EventType::Created, EventType::CommentAdded]);
+        assert!(entries.iter().all(|e| e.uid == item.uid));
+
+        let later = entries[1].event.timestamp + Duration::seconds(1);
+        assert!(ws.read_audit(Some(later)).unwrap().is_empty());
+
+        // An unwritable log doesn't fail the write itself
+        fs::remove_file(ws.audit_path()).unwrap();
+        fs::create_dir(ws.audit_path()).unwrap();
+        ws.add_comment(&item.uid, "Still works").unwrap();
+        assert_eq!(ws.comments(&item.uid).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,143 @@
+//! Event log compaction.
+//!
+//! Folds runs of changes to the same field into one event, so an item `set`
+//! thousands of times doesn't make every read of its history slow. The
+//! intermediate values are lost from `events.ndjson`; the workspace keeps
+//! the original lines in `events.ndjson.archive`.
+
+use serde::Serialize;
+use worky_core::{EventPayload, EventType, WorkEvent};
+
+/// Event counts of one compacted log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompactSummary {
+    /// Item whose log was compacted.
+    pub uid: String,
+    /// Events before compacting.
+    pub before: usize,
+    /// Events after compacting.
+    pub after: usize,
+}
+
+impl CompactSummary {
+    /// Whether compacting removed any events.
+    #[must_use]
+    pub const fn changed(&self) -> bool {
+        self.after < self.before
+    }
+}
+
+/// Fold each run of field changes (events with nothing else between them)
+/// so every field in the run changes once, from its value before the run
+/// to its value after it.
+///
+/// The folded event keeps the ID, time, and actor of the last change, so
+/// `blame` and `undo` still see it. Any other event ends a run, which keeps
+/// creation, state, assignee, label, and comment history intact, as does a
+/// change to a path nested in or around another one in the run
+/// (`fields.size` and `fields.size.points`).
+#[must_use]
+pub fn compact(events: Vec<WorkEvent>) -> Vec<WorkEvent> {
+    let mut compacted: Vec<WorkEvent> = Vec::with_capacity(events.len());
+    // Index in `compacted` where the current run of field changes starts
+    let mut run_start = 0;
+
+    for mut event in events {
+        let Some(path) = field_path(&event).map(String::from) else {
+            compacted.push(event);
+            run_start = compacted.len();
+            continue;
+        };
+
+        let run = &compacted[run_start..];
+        if run.iter().filter_map(field_path).any(|p| p != path && overlaps(p, &path)) {
+            run_start = compacted.len();
+        } else if let Some(i) = run.iter().position(|e| field_path(e) == Some(path.as_str())) {
+            let earlier = compacted.remove(run_start + i);
+            if let (EventPayload::FieldChange(earlier), EventPayload::FieldChange(change)) =
+                (earlier.payload, &mut event.payload)
+            {
+                change.old_value = earlier.old_value;
+            }
+        }
+        compacted.push(event);
+    }
+
+    compacted
+}
+
+/// The path a field change event sets.
+fn field_path(event: &WorkEvent) -> Option<&str> {
+    match (&event.event_type, &event.payload) {
+        (EventType::FieldChanged, EventPayload::FieldChange(change)) => Some(&change.path),
+        _ => None,
+    }
+}
+
+/// Whether one of two different paths is nested in the other.
+fn overlaps(a: &str, b: &str) -> bool {
+    let nested = |outer: &str, inner: &str| {
+        inner.strip_prefix(outer).is_some_and(|rest| rest.starts_with('.'))
+    };
+    nested(a, b) || nested(b, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn set(path: &str, old: Option<i64>, new: i64) -> WorkEvent {
+        WorkEvent::field_changed(path, old.map(Into::into), new.into())
+    }
+
+    fn changes(events: &[WorkEvent]) -> Vec<(String, Option<Value>, Value)> {
+        events
+            .iter()
+            .filter_map(|e| match &e.payload {
+                EventPayload::FieldChange(c) => {
+                    Some((c.path.clone(), c.old_value.clone(), c.new_value.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compact_folds_runs() {
+        let last = set("fields.progress", Some(2), 3).with_actor("bob");
+        let events = vec![
+            WorkEvent::created("Fix login", "alice"),
+            set("fields.progress", None, 1),
+            set("fields.estimate", None, 5),
+            set("fields.progress", Some(1), 2),
+            last.clone(),
+            WorkEvent::state_changed("TODO", "IN_PROGRESS"),
+            set("fields.progress", Some(3), 4),
+        ];
+
+        let compacted = compact(events);
+        assert_eq!(compacted.len(), 5);
+        assert_eq!(compacted[0].event_type, EventType::Created);
+        assert_eq!(
+            changes(&compacted),
+            [
+                ("fields.estimate".to_string(), None, json!(5)),
+                ("fields.progress".to_string(), None, json!(3)),
+                ("fields.progress".to_string(), Some(json!(3)), json!(4)),
+            ]
+        );
+        // The folded change is the last one, with the run's starting value
+        assert_eq!(compacted[2].id, last.id);
+        assert_eq!(compacted[2].actor.as_deref(), Some("bob"));
+        assert_eq!(compacted[3].event_type, EventType::StateChanged);
+
+        // Nested paths end a run rather than being reordered
+        let events = vec![
+            set("fields.size", None, 1),
+            set("fields.size.points", None, 2),
+            set("fields.size", Some(1), 3),
+        ];
+        assert_eq!(compact(events).len(), 3);
+    }
+}
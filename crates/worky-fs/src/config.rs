@@ -1,6 +1,26 @@
 //! Workspace configuration.
 
+use crate::error::{FsError, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_yaml::Mapping;
+use std::collections::BTreeMap;
+use worky_core::{CoreError, TimeFormat};
+
+/// Config version this worky writes. Older files are read as if brought up
+/// to it by [`WorkspaceConfig::migrate`], and saved that way only on request.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Upgrades a config file by one version, returning a note per change.
+type Migration = fn(&mut Mapping) -> Result<Vec<String>>;
+
+/// Upgrades from each older version, in order: the first takes a version 1
+/// file to version 2.
+const MIGRATIONS: &[Migration] = &[spell_out_defaults];
+
+/// Custom fields worky itself sets or reads, allowed even with
+/// `strict_fields`.
+const BUILTIN_FIELDS: &[&str] = &["description", "due"];
 
 /// Workspace configuration stored in `.worky/config.yml`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,9 +36,55 @@ pub struct WorkspaceConfig {
     /// Default values for new items.
     #[serde(default)]
     pub defaults: ItemDefaults,
+
+    /// Workflow settings.
+    #[serde(default)]
+    pub workflow: WorkflowSettings,
+
+    /// Slug generation settings.
+    #[serde(default)]
+    pub slug: SlugSettings,
+
+    /// Workspace-wide audit log settings.
+    #[serde(default)]
+    pub audit: AuditSettings,
+
+    /// Whether event appends wait for the disk.
+    #[serde(default)]
+    pub durability: Durability,
+
+    /// When item event logs are compacted.
+    #[serde(default, skip_serializing_if = "EventLogSettings::is_default")]
+    pub events: EventLogSettings,
+
+    /// Declared custom fields, keyed by name under `fields`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldDef>,
+
+    /// Reject custom fields that are not declared in `fields`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict_fields: bool,
+
+    /// How timestamps are shown.
+    #[serde(default, skip_serializing_if = "DisplaySettings::is_default")]
+    pub display: DisplaySettings,
+
+    /// How labels are shown.
+    #[serde(default, skip_serializing_if = "LabelSettings::is_default")]
+    pub labels: LabelSettings,
+
+    /// Editor command for interactive descriptions, e.g. `code --wait`.
+    /// `$VISUAL` and `$EDITOR` take precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+
+    /// Named `worky list` filters, as the arguments they were saved with,
+    /// e.g. `backend-bugs: [--label, backend, --state, TODO]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub saved_filters: BTreeMap<String, Vec<String>>,
 }
 
-fn default_version() -> u32 {
+const fn default_version() -> u32 {
     1
 }
 
@@ -55,12 +121,308 @@ impl Default for ItemDefaults {
     }
 }
 
+/// Workflow settings shared by listing, stats, the board, and dependency checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSettings {
+    /// States in the order they appear as board columns.
+    #[serde(default = "default_states")]
+    pub states: Vec<String>,
+
+    /// States in which an item is finished and no longer blocks others.
+    #[serde(default = "default_terminal")]
+    pub terminal: Vec<String>,
+
+    /// Most items allowed in a state at once, e.g. `IN_PROGRESS: 3`.
+    /// Moving an item into a full state fails unless forced.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wip_limits: BTreeMap<String, usize>,
+}
+
+fn default_states() -> Vec<String> {
+    ["TODO", "IN_PROGRESS", "IN_REVIEW", "BLOCKED", "DONE"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_terminal() -> Vec<String> {
+    vec!["DONE".to_string()]
+}
+
+impl Default for WorkflowSettings {
+    fn default() -> Self {
+        Self {
+            states: default_states(),
+            terminal: default_terminal(),
+            wip_limits: BTreeMap::new(),
+        }
+    }
+}
+
+impl WorkflowSettings {
+    /// Check whether a state is terminal.
+    #[must_use]
+    pub fn is_terminal(&self, state: &str) -> bool {
+        self.terminal.iter().any(|s| s.eq_ignore_ascii_case(state))
+    }
+
+    /// The states `advance` and `revert` step through: the board states in
+    /// order, except `BLOCKED`, which items enter and leave by `set`.
+    ///
+    /// Build a [`worky_core::Workflow`] from these to move between them.
+    #[must_use]
+    pub fn steps(&self) -> Vec<&str> {
+        self.states
+            .iter()
+            .map(String::as_str)
+            .filter(|s| !s.eq_ignore_ascii_case("BLOCKED"))
+            .collect()
+    }
+
+    /// The WIP limit of a state, if it has one.
+    #[must_use]
+    pub fn wip_limit(&self, state: &str) -> Option<usize> {
+        self.wip_limits
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(state))
+            .map(|(_, limit)| *limit)
+    }
+}
+
+/// How timestamps are shown. Stored times are always UTC.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    /// IANA time zone, e.g. `Europe/Berlin`. Defaults to UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// `strftime` pattern, e.g. `%d.%m.%Y %H:%M`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+}
+
+impl DisplaySettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The timestamp format to use, with `timezone` taking precedence over
+    /// the configured zone.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if the zone or pattern is invalid.
+    pub fn time_format(&self, timezone: Option<&str>) -> worky_core::Result<TimeFormat> {
+        let zone = timezone.or(self.timezone.as_deref()).unwrap_or("UTC");
+        TimeFormat::new(zone, self.time_format.as_deref())
+    }
+}
+
+/// How labels are shown on the board and in colored CLI output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelSettings {
+    /// Hex color per label, e.g. `bug: "#dc2626"`. Overrides the built-in
+    /// palette for common labels.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, String>,
+}
+
+/// Colors for the labels `worky new` suggests, used unless configured.
+const DEFAULT_LABEL_COLORS: &[(&str, &str)] = &[
+    ("backend", "#4f46e5"),
+    ("frontend", "#db2777"),
+    ("bug", "#dc2626"),
+    ("feature", "#16a34a"),
+    ("documentation", "#0284c7"),
+    ("security", "#7c3aed"),
+    ("performance", "#ea580c"),
+    ("devops", "#475569"),
+    ("testing", "#0d9488"),
+    ("urgent", "#facc15"),
+];
+
+impl LabelSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Every label with a color, lowercased, as RGB: the built-in palette
+    /// with configured colors on top. Configured colors that aren't valid
+    /// hex are left out.
+    #[must_use]
+    pub fn palette(&self) -> BTreeMap<String, [u8; 3]> {
+        let defaults = DEFAULT_LABEL_COLORS.iter().map(|&(label, color)| (label, color));
+        let configured = self.colors.iter().map(|(label, color)| (label.as_str(), color.as_str()));
+        defaults
+            .chain(configured)
+            .filter_map(|(label, color)| Some((label.to_lowercase(), parse_hex_color(color)?)))
+            .collect()
+    }
+
+    /// The color of `label` as RGB, or `None` if it has none.
+    #[must_use]
+    pub fn rgb(&self, label: &str) -> Option<[u8; 3]> {
+        self.palette().get(&label.to_lowercase()).copied()
+    }
+
+    /// Check that every configured color is `#rgb` or `#rrggbb`.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` naming the first invalid color.
+    pub fn validate(&self) -> worky_core::Result<()> {
+        match self.colors.iter().find(|(_, color)| parse_hex_color(color).is_none()) {
+            Some((label, color)) => Err(CoreError::Validation(format!(
+                "color '{color}' for label '{label}' must be #rgb or #rrggbb"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse `#rgb` or `#rrggbb`.
+fn parse_hex_color(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, value) in rgb.iter_mut().enumerate() {
+                *value = channel(&hex[i..=i])? * 0x11;
+            }
+            Some(rgb)
+        }
+        6 => Some([channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?]),
+        _ => None,
+    }
+}
+
+/// How item slugs are derived from titles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlugSettings {
+    /// What to do when a new item's slug is already taken.
+    #[serde(default)]
+    pub on_conflict: SlugConflict,
+}
+
+/// How hard event appends try to survive a crash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Durability {
+    /// Leave flushing to the OS. A crash can lose the last few events.
+    #[default]
+    Fast,
+    /// `fsync` each item's event log after every write.
+    Synced,
+}
+
+/// Settings for `.worky/audit.ndjson`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditSettings {
+    /// Also append every item event to the workspace audit log.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Settings for item `events.ndjson` logs.
+///
+/// Compaction drops intermediate field values (see [`crate::compact`]), so
+/// it only happens on request unless `auto_compact` is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLogSettings {
+    /// Events a log may hold before writes suggest `worky compact`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact_threshold: Option<usize>,
+
+    /// Compact logs over `compact_threshold` as they are written instead.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_compact: bool,
+}
+
+impl EventLogSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Behavior when a generated slug already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugConflict {
+    /// Fail with `FsError::ItemExists`.
+    #[default]
+    Error,
+    /// Append `-2`, `-3`, ... until the slug is unique.
+    Suffix,
+}
+
+/// Declaration of a custom field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldDef {
+    /// Value type of the field.
+    #[serde(rename = "type")]
+    pub kind: FieldType,
+
+    /// Allowed values for `enum` fields.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+/// Value type of a custom field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Bool,
+    Enum,
+}
+
+impl FieldDef {
+    /// Check that a value fits this declaration. `null` clears the field and is always allowed.
+    fn check(&self, name: &str, value: &Value) -> worky_core::Result<()> {
+        let ok = match (self.kind, value) {
+            (_, Value::Null)
+            | (FieldType::String, Value::String(_))
+            | (FieldType::Number, Value::Number(_))
+            | (FieldType::Bool, Value::Bool(_)) => true,
+            (FieldType::Enum, Value::String(s)) => self.values.iter().any(|v| v == s),
+            _ => false,
+        };
+
+        if ok {
+            return Ok(());
+        }
+
+        let expected = match self.kind {
+            FieldType::String => "a string".to_string(),
+            FieldType::Number => "a number".to_string(),
+            FieldType::Bool => "true or false".to_string(),
+            FieldType::Enum => format!("one of: {}", self.values.join(", ")),
+        };
+        Err(CoreError::Validation(format!(
+            "field '{name}' must be {expected}, got {value}"
+        )))
+    }
+}
+
 impl Default for WorkspaceConfig {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: CONFIG_VERSION,
             workspace: WorkspaceSettings::default(),
             defaults: ItemDefaults::default(),
+            workflow: WorkflowSettings::default(),
+            slug: SlugSettings::default(),
+            audit: AuditSettings::default(),
+            durability: Durability::default(),
+            events: EventLogSettings::default(),
+            fields: BTreeMap::new(),
+            strict_fields: false,
+            display: DisplaySettings::default(),
+            labels: LabelSettings::default(),
+            editor: None,
+            saved_filters: BTreeMap::new(),
         }
     }
 }
@@ -76,4 +438,452 @@ impl WorkspaceConfig {
             ..Default::default()
         }
     }
+
+    /// Use `states` as the workflow, in board order.
+    ///
+    /// New items start in the first state and the last one is terminal.
+    /// An empty list leaves the workflow unchanged.
+    #[must_use]
+    pub fn with_states(mut self, states: Vec<String>) -> Self {
+        if let (Some(first), Some(last)) = (states.first(), states.last()) {
+            self.defaults.state.clone_from(first);
+            self.workflow.terminal = vec![last.clone()];
+            self.workflow.states = states;
+        }
+        self
+    }
+
+    /// Validate an item's custom fields after a change, `old` and `new`
+    /// being its `fields` before and after, against the schema.
+    ///
+    /// Only fields the change set are checked, so declaring a schema
+    /// doesn't block edits to items made before it. Declared fields must
+    /// match their type; undeclared fields are rejected only when
+    /// `strict_fields` is set, and never the ones worky itself uses.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if a changed field violates the
+    /// schema.
+    pub fn validate_fields(
+        &self,
+        old: &BTreeMap<String, Value>,
+        new: &BTreeMap<String, Value>,
+    ) -> worky_core::Result<()> {
+        let changed: BTreeMap<&String, &Value> = new
+            .iter()
+            .filter(|(name, value)| old.get(*name) != Some(*value))
+            .collect();
+        for (name, value) in changed {
+            self.validate_field(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// The setting at dotted `key`, e.g. `defaults.state`, with defaults
+    /// filled in. `None` if the key is unset or not a setting.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let config = serde_json::to_value(self).ok()?;
+        key.split('.')
+            .try_fold(&config, |value, k| value.get(k))
+            .filter(|value| !value.is_null())
+            .cloned()
+    }
+
+    /// Every setting as a dotted key and its value, defaults included.
+    /// Lists are single values; unset settings are left out.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(String, Value)> {
+        let mut entries = Vec::new();
+        if let Ok(config) = serde_json::to_value(self) {
+            flatten(String::new(), config, &mut entries);
+        }
+        entries
+    }
+
+    /// Bring `raw`, the YAML text of a config file, up to
+    /// [`CONFIG_VERSION`], returning the new text and a note per change, or
+    /// `None` if it is already current. A file without a version is
+    /// version 1. Keys this version doesn't know are kept; comments are not.
+    ///
+    /// # Errors
+    /// Returns `FsError::NewerConfig` if `raw` is from a newer worky, or
+    /// error if it is not a valid config.
+    pub fn migrate(raw: &str) -> Result<Option<(String, Vec<String>)>> {
+        let mut doc: serde_yaml::Value = serde_yaml::from_str(raw)?;
+        if doc.is_null() {
+            doc = Mapping::new().into();
+        }
+        let Some(map) = doc.as_mapping_mut() else {
+            return Err(CoreError::Validation("config must be a mapping".to_string()).into());
+        };
+
+        let version = match map.get("version") {
+            None => default_version(),
+            Some(version) => version
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .filter(|v| *v >= 1)
+                .ok_or_else(|| {
+                    CoreError::Validation("config version must be a positive integer".to_string())
+                })?,
+        };
+        if version > CONFIG_VERSION {
+            return Err(FsError::NewerConfig {
+                version,
+                supported: CONFIG_VERSION,
+            });
+        }
+        if version == CONFIG_VERSION {
+            return Ok(None);
+        }
+
+        let mut changes = Vec::new();
+        for (from, migration) in (version..).zip(&MIGRATIONS[version as usize - 1..]) {
+            changes.extend(
+                migration(map)?
+                    .into_iter()
+                    .map(|change| format!("v{from} -> v{}: {change}", from + 1)),
+            );
+        }
+        map.insert("version".into(), CONFIG_VERSION.into());
+
+        Ok(Some((serde_yaml::to_string(&doc)?, changes)))
+    }
+
+    /// Set `key` to `value` in `raw`, the YAML text of a config file, and
+    /// return the new text with the config it describes. A null `value`
+    /// removes the key so its default applies again.
+    ///
+    /// Other keys are kept as they are, including ones this version doesn't
+    /// know about; comments are not.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if `raw` is not valid YAML, `key` is
+    /// not a setting, or `value` has the wrong type for it.
+    pub fn edit(raw: &str, key: &str, value: &Value) -> worky_core::Result<(String, Self)> {
+        let invalid = |message: String| CoreError::Validation(message);
+
+        let mut doc: serde_yaml::Value = serde_yaml::from_str(raw)
+            .map_err(|e| invalid(format!("config is not valid YAML: {e}")))?;
+        if doc.is_null() {
+            doc = Mapping::new().into();
+        }
+
+        let keys: Vec<&str> = key.split('.').collect();
+        let Some((last, parents)) = keys.split_last().filter(|_| !keys.contains(&"")) else {
+            return Err(invalid(format!("invalid config key '{key}'")));
+        };
+
+        if value.is_null() {
+            if let Some(map) = parents
+                .iter()
+                .try_fold(&mut doc, |node, k| node.get_mut(*k))
+                .and_then(serde_yaml::Value::as_mapping_mut)
+            {
+                map.remove(*last);
+            }
+        } else {
+            let mut node = &mut doc;
+            for k in parents {
+                let Some(map) = node.as_mapping_mut() else {
+                    return Err(invalid(format!("'{key}' is not inside a config section")));
+                };
+                node = map
+                    .entry((*k).into())
+                    .or_insert_with(|| Mapping::new().into());
+            }
+            let Some(map) = node.as_mapping_mut() else {
+                return Err(invalid(format!("'{key}' is not inside a config section")));
+            };
+            let value = serde_yaml::to_value(value).map_err(|e| invalid(e.to_string()))?;
+            map.insert((*last).into(), value);
+        }
+
+        let config: Self = serde_yaml::from_value(doc.clone())
+            .map_err(|e| invalid(format!("invalid value for '{key}': {e}")))?;
+
+        // Keys serde doesn't know are silently dropped, so look for the value
+        if !value.is_null() && !config.get(key).is_some_and(|actual| contains(&actual, value)) {
+            return Err(invalid(format!("unknown config key '{key}'")));
+        }
+        config.display.time_format(None)?;
+        config.labels.validate()?;
+
+        let yaml = serde_yaml::to_string(&doc).map_err(|e| invalid(e.to_string()))?;
+        Ok((yaml, config))
+    }
+
+    fn validate_field(&self, name: &str, value: &Value) -> worky_core::Result<()> {
+        match self.fields.get(name) {
+            Some(def) => def.check(name, value),
+            None if self.strict_fields && !BUILTIN_FIELDS.contains(&name) => Err(
+                CoreError::Validation(format!("unknown field '{name}'")),
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Version 2: write out every setting `init` writes that the file leaves
+/// to its default, so the file shows what applies. The values are the ones
+/// the file already implied, so nothing changes in behavior.
+fn spell_out_defaults(map: &mut Mapping) -> Result<Vec<String>> {
+    let config: WorkspaceConfig = serde_yaml::from_value(map.clone().into())?;
+    let serde_yaml::Value::Mapping(effective) = serde_yaml::to_value(&config)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut changes = Vec::new();
+    fill_missing(map, effective, "", &mut changes);
+    Ok(changes)
+}
+
+/// Copy the keys of `source` that `target` lacks, recursing into sections
+/// both have, and note each one added.
+fn fill_missing(target: &mut Mapping, source: Mapping, prefix: &str, changes: &mut Vec<String>) {
+    for (key, value) in source {
+        let Some(name) = key.as_str().map(|k| format!("{prefix}{k}")) else {
+            continue;
+        };
+        match (target.get_mut(&key), value) {
+            (None, value) => {
+                changes.push(format!("added {name}"));
+                target.insert(key, value);
+            }
+            (Some(serde_yaml::Value::Mapping(section)), serde_yaml::Value::Mapping(defaults)) => {
+                fill_missing(section, defaults, &format!("{name}."), changes);
+            }
+            (Some(_), _) => {}
+        }
+    }
+}
+
+/// Push the leaves of `value` under `prefix` onto `entries`.
+fn flatten(prefix: String, value: Value, entries: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(path, child, entries);
+            }
+        }
+        leaf => entries.push((prefix, leaf)),
+    }
+}
+
+/// Whether `actual` has everything in `expected`; extra object keys (e.g.
+/// defaults filled in beside a partial section) are fine.
+fn contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(k, v)| actual.get(k).is_some_and(|a| contains(a, v))),
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use worky_core::Workflow;
+
+    fn schema() -> WorkspaceConfig {
+        serde_yaml::from_str(
+            r"
+fields:
+  estimate:
+    type: number
+  size:
+    type: enum
+    values: [s, m, l]
+strict_fields: true
+",
+        )
+        .unwrap()
+    }
+
+    fn fields(entries: &[(&str, Value)]) -> BTreeMap<String, Value> {
+        entries
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        let config = schema();
+        let check = |name: &str, value: Value| {
+            config.validate_fields(&BTreeMap::new(), &fields(&[(name, value)]))
+        };
+
+        assert!(check("estimate", 3.into()).is_ok());
+        assert!(check("size", "m".into()).is_ok());
+        assert!(check("size", Value::Null).is_ok());
+        assert!(check("description", "Steps".into()).is_ok());
+        assert!(check("due", "2025-03-01".into()).is_ok());
+
+        assert!(check("estimate", "lots".into()).is_err());
+        assert!(check("size", "xl".into()).is_err());
+        assert!(check("prioriy", "high".into()).is_err());
+        assert!(check("estimate", serde_json::json!({"hours": 3})).is_err());
+
+        // Fields the change leaves alone aren't checked
+        let old = fields(&[("legacy", true.into())]);
+        let new = fields(&[("legacy", true.into()), ("estimate", 3.into())]);
+        assert!(config.validate_fields(&old, &new).is_ok());
+    }
+
+    #[test]
+    fn test_no_schema_allows_anything() {
+        let config = WorkspaceConfig::default();
+        let new = fields(&[("anything", true.into())]);
+
+        assert!(config.validate_fields(&BTreeMap::new(), &new).is_ok());
+    }
+
+    #[test]
+    fn test_with_states() {
+        let config = WorkspaceConfig::new("Project")
+            .with_states(vec!["OPEN".into(), "DOING".into(), "SHIPPED".into()]);
+        assert_eq!(config.workspace.name.as_deref(), Some("Project"));
+        assert_eq!(config.workflow.states, vec!["OPEN", "DOING", "SHIPPED"]);
+        assert_eq!(config.defaults.state, "OPEN");
+        assert!(config.workflow.is_terminal("shipped"));
+
+        let config = WorkspaceConfig::default().with_states(Vec::new());
+        assert_eq!(config.workflow.states, default_states());
+    }
+
+    #[test]
+    fn test_workflow_states_default_independently() {
+        let config: WorkspaceConfig =
+            serde_yaml::from_str("workflow:\n  terminal: [CLOSED]\n").unwrap();
+        assert_eq!(config.workflow.states, default_states());
+        assert!(config.workflow.is_terminal("closed"));
+
+        let config: WorkspaceConfig =
+            serde_yaml::from_str("workflow:\n  states: [OPEN, CLOSED]\n").unwrap();
+        assert_eq!(config.workflow.states, vec!["OPEN", "CLOSED"]);
+        assert_eq!(config.workflow.terminal, vec!["DONE"]);
+    }
+
+    #[test]
+    fn test_workflow_steps() {
+        let settings = WorkflowSettings::default();
+        let steps = settings.steps();
+        assert_eq!(Workflow::new(&steps), Workflow::DEFAULT);
+
+        let config = WorkspaceConfig::default()
+            .with_states(vec!["OPEN".into(), "BLOCKED".into(), "DOING".into(), "SHIPPED".into()]);
+        let steps = config.workflow.steps();
+        let workflow = Workflow::new(&steps);
+        assert_eq!(workflow.next("OPEN"), Some("DOING"));
+        assert_eq!(workflow.next("SHIPPED"), None);
+        assert_eq!(workflow.previous("DOING"), Some("OPEN"));
+        assert_eq!(workflow.next("BLOCKED"), Some("DOING"));
+        assert_eq!(workflow.next("TODO"), Some("DOING"));
+    }
+
+    #[test]
+    fn test_display_time_format() {
+        let config: WorkspaceConfig =
+            serde_yaml::from_str("display:\n  timezone: America/New_York\n").unwrap();
+        let at = "2025-01-15T14:30:00Z".parse().unwrap();
+
+        let format = config.display.time_format(None).unwrap();
+        assert_eq!(format.format(&at), "2025-01-15 09:30:00 EST");
+
+        // An explicit zone wins over the config
+        let format = config.display.time_format(Some("Europe/Berlin")).unwrap();
+        assert_eq!(format.format(&at), "2025-01-15 15:30:00 CET");
+
+        // Unset display settings stay out of the file
+        let yaml = serde_yaml::to_string(&WorkspaceConfig::default()).unwrap();
+        assert!(!yaml.contains("display"));
+    }
+
+    #[test]
+    fn test_label_colors() {
+        let yaml = "labels:\n  colors:\n    bug: '#f00'\n    ux: '#00ff80'\n";
+        let config: WorkspaceConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.labels.rgb("bug"), Some([255, 0, 0]));
+        assert_eq!(config.labels.rgb("UX"), Some([0, 255, 128]));
+        assert_eq!(config.labels.rgb("feature"), Some([0x16, 0xa3, 0x4a]));
+        assert_eq!(config.labels.rgb("misc"), None);
+
+        let err = WorkspaceConfig::edit("version: 1\n", "labels.colors.bug", &"red".into());
+        assert!(err.is_err());
+        let (_, config) =
+            WorkspaceConfig::edit("version: 1\n", "labels.colors.bug", &"#123456".into()).unwrap();
+        assert_eq!(config.labels.rgb("bug"), Some([0x12, 0x34, 0x56]));
+    }
+
+    #[test]
+    fn test_migrate_v1() {
+        let raw = "version: 1\nworkflow:\n  states: [OPEN, CLOSED]\nplugins:\n  sync: true\n";
+        let (yaml, changes) = WorkspaceConfig::migrate(raw).unwrap().unwrap();
+        assert!(changes.contains(&"v1 -> v2: added workflow.terminal".to_string()));
+        assert!(changes.contains(&"v1 -> v2: added slug".to_string()));
+
+        let config: WorkspaceConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.workflow.states, ["OPEN", "CLOSED"]);
+        assert_eq!(config.workflow.terminal, ["DONE"]);
+        assert!(yaml.contains("sync: true"));
+
+        // Current files are left alone; newer ones are refused
+        assert!(WorkspaceConfig::migrate(&yaml).unwrap().is_none());
+        let newer = format!("version: {}\n", CONFIG_VERSION + 1);
+        assert!(matches!(
+            WorkspaceConfig::migrate(&newer),
+            Err(FsError::NewerConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn test_saved_filters() {
+        let args = json!(["--label", "backend", "--state", "TODO,IN_PROGRESS"]);
+        let (yaml, config) =
+            WorkspaceConfig::edit("version: 1\n", "saved_filters.backend-bugs", &args).unwrap();
+        assert_eq!(config.saved_filters["backend-bugs"][1], "backend");
+        assert!(WorkspaceConfig::edit(&yaml, "saved_filters.mine", &json!("--open")).is_err());
+
+        let (_, config) =
+            WorkspaceConfig::edit(&yaml, "saved_filters.backend-bugs", &Value::Null).unwrap();
+        assert!(config.saved_filters.is_empty());
+    }
+
+    #[test]
+    fn test_get_and_edit() {
+        let raw = "version: 1\nplugins:\n  sync: true\n";
+
+        let (yaml, config) =
+            WorkspaceConfig::edit(raw, "workflow.states", &json!(["OPEN", "DONE"])).unwrap();
+        assert_eq!(config.get("workflow.states"), Some(json!(["OPEN", "DONE"])));
+        assert_eq!(config.get("defaults.state"), Some(json!("TODO")));
+        assert_eq!(config.get("editor"), None);
+        // Keys this version doesn't know survive the rewrite
+        assert!(yaml.contains("sync: true"));
+
+        let (yaml, config) = WorkspaceConfig::edit(&yaml, "editor", &json!("nano")).unwrap();
+        assert!(config
+            .entries()
+            .contains(&("editor".to_string(), json!("nano"))));
+
+        // Null restores the default
+        let (_, config) = WorkspaceConfig::edit(&yaml, "workflow.states", &Value::Null).unwrap();
+        assert_eq!(config.workflow.states, default_states());
+
+        assert!(WorkspaceConfig::edit(raw, "workflow.states", &json!("OPEN")).is_err());
+        assert!(WorkspaceConfig::edit(raw, "workflow.stats", &json!(["OPEN"])).is_err());
+        assert!(WorkspaceConfig::edit(raw, "display.timezone", &json!("Mars/Base")).is_err());
+        assert!(WorkspaceConfig::edit(raw, "defaults..state", &json!("TODO")).is_err());
+    }
 }
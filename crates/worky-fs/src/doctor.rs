@@ -0,0 +1,66 @@
+//! Workspace consistency checks.
+
+use serde::Serialize;
+
+/// Something wrong with an item directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Problem {
+    /// Directory under `work/items` without a `meta.yml`.
+    Orphaned,
+    /// `meta.yml` doesn't parse.
+    InvalidMeta { error: String },
+    /// A file that should be text isn't valid UTF-8.
+    NotUtf8 { file: String },
+    /// `events.ndjson` is missing.
+    MissingEvents,
+    /// Lines of `events.ndjson` that don't deserialize (1-based).
+    BadEventLines { lines: Vec<usize> },
+    /// `notes.md` is missing.
+    MissingNotes,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Orphaned => write!(f, "directory has no meta.yml"),
+            Self::InvalidMeta { error } => write!(f, "meta.yml is invalid: {error}"),
+            Self::NotUtf8 { file } => write!(f, "{file} is not valid UTF-8"),
+            Self::MissingEvents => write!(f, "events.ndjson is missing"),
+            Self::BadEventLines { lines } => {
+                let lines: Vec<String> = lines.iter().map(ToString::to_string).collect();
+                write!(f, "unreadable events on line(s) {}", lines.join(", "))
+            }
+            Self::MissingNotes => write!(f, "notes.md is missing"),
+        }
+    }
+}
+
+/// A problem found in one item directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    /// Item directory name.
+    pub slug: String,
+    /// What's wrong.
+    #[serde(flatten)]
+    pub problem: Problem,
+    /// Whether `--fix` repaired it.
+    pub fixed: bool,
+}
+
+/// Result of checking every item directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    /// Number of item directories checked.
+    pub checked: usize,
+    /// Problems found, in directory order.
+    pub findings: Vec<Finding>,
+}
+
+impl DoctorReport {
+    /// Number of problems that were not repaired.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.findings.iter().filter(|f| !f.fixed).count()
+    }
+}
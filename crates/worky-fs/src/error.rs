@@ -2,6 +2,7 @@
 
 use std::path::PathBuf;
 use thiserror::Error;
+use worky_core::WorkItem;
 
 /// Result type alias for filesystem operations.
 pub type Result<T> = std::result::Result<T, FsError>;
@@ -25,6 +26,24 @@ pub enum FsError {
     #[error("work item already exists: {0}")]
     ItemExists(String),
 
+    /// A partial UID matches more than one item.
+    #[error("'{input}' matches several items: {}", .candidates.join(", "))]
+    AmbiguousUid {
+        input: String,
+        candidates: Vec<String>,
+    },
+
+    /// The item changed after the version an update was based on.
+    #[error("{uid} has changed since version {expected} (now {version}); fetch it and retry")]
+    VersionMismatch {
+        uid: String,
+        expected: String,
+        /// The item's current version.
+        version: String,
+        /// The item as it is now.
+        current: Box<WorkItem>,
+    },
+
     /// Invalid UID format.
     #[error("invalid UID format: {0}")]
     InvalidUid(String),
@@ -45,7 +64,55 @@ pub enum FsError {
     #[error("core error: {0}")]
     Core(#[from] worky_core::CoreError),
 
+    /// Dependency link would create a cycle.
+    #[error("dependency cycle: {0}")]
+    DependencyCycle(String),
+
+    /// The most recent change to an item can't be undone.
+    #[error("cannot undo: {0}")]
+    NotReversible(String),
+
+    /// The config was written by a newer worky with settings this one
+    /// doesn't know how to read.
+    #[error(
+        "workspace config is version {version}, created by a newer worky (this one reads up to \
+         version {supported}); upgrade worky to use it"
+    )]
+    NewerConfig { version: u32, supported: u32 },
+
+    /// Moving an item into a state would exceed the state's WIP limit.
+    #[error("{state} is at its WIP limit of {limit}")]
+    WipLimitReached { state: String, limit: usize },
+
+    /// Named template doesn't exist.
+    #[error("template '{name}' not found (available: {})", if .available.is_empty() { "none".to_string() } else { .available.join(", ") })]
+    TemplateNotFound {
+        name: String,
+        available: Vec<String>,
+    },
+
+    /// Artifact name is empty or could escape the artifacts directory.
+    #[error("invalid artifact name: {0}")]
+    InvalidArtifactName(String),
+
+    /// No artifact with that name on the item.
+    #[error("artifact not found: {0}")]
+    ArtifactNotFound(String),
+
+    /// No comment with that event ID on the item.
+    #[error("comment not found: {0}")]
+    CommentNotFound(String),
+
     /// Invalid slug.
     #[error("invalid slug: {0}")]
     InvalidSlug(String),
+
+    /// A `.workyignore` pattern could not be parsed.
+    #[error("invalid .workyignore pattern: {0}")]
+    InvalidIgnorePattern(String),
+
+    /// An item file isn't UTF-8 text, e.g. after an editor saved it in
+    /// another encoding.
+    #[error("{} of {uid} is not valid UTF-8; re-save it as UTF-8", .path.display())]
+    NotUtf8 { uid: String, path: PathBuf },
 }
@@ -0,0 +1,51 @@
+//! Whole-workspace export bundles and importing them back.
+
+use serde::{Deserialize, Serialize};
+use worky_core::{WorkEvent, WorkItem};
+
+/// Layout of an export bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A single JSON array of items.
+    Json,
+    /// One JSON item per line.
+    Ndjson,
+}
+
+/// A work item together with everything stored alongside it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedItem {
+    /// Item metadata.
+    #[serde(flatten)]
+    pub item: WorkItem,
+
+    /// Full event log, oldest first.
+    #[serde(default)]
+    pub events: Vec<WorkEvent>,
+
+    /// Contents of `notes.md`, if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// How to handle bundle items whose UID already exists in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the existing item and count the bundle item as skipped.
+    Skip,
+    /// Replace the existing item entirely.
+    Overwrite,
+    /// Stop the import with `FsError::ItemExists`.
+    ErrorOnConflict,
+}
+
+/// Counts reported after an import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Items written to the workspace.
+    pub created: usize,
+    /// Items left alone because they already existed.
+    pub skipped: usize,
+    /// Items rejected for an invalid UID.
+    pub failed: usize,
+}
@@ -0,0 +1,165 @@
+//! iCalendar (RFC 5545) view of items with a due date.
+//!
+//! Each item whose `fields.due` is a date (`2025-03-01`) or an RFC 3339
+//! timestamp becomes one `VEVENT`: dates as all-day events, timestamps as
+//! events at that instant. Items without a due date are left out, and ones
+//! whose due date doesn't parse are skipped with a warning.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+use tracing::warn;
+use worky_core::WorkItem;
+
+/// Longest content line before folding, in octets (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// When an item is due.
+enum Due {
+    Day(NaiveDate),
+    At(DateTime<Utc>),
+}
+
+impl Due {
+    fn of(item: &WorkItem) -> Option<Self> {
+        let due = item.fields.get("due")?;
+        let parsed = due.as_str().and_then(|due| {
+            NaiveDate::parse_from_str(due, "%Y-%m-%d")
+                .map(Self::Day)
+                .ok()
+                .or_else(|| DateTime::parse_from_rfc3339(due).ok().map(|at| Self::At(at.to_utc())))
+        });
+        if parsed.is_none() && !due.is_null() {
+            warn!(uid = %item.uid, due = %due, "Skipping item with unreadable due date");
+        }
+        parsed
+    }
+}
+
+/// A calendar with one event per item in `items` that has a due date,
+/// titled `name` if given. Lines end in CRLF, as the format requires.
+#[must_use]
+pub fn calendar(name: Option<&str>, items: &[WorkItem]) -> String {
+    let mut out = String::new();
+    let mut line = |text: String| {
+        out.push_str(&fold(&text));
+        out.push_str("\r\n");
+    };
+
+    line("BEGIN:VCALENDAR".to_string());
+    line("VERSION:2.0".to_string());
+    line(format!("PRODID:-//worky//worky {}//EN", env!("CARGO_PKG_VERSION")));
+    line("CALSCALE:GREGORIAN".to_string());
+    if let Some(name) = name {
+        line(format!("X-WR-CALNAME:{}", escape(name)));
+    }
+
+    for item in items {
+        let Some(due) = Due::of(item) else {
+            continue;
+        };
+        line("BEGIN:VEVENT".to_string());
+        line(format!("UID:{}", escape(&item.uid)));
+        line(format!("DTSTAMP:{}", timestamp(item.updated_at)));
+        match due {
+            Due::Day(day) => {
+                line(format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")));
+                if let Some(next) = day.succ_opt() {
+                    line(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+                }
+            }
+            Due::At(at) => line(format!("DTSTART:{}", timestamp(at))),
+        }
+        line(format!("SUMMARY:{}", escape(&item.title)));
+        if let Some(description) = item.fields.get("description").and_then(Value::as_str) {
+            line(format!("DESCRIPTION:{}", escape(description)));
+        }
+        line("END:VEVENT".to_string());
+    }
+
+    line("END:VCALENDAR".to_string());
+    out
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Split `line` into lines of at most 75 octets, continuations starting
+/// with a space, without breaking a UTF-8 character.
+fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts towards the next line
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(uid: &str, title: &str, due: Option<&str>) -> WorkItem {
+        let mut item = WorkItem::new(uid, title);
+        if let Some(due) = due {
+            item.fields.insert("due".to_string(), due.into());
+        }
+        item
+    }
+
+    #[test]
+    fn test_calendar() {
+        let mut launch = item("fs:launch", "Launch; v2, finally", Some("2025-03-01"));
+        launch
+            .fields
+            .insert("description".to_string(), "Ship it\nthen rest".into());
+        let items = [
+            launch,
+            item("fs:demo", "Demo", Some("2025-03-02T15:30:00+01:00")),
+            item("fs:someday", "Someday", None),
+            item("fs:typo", "Typo", Some("next week")),
+        ];
+
+        let ics = calendar(Some("Team"), &items);
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert!(lines.contains(&"X-WR-CALNAME:Team"));
+        assert_eq!(lines.iter().filter(|l| **l == "BEGIN:VEVENT").count(), 2);
+        assert!(lines.contains(&"UID:fs:launch"));
+        assert!(lines.contains(&"SUMMARY:Launch\\; v2\\, finally"));
+        assert!(lines.contains(&"DESCRIPTION:Ship it\\nthen rest"));
+        assert!(lines.contains(&"DTSTART;VALUE=DATE:20250301"));
+        assert!(lines.contains(&"DTEND;VALUE=DATE:20250302"));
+        assert!(lines.contains(&"DTSTART:20250302T143000Z"));
+        assert!(!ics.contains("fs:someday") && !ics.contains("fs:typo"));
+    }
+
+    #[test]
+    fn test_fold() {
+        let long = format!("SUMMARY:{}", "é".repeat(50));
+        let folded = fold(&long);
+        assert!(folded.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+}
@@ -0,0 +1,268 @@
+//! `.worky/index.json`: a cache of the fields listings filter on.
+//!
+//! Written by [`Workspace::reindex`](crate::Workspace::reindex), then kept
+//! up to date as items are saved. Filtered listings and lookups by
+//! external ID check each item against its entry first and skip reading
+//! `meta.yml` when the entry already rules it out. An entry is only trusted
+//! while `meta.yml` still has the size and modification time recorded for
+//! it, so files changed behind worky's back are read as usual.
+//!
+//! The index also maps external IDs to slugs, so an item can be found by
+//! its external ID without going through the others. The index is rewritten
+//! whole without a lock, so concurrent writes can drop each other's entries;
+//! nothing relies on an entry being there, only on one that is being fresh.
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tracing::warn;
+use worky_core::WorkItem;
+
+/// File name of the index, inside `.worky`.
+pub const INDEX_FILE: &str = "index.json";
+
+/// Bumped whenever the index changes shape; older indexes are ignored.
+const INDEX_VERSION: u32 = 3;
+
+/// Indexed fields of every item, keyed by slug.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemIndex {
+    version: u32,
+    items: BTreeMap<String, IndexEntry>,
+    /// Slug of the item with each external ID.
+    #[serde(default)]
+    external_ids: BTreeMap<String, String>,
+    /// When the index file was last written, set on load.
+    #[serde(skip)]
+    written: Option<SystemTime>,
+}
+
+/// What the index knows about one item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Workflow state.
+    pub state: String,
+    /// Assignee, if any.
+    pub assignee: Option<String>,
+    /// Labels.
+    pub labels: Vec<String>,
+    /// Key in another system, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Last update.
+    pub updated_at: DateTime<Utc>,
+    /// Modification time of `meta.yml` when it was indexed.
+    pub mtime: SystemTime,
+    /// Size of `meta.yml` in bytes when it was indexed.
+    pub len: u64,
+}
+
+impl IndexEntry {
+    /// Index `item`, whose `meta.yml` had `stamp` before it was read.
+    #[must_use]
+    pub fn new(item: &WorkItem, stamp: Stamp) -> Self {
+        Self {
+            state: item.state.clone(),
+            assignee: item.assignee.clone(),
+            labels: item.labels.clone(),
+            external_id: item.external_id.clone(),
+            updated_at: item.updated_at,
+            mtime: stamp.mtime,
+            len: stamp.len,
+        }
+    }
+
+    /// Whether the item has `label`, ignoring case.
+    #[must_use]
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+    }
+}
+
+/// Size and modification time of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stamp {
+    mtime: SystemTime,
+    len: u64,
+}
+
+impl Stamp {
+    /// Stat `path`.
+    ///
+    /// # Errors
+    /// Returns error if the file's metadata cannot be read.
+    pub fn of(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            mtime: metadata.modified()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+impl ItemIndex {
+    /// Read the index at `path`.
+    ///
+    /// Returns `None`, logging why, if there is no index or it can't be used;
+    /// listings then read every item as if there were none.
+    #[must_use]
+    pub fn load(path: &Path) -> Option<Self> {
+        let read = || -> Result<Self> {
+            let written = fs::metadata(path)?.modified()?;
+            let index: Self = serde_json::from_slice(&fs::read(path)?)?;
+            Ok(Self {
+                written: Some(written),
+                ..index
+            })
+        };
+
+        if !path.exists() {
+            return None;
+        }
+        match read() {
+            Ok(index) if index.version == INDEX_VERSION => Some(index),
+            Ok(index) => {
+                warn!(
+                    version = index.version,
+                    "Ignoring index from another version; run `worky reindex`"
+                );
+                None
+            }
+            Err(e) => {
+                warn!(error = %e, "Ignoring unreadable index; run `worky reindex`");
+                None
+            }
+        }
+    }
+
+    /// Write the index to `path`, replacing any previous one whole.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let index = Self {
+            version: INDEX_VERSION,
+            ..self.clone()
+        };
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(&index)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Add or replace the entry for `slug`.
+    pub fn insert(&mut self, slug: impl Into<String>, entry: IndexEntry) {
+        let slug = slug.into();
+        if let Some(old) = self.items.get(&slug).and_then(|e| e.external_id.as_ref())
+            && self.external_ids.get(old) == Some(&slug)
+        {
+            self.external_ids.remove(old);
+        }
+        if let Some(external_id) = &entry.external_id {
+            self.external_ids.insert(external_id.clone(), slug.clone());
+        }
+        self.items.insert(slug, entry);
+    }
+
+    /// The slug of the item last indexed with `external_id`, if any.
+    #[must_use]
+    pub fn slug_for_external_id(&self, external_id: &str) -> Option<&str> {
+        self.external_ids.get(external_id).map(String::as_str)
+    }
+
+    /// Number of indexed items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no items are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The entry for `slug`, if `meta_path` hasn't changed since it was
+    /// indexed.
+    ///
+    /// As with git's index, an entry whose `meta.yml` was modified no earlier
+    /// than the index was written isn't trusted either: a second write in the
+    /// same clock tick could leave the modification time unchanged.
+    #[must_use]
+    pub fn fresh(&self, slug: &str, meta_path: &Path) -> Option<&IndexEntry> {
+        let entry = self.items.get(slug)?;
+        let written = self.written?;
+        let stamp = Stamp::of(meta_path).ok()?;
+
+        (stamp.mtime == entry.mtime && stamp.len == entry.len && entry.mtime < written)
+            .then_some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Workspace;
+    use crate::workspace::ItemFilter;
+    use std::fs::File;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_skips_and_invalidates() {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        let first = ws.create_item("First").unwrap();
+        ws.create_item("Second").unwrap();
+
+        // Backdate the files so the index can trust them straight away
+        let meta = ws.items_dir().join("first").join("meta.yml");
+        for slug in ["first", "second"] {
+            File::options()
+                .write(true)
+                .open(ws.items_dir().join(slug).join("meta.yml"))
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_hours(1))
+                .unwrap();
+        }
+        assert_eq!(ws.reindex().unwrap(), 2);
+
+        // Make the index disagree with the file to see when it is used
+        let mut index = ItemIndex::load(&ws.index_path()).unwrap();
+        index.items.get_mut("first").unwrap().state = "DONE".to_string();
+        index.save(&ws.index_path()).unwrap();
+
+        let todo = ItemFilter::new().state("TODO");
+        let titles = |filter: Option<&ItemFilter>| -> Vec<String> {
+            let mut titles: Vec<String> = ws
+                .list_items(filter)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.title)
+                .collect();
+            titles.sort();
+            titles
+        };
+        assert_eq!(titles(Some(&todo)), ["Second"]);
+        assert_eq!(titles(None), ["First", "Second"]);
+
+        // Rewriting the file, even unchanged, makes the entry stale
+        fs::write(&meta, fs::read(&meta).unwrap()).unwrap();
+        assert_eq!(titles(Some(&todo)), ["First", "Second"]);
+        assert_eq!(ws.get_item(&first.uid).unwrap().state, "TODO");
+
+        // Saving an item updates its entry
+        ws.assign("fs:second", Some("alice")).unwrap();
+        let index = ItemIndex::load(&ws.index_path()).unwrap();
+        assert_eq!(index.items["second"].assignee.as_deref(), Some("alice"));
+
+        // A corrupt index is ignored
+        fs::write(ws.index_path(), "{").unwrap();
+        assert!(ItemIndex::load(&ws.index_path()).is_none());
+        assert_eq!(titles(Some(&todo)), ["First", "Second"]);
+    }
+}
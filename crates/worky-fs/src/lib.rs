@@ -6,10 +6,36 @@
 //! - `notes.md`: Free-form notes
 //! - `artifacts/`: Attached files
 
+pub mod actor;
+pub mod artifact;
+pub mod audit;
+pub mod audit_log;
+pub mod compact;
 pub mod config;
+pub mod doctor;
 pub mod error;
+pub mod export;
+pub mod ical;
+pub mod index;
+pub mod notes;
+pub mod provenance;
+pub mod stats;
+pub mod template;
 pub mod workspace;
+pub mod workyignore;
 
+pub use artifact::Artifact;
+pub use audit::{AiAudit, Audited};
+pub use audit_log::AuditEntry;
+pub use compact::CompactSummary;
 pub use config::WorkspaceConfig;
+pub use doctor::DoctorReport;
 pub use error::{FsError, Result};
+pub use export::{ExportFormat, ImportMode, ImportSummary};
+pub use index::ItemIndex;
+pub use notes::Notes;
+pub use provenance::FieldProvenance;
+pub use stats::WorkspaceStats;
+pub use template::ItemTemplate;
 pub use workspace::Workspace;
+pub use workyignore::WorkyIgnore;
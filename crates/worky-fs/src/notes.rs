@@ -0,0 +1,136 @@
+//! `notes.md`, with optional YAML front matter.
+//!
+//! Notes that begin with a `---` line and have a closing `---` line carry a
+//! YAML mapping between the two, which `worky sync-notes` merges into the
+//! item's custom fields. The file itself is never rewritten, so a front
+//! matter block that doesn't parse costs nothing but a warning.
+
+use serde_json::{Map, Value};
+use worky_core::CoreError;
+
+/// The contents of an item's `notes.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notes {
+    /// The whole file, front matter included.
+    pub text: String,
+    /// Byte offset of the body in `text`.
+    body_start: usize,
+    front_matter: FrontMatter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FrontMatter {
+    None,
+    Parsed(Map<String, Value>),
+    Invalid(String),
+}
+
+impl Notes {
+    /// Split `text` into front matter and body.
+    ///
+    /// A block that doesn't parse as a YAML mapping is kept as an error for
+    /// [`Self::front_matter`]; the body still starts after it. If the block is
+    /// never closed, the whole text is the body.
+    #[must_use]
+    pub fn parse(text: String) -> Self {
+        let Some(yaml_start) = opening_delimiter(&text) else {
+            return Self {
+                text,
+                body_start: 0,
+                front_matter: FrontMatter::None,
+            };
+        };
+        let Some((yaml_end, body_start)) = closing_delimiter(&text, yaml_start) else {
+            return Self {
+                text,
+                body_start: 0,
+                front_matter: FrontMatter::Invalid(
+                    "front matter has no closing `---` line".to_string(),
+                ),
+            };
+        };
+
+        let yaml = &text[yaml_start..yaml_end];
+        let front_matter = if yaml.trim().is_empty() {
+            FrontMatter::Parsed(Map::new())
+        } else {
+            match serde_yaml::from_str::<Value>(yaml) {
+                Ok(Value::Object(map)) => FrontMatter::Parsed(map),
+                Ok(_) => FrontMatter::Invalid("front matter is not a mapping".to_string()),
+                Err(e) => FrontMatter::Invalid(format!("front matter is not valid YAML: {e}")),
+            }
+        };
+
+        Self {
+            text,
+            body_start,
+            front_matter,
+        }
+    }
+
+    /// The notes without their front matter.
+    #[must_use]
+    pub fn body(&self) -> &str {
+        &self.text[self.body_start..]
+    }
+
+    /// The front matter's keys and values, or `None` if there is none.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if the front matter is malformed.
+    pub fn front_matter(&self) -> worky_core::Result<Option<&Map<String, Value>>> {
+        match &self.front_matter {
+            FrontMatter::None => Ok(None),
+            FrontMatter::Parsed(map) => Ok(Some(map)),
+            FrontMatter::Invalid(message) => Err(CoreError::Validation(message.clone())),
+        }
+    }
+}
+
+/// Offset just past an opening `---` line at the very start of `text`.
+fn opening_delimiter(text: &str) -> Option<usize> {
+    let first_line = text.split_inclusive('\n').next()?;
+    (first_line.trim_end() == "---").then_some(first_line.len())
+}
+
+/// Where the YAML starting at `from` ends and the body begins, at the next
+/// line that is `---` or `...`.
+fn closing_delimiter(text: &str, from: usize) -> Option<(usize, usize)> {
+    let mut offset = from;
+    for line in text[from..].split_inclusive('\n') {
+        let end = offset + line.len();
+        if matches!(line.trim_end(), "---" | "...") {
+            return Some((offset, end));
+        }
+        offset = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_front_matter() {
+        let notes = Notes::parse("---\nteam: web\npoints: 3\n---\n# Fix login\n".to_string());
+        let front_matter = notes.front_matter().unwrap().unwrap();
+        assert_eq!(front_matter["team"], json!("web"));
+        assert_eq!(front_matter["points"], json!(3));
+        assert_eq!(notes.body(), "# Fix login\n");
+
+        let plain = Notes::parse("# Fix login\n\n---\nnot: front matter\n".to_string());
+        assert_eq!(plain.front_matter().unwrap(), None);
+        assert_eq!(plain.body(), plain.text);
+
+        // Malformed blocks are reported, and the body is left alone
+        let bad = Notes::parse("---\nteam: [web\n---\nBody\n".to_string());
+        assert!(bad.front_matter().is_err());
+        assert_eq!(bad.body(), "Body\n");
+
+        let unclosed = Notes::parse("---\nteam: web\n".to_string());
+        assert!(unclosed.front_matter().is_err());
+        assert_eq!(unclosed.body(), unclosed.text);
+    }
+}
@@ -0,0 +1,145 @@
+//! Who last changed each field of an item, from its event log.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use worky_core::{EventPayload, EventType, WorkEvent, WorkItem};
+
+/// When a field was last changed, and by whom.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldProvenance {
+    /// Time of the most recent change.
+    pub at: DateTime<Utc>,
+    /// Who made it, if recorded.
+    pub actor: Option<String>,
+}
+
+impl FieldProvenance {
+    /// Replay `events` (oldest first) and attribute each field `item`
+    /// currently has, keyed by path (e.g. `state`, `fields.estimate`).
+    ///
+    /// Fields that were set and later removed are left out, as are fields
+    /// with no recorded change (e.g. ones edited by hand in `meta.yml`).
+    ///
+    /// # Panics
+    /// Panics if `item` cannot be converted to JSON, which cannot happen for
+    /// a deserialized item.
+    #[must_use]
+    pub fn from_events(item: &WorkItem, events: &[WorkEvent]) -> BTreeMap<String, Self> {
+        let mut provenance = BTreeMap::new();
+
+        for event in events {
+            let stamp = Self {
+                at: event.timestamp,
+                actor: event.actor.clone(),
+            };
+
+            match (&event.event_type, &event.payload) {
+                (EventType::Created, _) => {
+                    provenance.insert("title".to_string(), stamp.clone());
+                    provenance.insert("state".to_string(), stamp);
+                }
+                (_, EventPayload::StateChange(p)) => {
+                    let to = Value::String(p.to.clone());
+                    record(&mut provenance, "state", &to, &stamp);
+                }
+                (_, EventPayload::AssigneeChange(p)) => {
+                    let to = p.to.clone().map_or(Value::Null, Value::String);
+                    record(&mut provenance, "assignee", &to, &stamp);
+                }
+                (_, EventPayload::Label(_)) => {
+                    provenance.insert("labels".to_string(), stamp);
+                }
+                (_, EventPayload::FieldChange(p)) => {
+                    record(&mut provenance, &p.path, &p.new_value, &stamp);
+                }
+                _ => {}
+            }
+        }
+
+        // Drop anything the item no longer has, e.g. labels that were all removed
+        let current = serde_json::to_value(item).expect("work items serialize to JSON");
+        provenance.retain(|path, _| {
+            path.split('.')
+                .try_fold(&current, |value, key| value.get(key))
+                .is_some_and(|value| !value.is_null())
+        });
+
+        provenance
+    }
+}
+
+/// Attribute `path` to `stamp`, superseding anything recorded at, above, or
+/// below it. Objects are attributed leaf by leaf; `null` removes the path.
+fn record(
+    provenance: &mut BTreeMap<String, FieldProvenance>,
+    path: &str,
+    value: &Value,
+    stamp: &FieldProvenance,
+) {
+    provenance.retain(|key, _| key != path && !is_beneath(key, path) && !is_beneath(path, key));
+
+    match value {
+        Value::Null => {}
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                record(provenance, &format!("{path}.{key}"), child, stamp);
+            }
+        }
+        _ => {
+            provenance.insert(path.to_string(), stamp.clone());
+        }
+    }
+}
+
+/// Whether `path` is a strict descendant of `ancestor`.
+fn is_beneath(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_provenance() {
+        let mut item = WorkItem::new("fs:fix-login", "Fix login");
+        item.state = "IN_PROGRESS".to_string();
+        item.fields.insert("estimate".to_string(), json!(3));
+
+        let events = vec![
+            WorkEvent::created("Fix login", "alice").with_actor("alice"),
+            // Adding the first field records the whole object
+            WorkEvent::field_changed("fields", None, json!({ "estimate": 1, "team": "web" }))
+                .with_actor("alice"),
+            WorkEvent::state_changed("TODO", "IN_PROGRESS").with_actor("bob"),
+            WorkEvent::field_changed("fields.estimate", Some(json!(1)), json!(3))
+                .with_actor("carol"),
+            // Removed fields drop out
+            WorkEvent::field_changed("fields.team", Some(json!("web")), Value::Null),
+            WorkEvent::assigned(None, Some("bob".to_string())),
+            WorkEvent::assigned(Some("bob".to_string()), None),
+            WorkEvent::label_added("bug"),
+            WorkEvent::label_removed("bug"),
+        ];
+
+        let provenance = FieldProvenance::from_events(&item, &events);
+        let actors: Vec<(&str, Option<&str>)> = provenance
+            .iter()
+            .map(|(path, p)| (path.as_str(), p.actor.as_deref()))
+            .collect();
+
+        assert_eq!(
+            actors,
+            [
+                ("fields.estimate", Some("carol")),
+                ("state", Some("bob")),
+                ("title", Some("alice")),
+            ]
+        );
+        assert_eq!(provenance["state"].at, events[2].timestamp);
+    }
+}
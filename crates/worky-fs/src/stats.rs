@@ -0,0 +1,98 @@
+//! Aggregate statistics over a workspace.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use worky_core::WorkItem;
+
+/// Summary counts for a set of work items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WorkspaceStats {
+    /// Total number of items.
+    pub total: usize,
+    /// Number of items not in a terminal state.
+    pub open: usize,
+    /// Number of items without an assignee.
+    pub unassigned: usize,
+    /// Item count per state.
+    pub by_state: BTreeMap<String, usize>,
+    /// Item count per assignee.
+    pub by_assignee: BTreeMap<String, usize>,
+    /// Item count per label.
+    pub by_label: BTreeMap<String, usize>,
+    /// Average age of open items in days, if there are any.
+    pub average_open_age_days: Option<f64>,
+}
+
+impl WorkspaceStats {
+    /// Compute statistics for `items` as of `now`.
+    #[must_use]
+    pub fn from_items(
+        items: &[WorkItem],
+        now: DateTime<Utc>,
+        is_terminal: impl Fn(&str) -> bool,
+    ) -> Self {
+        let mut stats = Self {
+            total: items.len(),
+            ..Self::default()
+        };
+        let mut open_age_secs = 0i64;
+
+        for item in items {
+            *stats.by_state.entry(item.state.clone()).or_default() += 1;
+
+            match &item.assignee {
+                Some(assignee) => *stats.by_assignee.entry(assignee.clone()).or_default() += 1,
+                None => stats.unassigned += 1,
+            }
+
+            for label in &item.labels {
+                *stats.by_label.entry(label.clone()).or_default() += 1;
+            }
+
+            if !is_terminal(&item.state) {
+                stats.open += 1;
+                open_age_secs += (now - item.created_at).num_seconds().max(0);
+            }
+        }
+
+        if stats.open > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let days = open_age_secs as f64 / 86_400.0 / stats.open as f64;
+            stats.average_open_age_days = Some((days * 10.0).round() / 10.0);
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_stats_from_items() {
+        let now = Utc::now();
+        let mut old = WorkItem::new("fs:a", "A")
+            .with_assignee("alice")
+            .with_labels(["backend"]);
+        old.created_at = now - Duration::days(4);
+        let mut recent = WorkItem::new("fs:b", "B").with_labels(["backend", "bug"]);
+        recent.created_at = now - Duration::days(2);
+        let done = WorkItem::new("fs:c", "C")
+            .with_state("DONE")
+            .with_assignee("alice");
+
+        let stats = WorkspaceStats::from_items(&[old, recent, done], now, |s| s == "DONE");
+
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.open, 2);
+        assert_eq!(stats.unassigned, 1);
+        assert_eq!(stats.by_state["TODO"], 2);
+        assert_eq!(stats.by_state["DONE"], 1);
+        assert_eq!(stats.by_assignee["alice"], 2);
+        assert_eq!(stats.by_label["backend"], 2);
+        assert_eq!(stats.average_open_age_days, Some(3.0));
+    }
+}
@@ -0,0 +1,153 @@
+//! Item templates stored in `.worky/templates/<name>.yml`.
+//!
+//! A template's title and description may contain `{{name}}` placeholders,
+//! filled in by [`ItemTemplate::render`].
+
+use crate::workspace::NewItemFields;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use worky_core::{CoreError, Result};
+
+/// Defaults shared by a recurring kind of item, such as a bug report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemTemplate {
+    /// Title for items created without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Variables that must be given to render the template.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<String>,
+
+    /// Initial state (workspace default if not set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    /// Labels added to every item created from this template.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+
+    /// Description skeleton.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ItemTemplate {
+    /// This template with each `{{name}}` in its title and description
+    /// replaced by the value of `name` in `vars`. Placeholders without a
+    /// value are left as they are.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` listing the declared `variables`
+    /// missing from `vars`.
+    pub fn render(&self, vars: &BTreeMap<String, String>) -> Result<Self> {
+        let missing: Vec<&str> = self
+            .variables
+            .iter()
+            .filter(|name| !vars.contains_key(*name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(CoreError::Validation(format!(
+                "missing template variables: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(Self {
+            title: self.title.as_deref().map(|text| substitute(text, vars)),
+            description: self.description.as_deref().map(|text| substitute(text, vars)),
+            ..self.clone()
+        })
+    }
+
+    /// Fill in anything the caller left unset. Labels are merged.
+    pub fn apply_to(&self, fields: &mut NewItemFields) {
+        if fields.state.is_none() {
+            fields.state.clone_from(&self.state);
+        }
+
+        if fields.description.is_none() {
+            fields.description.clone_from(&self.description);
+        }
+
+        for label in &self.labels {
+            if !fields.labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                fields.labels.push(label.clone());
+            }
+        }
+    }
+}
+
+/// `text` with each `{{name}}` (spaces inside the braces allowed) that has
+/// a value in `vars` replaced by it.
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        match vars.get(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_to_keeps_explicit_values() {
+        let template = ItemTemplate {
+            state: Some("TRIAGE".into()),
+            labels: vec!["bug".into()],
+            description: Some("## Steps to reproduce\n".into()),
+            ..ItemTemplate::default()
+        };
+
+        let mut fields = NewItemFields {
+            state: Some("TODO".into()),
+            labels: vec!["Bug".into(), "ui".into()],
+            ..NewItemFields::default()
+        };
+        template.apply_to(&mut fields);
+
+        assert_eq!(fields.state.as_deref(), Some("TODO"));
+        assert_eq!(fields.labels, vec!["Bug", "ui"]);
+        assert_eq!(fields.description.as_deref(), Some("## Steps to reproduce\n"));
+    }
+
+    #[test]
+    fn test_render() {
+        let template = ItemTemplate {
+            title: Some("{{service}} outage".into()),
+            variables: vec!["service".into(), "sev".into()],
+            description: Some("Service: {{ service }}, sev {{sev}}, owner {{owner}} {{".into()),
+            ..ItemTemplate::default()
+        };
+        let mut vars = BTreeMap::from([("service".to_string(), "auth".to_string())]);
+
+        let err = template.render(&vars).unwrap_err();
+        assert!(err.to_string().contains("missing template variables: sev"));
+
+        vars.insert("sev".into(), "2".into());
+        let rendered = template.render(&vars).unwrap();
+        assert_eq!(rendered.title.as_deref(), Some("auth outage"));
+        assert_eq!(
+            rendered.description.as_deref(),
+            Some("Service: auth, sev 2, owner {{owner}} {{")
+        );
+    }
+}
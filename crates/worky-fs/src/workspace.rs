@@ -1,20 +1,41 @@
 //! Workspace management and work item operations.
 
-use crate::config::WorkspaceConfig;
+use crate::actor;
+use crate::artifact::{self, Artifact};
+use crate::audit_log::{self, AuditEntry, AUDIT_FILE};
+use crate::compact::{self, CompactSummary};
+use crate::config::{Durability, SlugConflict, WorkspaceConfig, CONFIG_VERSION};
+use crate::doctor::{DoctorReport, Finding, Problem};
 use crate::error::{FsError, Result};
-use chrono::{DateTime, Utc};
+use crate::export::{ExportFormat, ExportedItem, ImportMode, ImportSummary};
+use crate::ical;
+use crate::index::{IndexEntry, ItemIndex, Stamp, INDEX_FILE};
+use crate::notes::Notes;
+use crate::provenance::FieldProvenance;
+use crate::stats::WorkspaceStats;
+use crate::template::ItemTemplate;
+use crate::workyignore::{WorkyIgnore, IGNORE_FILE};
+use chrono::{DateTime, Duration, Utc};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use slug::slugify;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use worky_core::{
-    apply_merge_patch, apply_set_operation, diff_values, SetOperation, WorkEvent, WorkItem,
+    apply_json_patch, apply_merge_patch, apply_set_operation, diff_values, ChecklistItem,
+    CoreError, EventPayload, EventType, PatchOp, SetOperation, WorkEvent, WorkItem,
 };
 
 /// Directory name for worky configuration.
 const WORKY_DIR: &str = ".worky";
+/// Gap left between neighbouring ranks when a column is renumbered.
+const RANK_STEP: f64 = 1024.0;
+/// Directory under `.worky` holding item templates.
+const TEMPLATES_DIR: &str = "templates";
 /// Configuration file name.
 const CONFIG_FILE: &str = "config.yml";
 /// Items directory name.
@@ -25,14 +46,36 @@ const META_FILE: &str = "meta.yml";
 const EVENTS_FILE: &str = "events.ndjson";
 /// Notes file name within item directory.
 const NOTES_FILE: &str = "notes.md";
+/// Quarantine for event lines that failed to parse.
+const BAD_EVENTS_FILE: &str = "events.ndjson.bad";
+/// Original event lines kept by [`Workspace::compact_events`].
+const ARCHIVE_EVENTS_FILE: &str = "events.ndjson.archive";
+/// Attached files directory within item directory.
+const ARTIFACTS_DIR: &str = "artifacts";
+/// Longest slug generated from a title, well within path-length limits.
+const MAX_SLUG_LEN: usize = 80;
+/// Hex digits of the hash that ends a shortened slug.
+const SLUG_HASH_LEN: usize = 8;
+/// Hex digits of an item version.
+const VERSION_LEN: usize = 16;
+/// Fewest bytes an event written by worky takes up in a log, ID included.
+/// A log shorter than `compact_threshold` times this can't be over it.
+const MIN_EVENT_LEN: u64 = 64;
 
 /// A workspace manages work items on the filesystem.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Workspace {
     /// Root path of the workspace.
     root: PathBuf,
     /// Workspace configuration.
     config: WorkspaceConfig,
+    /// Whether state changes respect `workflow.wip_limits`.
+    enforce_wip_limits: bool,
+    /// Who new events are attributed to.
+    actor: String,
+    /// Whether writes belong to a [`Self::transaction`], whose rollback
+    /// would corrupt a log compacted in the meantime.
+    in_transaction: bool,
 }
 
 impl Workspace {
@@ -41,6 +84,16 @@ impl Workspace {
     /// # Errors
     /// Returns error if workspace already exists or IO fails.
     pub fn init(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init_with_config(path, WorkspaceConfig::default())
+    }
+
+    /// Initialize a new workspace at the given path with `config` as its
+    /// `.worky/config.yml`.
+    ///
+    /// # Errors
+    /// Returns `FsError::WorkspaceExists` if there is already a workspace
+    /// there, or error if IO fails.
+    pub fn init_with_config(path: impl AsRef<Path>, config: WorkspaceConfig) -> Result<Self> {
         let root = path.as_ref().to_path_buf();
         let worky_dir = root.join(WORKY_DIR);
 
@@ -52,15 +105,20 @@ impl Workspace {
         fs::create_dir_all(&worky_dir)?;
         fs::create_dir_all(root.join(ITEMS_DIR))?;
 
-        // Write default config
-        let config = WorkspaceConfig::default();
+        // Write config
         let config_path = worky_dir.join(CONFIG_FILE);
         let config_content = serde_yaml::to_string(&config)?;
         fs::write(&config_path, config_content)?;
 
         info!(path = %root.display(), "Initialized workspace");
 
-        Ok(Self { root, config })
+        Ok(Self {
+            root,
+            config,
+            enforce_wip_limits: true,
+            actor: actor::current(),
+            in_transaction: false,
+        })
     }
 
     /// Open an existing workspace at the given path.
@@ -76,12 +134,70 @@ impl Workspace {
             return Err(FsError::WorkspaceNotFound(root));
         }
 
-        let config_content = fs::read_to_string(&config_path)?;
+        let mut config_content = fs::read_to_string(&config_path)?;
+        // Older files are read as if migrated; only `migrate_config` saves that
+        if let Some((migrated, changes)) = WorkspaceConfig::migrate(&config_content)? {
+            debug!(?changes, version = CONFIG_VERSION, "Migrated workspace config in memory");
+            config_content = migrated;
+        }
         let config: WorkspaceConfig = serde_yaml::from_str(&config_content)?;
 
         debug!(path = %root.display(), "Opened workspace");
 
-        Ok(Self { root, config })
+        Ok(Self {
+            root,
+            config,
+            enforce_wip_limits: true,
+            actor: actor::current(),
+            in_transaction: false,
+        })
+    }
+
+    /// Open the workspace containing `start`, checking `start` and then each
+    /// parent directory for `.worky/config.yml`, the way git finds `.git`.
+    ///
+    /// # Errors
+    /// Returns `FsError::WorkspaceNotFound` if no directory up to the
+    /// filesystem root has one, or error if its config is invalid.
+    pub fn discover(start: impl AsRef<Path>) -> Result<Self> {
+        let start = start.as_ref();
+
+        start
+            .ancestors()
+            .find(|dir| dir.join(WORKY_DIR).join(CONFIG_FILE).is_file())
+            .map_or_else(
+                || Err(FsError::WorkspaceNotFound(start.to_path_buf())),
+                Self::open,
+            )
+    }
+
+    /// Attribute new events to `actor` instead of the one resolved from the
+    /// environment (see [`crate::actor`]).
+    #[must_use]
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = actor.into();
+        self
+    }
+
+    /// Who new events are attributed to.
+    #[must_use]
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Let state changes exceed `workflow.wip_limits`, e.g. for `--force`.
+    #[must_use]
+    pub const fn ignoring_wip_limits(mut self) -> Self {
+        self.enforce_wip_limits = false;
+        self
+    }
+
+    /// Number the slugs of new items whose title is taken, whatever
+    /// `slug.on_conflict` says, e.g. for imports.
+    #[must_use]
+    pub const fn suffixing_slugs(mut self) -> Self {
+        self.config.slug.on_conflict = SlugConflict::Suffix;
+        self
     }
 
     /// Get the workspace root path.
@@ -92,12 +208,64 @@ impl Workspace {
 
     /// Get the workspace configuration.
     #[must_use]
-    pub fn config(&self) -> &WorkspaceConfig {
+    pub const fn config(&self) -> &WorkspaceConfig {
         &self.config
     }
 
+    /// Set the config key `key` (e.g. `defaults.state`) to `value` and save
+    /// `.worky/config.yml`. A null `value` restores the default.
+    ///
+    /// # Errors
+    /// Returns error if the key is unknown, the value has the wrong type, or
+    /// the config cannot be read or written.
+    pub fn set_config(&mut self, key: &str, value: &Value) -> Result<()> {
+        let path = self.config_path();
+        let raw = fs::read_to_string(&path)?;
+        let (yaml, config) = WorkspaceConfig::edit(&raw, key, value)?;
+        replace_file(&path, &yaml)?;
+        self.config = config;
+
+        info!(key, value = %value, "Updated config");
+        Ok(())
+    }
+
+    /// Rewrite `.worky/config.yml` in the [`CONFIG_VERSION`] format, returning
+    /// a note per change, or none if the file is already current.
+    ///
+    /// Opening a workspace migrates an older config only in memory, so the
+    /// file, comments and all, is left alone until this is called.
+    ///
+    /// # Errors
+    /// Returns error if the config is invalid or cannot be read or written.
+    pub fn migrate_config(&mut self) -> Result<Vec<String>> {
+        let path = self.config_path();
+        let raw = fs::read_to_string(&path)?;
+        let Some((yaml, changes)) = WorkspaceConfig::migrate(&raw)? else {
+            return Ok(Vec::new());
+        };
+        let config = serde_yaml::from_str(&yaml)?;
+        replace_file(&path, &yaml)?;
+        self.config = config;
+
+        info!(?changes, version = CONFIG_VERSION, "Migrated workspace config");
+        Ok(changes)
+    }
+
+    /// Get the path of `.worky/config.yml`.
+    #[must_use]
+    pub fn config_path(&self) -> PathBuf {
+        self.root.join(WORKY_DIR).join(CONFIG_FILE)
+    }
+
+    /// Check whether a state is terminal according to the workflow config.
+    #[must_use]
+    pub fn is_terminal(&self, state: &str) -> bool {
+        self.config.workflow.is_terminal(state)
+    }
+
     /// Get the items directory path.
-    fn items_dir(&self) -> PathBuf {
+    #[must_use]
+    pub fn items_dir(&self) -> PathBuf {
         self.root.join(ITEMS_DIR)
     }
 
@@ -107,327 +275,3020 @@ impl Workspace {
     }
 
     /// Extract slug from a UID.
-    fn slug_from_uid(uid: &str) -> Result<&str> {
+    pub(crate) fn slug_from_uid(uid: &str) -> Result<&str> {
         uid.strip_prefix("fs:")
             .ok_or_else(|| FsError::InvalidUid(uid.to_string()))
     }
 
     /// Generate a slug from a title.
-    fn generate_slug(title: &str) -> String {
-        slugify(title)
+    ///
+    /// Slugs longer than [`MAX_SLUG_LEN`] are cut short and end in a hash of
+    /// the full slug, so long titles still get distinct, stable slugs.
+    fn generate_slug(title: &str) -> Result<String> {
+        let slug = slugify(title);
+        if slug.is_empty() || slug == "." || slug == ".." {
+            return Err(FsError::InvalidSlug(format!(
+                "title '{title}' has no letters or digits to build a slug from"
+            )));
+        }
+
+        if slug.len() <= MAX_SLUG_LEN {
+            return Ok(slug);
+        }
+
+        // Slugs are ASCII, so any byte index is a char boundary
+        let hash = &artifact::sha256_hex(slug.as_bytes())[..SLUG_HASH_LEN];
+        let head = slug[..MAX_SLUG_LEN - SLUG_HASH_LEN - 1].trim_end_matches('-');
+        Ok(format!("{head}-{hash}"))
     }
 
     /// Create a new work item.
     ///
+    /// If the slug is taken, `slug.on_conflict: suffix` picks the first free
+    /// `<slug>-2`, `<slug>-3`, ...; the returned item's UID has the final slug.
+    ///
     /// # Errors
-    /// Returns error if item already exists or IO fails.
+    /// Returns error if item already exists (with the default `on_conflict:
+    /// error`) or IO fails.
     pub fn create_item(&self, title: impl Into<String>) -> Result<WorkItem> {
-        let title = title.into();
-        let slug = Self::generate_slug(&title);
-        let uid = format!("fs:{slug}");
+        self.create_item_with(title, &NewItemFields::default())
+    }
+
+    /// Copy an item under a new title.
+    ///
+    /// The copy keeps the assignee, priority, labels, custom fields, and
+    /// notes, but starts in the default state with fresh timestamps and an
+    /// event log holding only a CREATED event that names the source.
+    /// Blockers, rank, and artifacts are not copied.
+    ///
+    /// # Errors
+    /// Returns error if the source doesn't exist or the copy cannot be created.
+    pub fn clone_item(&self, uid: &str, title: impl Into<String>) -> Result<WorkItem> {
+        let source = self.get_item(uid)?;
+        let notes = self.read_notes(uid)?;
 
+        let title = title.into();
+        let slug = self.allocate_slug(&title)?;
+        let new_uid = format!("fs:{slug}");
         let item_dir = self.item_dir(&slug);
-        if item_dir.exists() {
-            return Err(FsError::ItemExists(uid));
-        }
 
-        // Create item directory
         fs::create_dir_all(&item_dir)?;
-        fs::create_dir_all(item_dir.join("artifacts"))?;
-
-        // Create work item
-        let item = WorkItem::new(&uid, &title).with_state(&self.config.defaults.state);
+        fs::create_dir_all(item_dir.join(ARTIFACTS_DIR))?;
 
-        // Write meta.yml
+        let mut item = WorkItem::new(&new_uid, &title).with_state(&self.config.defaults.state);
+        item.assignee = source.assignee;
+        item.priority = source.priority;
+        item.labels = source.labels;
+        item.fields = source.fields;
         self.write_meta(&slug, &item)?;
 
-        // Write empty notes.md
-        fs::write(item_dir.join(NOTES_FILE), format!("# {title}\n\n"))?;
+        // Notes start with the title as a heading; keep that in step
+        let old_heading = format!("# {}\n", source.title);
+        let mut notes = notes.map_or_else(|| format!("{old_heading}\n"), |n| n.text);
+        if notes.starts_with(&old_heading) {
+            notes.replace_range(..old_heading.len(), &format!("# {title}\n"));
+        }
+        fs::write(item_dir.join(NOTES_FILE), notes)?;
 
-        // Append CREATED event
-        let event = WorkEvent::created(&title);
-        self.append_event(&slug, &event)?;
+        self.append_event(&slug, &WorkEvent::cloned(&title, &self.actor, &source.uid))?;
 
-        info!(uid = %uid, title = %title, "Created work item");
+        info!(uid = %new_uid, source = %uid, "Cloned work item");
 
         Ok(item)
     }
 
-    /// Get a work item by UID.
-    ///
-    /// # Errors
-    /// Returns error if item doesn't exist or meta is invalid.
-    pub fn get_item(&self, uid: &str) -> Result<WorkItem> {
-        let slug = Self::slug_from_uid(uid)?;
-        let item_dir = self.item_dir(slug);
-
-        if !item_dir.exists() {
-            return Err(FsError::ItemNotFound(uid.to_string()));
+    /// A free slug for `title`, numbered if the plain one is taken.
+    fn allocate_slug(&self, title: &str) -> Result<String> {
+        let slug = Self::generate_slug(title)?;
+        if self.item_dir(&slug).exists() && self.config.slug.on_conflict == SlugConflict::Error {
+            return Err(FsError::ItemExists(format!("fs:{slug}")));
         }
 
-        self.read_meta(slug)
+        Ok(self.free_slug(&slug))
     }
 
-    /// List all work items, optionally filtered.
-    pub fn list_items(&self, filter: Option<&ItemFilter>) -> Result<Vec<WorkItem>> {
-        let items_dir = self.items_dir();
-        if !items_dir.exists() {
-            return Ok(Vec::new());
+    /// `base`, or the first of `<base>-2`, `<base>-3`, ... that is free.
+    fn free_slug(&self, base: &str) -> String {
+        let mut slug = base.to_string();
+        let mut n = 2;
+        while self.item_dir(&slug).exists() {
+            slug = format!("{base}-{n}");
+            n += 1;
         }
+        slug
+    }
 
-        let mut items = Vec::new();
-
-        for entry in fs::read_dir(&items_dir)? {
-            let entry = entry?;
-            if !entry.file_type()?.is_dir() {
-                continue;
-            }
+    /// Create a new work item with initial field values.
+    ///
+    /// The values are checked like an update (field schema, WIP limits,
+    /// external IDs) before anything is written, so an item that can't have
+    /// them isn't created at all. The log records the creation followed by
+    /// one event per value set.
+    ///
+    /// # Errors
+    /// Returns error if the item already exists, the fields cannot be
+    /// applied, or IO fails.
+    pub fn create_item_with(
+        &self,
+        title: impl Into<String>,
+        fields: &NewItemFields,
+    ) -> Result<WorkItem> {
+        let title = title.into();
+        let slug = self.allocate_slug(&title)?;
+        let uid = format!("fs:{slug}");
 
-            let slug = entry.file_name().to_string_lossy().to_string();
-            let meta_path = entry.path().join(META_FILE);
+        let item = WorkItem::new(&uid, &title).with_state(&self.config.defaults.state);
+        let UpdatePlan { item, events } = self.plan_operations(item, &fields.to_operations())?;
 
-            if !meta_path.exists() {
-                continue;
-            }
+        let item_dir = self.item_dir(&slug);
+        fs::create_dir_all(item_dir.join(ARTIFACTS_DIR))?;
+        self.write_meta(&slug, &item)?;
+        fs::write(item_dir.join(NOTES_FILE), format!("# {title}\n\n"))?;
 
-            match self.read_meta(&slug) {
-                Ok(item) => {
-                    if filter.is_none_or(|f| f.matches(&item)) {
-                        items.push(item);
-                    }
-                }
-                Err(e) => {
-                    debug!(slug = %slug, error = %e, "Failed to read item, skipping");
-                }
-            }
-        }
+        let created = std::iter::once(WorkEvent::created(&title, &self.actor));
+        self.append_events(&slug, &created.chain(events).collect::<Vec<_>>())?;
 
-        // Sort by updated_at descending
-        items.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        info!(uid = %uid, title = %title, "Created work item");
 
-        Ok(items)
+        Ok(item)
     }
 
-    /// Update a work item with set operations.
+    /// Names of the templates in `.worky/templates`, sorted.
     ///
     /// # Errors
-    /// Returns error if item doesn't exist or update fails.
-    pub fn update_item(&self, uid: &str, operations: &[SetOperation]) -> Result<WorkItem> {
-        let slug = Self::slug_from_uid(uid)?;
-        let mut item = self.read_meta(slug)?;
-        let old_item = item.clone();
-
-        // Convert to JSON for patching
-        let mut json_value = serde_json::to_value(&item)?;
-
-        for op in operations {
-            apply_set_operation(&mut json_value, op)?;
+    /// Returns error if the templates directory cannot be read.
+    pub fn list_templates(&self) -> Result<Vec<String>> {
+        let dir = self.templates_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
         }
 
-        // Deserialize back
-        item = serde_json::from_value(json_value)?;
-        item.touch();
-
-        // Generate events for changes
-        let old_json = serde_json::to_value(&old_item)?;
-        let new_json = serde_json::to_value(&item)?;
-        let changes = diff_values(&old_json, &new_json);
-
-        for (path, old_val, new_val) in changes {
-            // Skip updated_at changes
-            if path == "updated_at" {
-                continue;
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "yml")
+                && let Some(name) = path.file_stem()
+            {
+                names.push(name.to_string_lossy().to_string());
             }
-
-            let event = if path == "state" {
-                WorkEvent::state_changed(
-                    old_val
-                        .as_ref()
-                        .and_then(|v| v.as_str())
-                        .unwrap_or(""),
-                    new_val.as_str().unwrap_or(""),
-                )
-            } else if path == "assignee" {
-                WorkEvent::assigned(
-                    old_val.as_ref().and_then(|v| v.as_str()).map(String::from),
-                    new_val.as_str().map(String::from),
-                )
-            } else {
-                WorkEvent::field_changed(path, old_val, new_val)
-            };
-
-            self.append_event(slug, &event)?;
         }
 
-        // Write updated meta
-        self.write_meta(slug, &item)?;
-
-        info!(uid = %uid, "Updated work item");
-
-        Ok(item)
+        names.sort();
+        Ok(names)
     }
 
-    /// Apply a JSON merge patch to a work item.
+    /// Load a template by name.
     ///
     /// # Errors
-    /// Returns error if item doesn't exist or patch fails.
-    pub fn patch_item(&self, uid: &str, patch: &Value) -> Result<WorkItem> {
-        let slug = Self::slug_from_uid(uid)?;
-        let item = self.read_meta(slug)?;
-
-        let old_json = serde_json::to_value(&item)?;
-        let mut new_json = old_json.clone();
-        apply_merge_patch(&mut new_json, patch);
-
-        // Ensure updated_at is refreshed
-        new_json["updated_at"] = serde_json::to_value(Utc::now())?;
+    /// Returns `FsError::TemplateNotFound` listing the available names if it
+    /// doesn't exist, or error if the file is invalid.
+    pub fn load_template(&self, name: &str) -> Result<ItemTemplate> {
+        let path = self.templates_dir().join(format!("{name}.yml"));
 
-        let new_item: WorkItem = serde_json::from_value(new_json.clone())?;
+        // Names are file stems; anything with a separator can't be one
+        let is_plain_name = !name.is_empty() && !name.contains(['/', '\\', '.']);
+        if !is_plain_name || !path.exists() {
+            return Err(FsError::TemplateNotFound {
+                name: name.to_string(),
+                available: self.list_templates()?,
+            });
+        }
 
-        // Generate events for changes
-        let changes = diff_values(&old_json, &new_json);
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
 
-        for (path, old_val, new_val) in changes {
-            if path == "updated_at" {
-                continue;
-            }
+    fn templates_dir(&self) -> PathBuf {
+        self.root.join(WORKY_DIR).join(TEMPLATES_DIR)
+    }
 
-            let event = if path == "state" {
-                WorkEvent::state_changed(
-                    old_val
-                        .as_ref()
-                        .and_then(|v| v.as_str())
-                        .unwrap_or(""),
-                    new_val.as_str().unwrap_or(""),
-                )
-            } else {
-                WorkEvent::field_changed(path, old_val, new_val)
-            };
+    /// Get a work item by what [`Self::resolve_uid`] accepts.
+    ///
+    /// # Errors
+    /// Returns error if item doesn't exist or meta is invalid.
+    pub fn get_item(&self, uid: &str) -> Result<WorkItem> {
+        self.read_meta(&self.resolve_slug(uid)?)
+    }
 
-            self.append_event(slug, &event)?;
-        }
+    /// Get the item a stored reference, such as a `blocked_by` entry, names.
+    ///
+    /// Unlike [`Self::get_item`], only the exact UID matches: a reference to
+    /// a deleted item must not land on another whose slug merely starts the
+    /// same way.
+    ///
+    /// # Errors
+    /// Returns `FsError::ItemNotFound` if no item has exactly that UID, or
+    /// error if its meta is invalid.
+    pub fn get_linked_item(&self, uid: &str) -> Result<WorkItem> {
+        let slug = self
+            .exact_slug(uid)
+            .ok_or_else(|| FsError::ItemNotFound(uid.to_string()))?;
+        self.read_meta(slug)
+    }
 
-        self.write_meta(slug, &new_item)?;
+    /// The item's current version: a hash of its `meta.yml`, so it changes
+    /// with every write to the item. See [`Self::update_item_if`].
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or its meta cannot be read.
+    pub fn item_version(&self, uid: &str) -> Result<String> {
+        self.version_of(&self.resolve_slug(uid)?)
+    }
 
-        info!(uid = %uid, "Patched work item");
+    fn version_of(&self, slug: &str) -> Result<String> {
+        let meta = fs::read(self.item_dir(slug).join(META_FILE))?;
+        Ok(artifact::sha256_hex(&meta)[..VERSION_LEN].to_string())
+    }
 
-        Ok(new_item)
+    /// Resolve what was typed for an item to its UID: the exact UID, then
+    /// `fs:` plus `input`, then the item with `input` as its external ID,
+    /// then the one slug starting with `input` or, failing that, containing
+    /// it. Every lookup by UID goes through this, so reads and writes always
+    /// agree on the item.
+    ///
+    /// Slugs are only scanned when no exact form names an item.
+    ///
+    /// # Errors
+    /// Returns [`FsError::AmbiguousUid`] if several slugs match, or
+    /// [`FsError::ItemNotFound`] if none do.
+    pub fn resolve_uid(&self, input: &str) -> Result<String> {
+        self.resolve_slug(input).map(|slug| format!("fs:{slug}"))
     }
 
-    /// Append an event to an item's event log.
-    pub fn append_event(&self, slug: &str, event: &WorkEvent) -> Result<()> {
-        let events_path = self.item_dir(slug).join(EVENTS_FILE);
+    /// The slug of the item [`Self::resolve_uid`] finds for `input`.
+    pub(crate) fn resolve_slug(&self, input: &str) -> Result<String> {
+        if let Some(slug) = self.exact_slug(input) {
+            return Ok(slug.to_string());
+        }
+        match self.find_by_external_id(input) {
+            Ok(item) => Ok(Self::slug_from_uid(&item.uid)?.to_string()),
+            Err(FsError::ItemNotFound(_)) => self.match_slug(input),
+            Err(e) => Err(e),
+        }
+    }
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&events_path)?;
+    /// The slug `input` names exactly, as `fs:<slug>` or a bare slug, if
+    /// that item exists.
+    pub(crate) fn exact_slug<'a>(&self, input: &'a str) -> Option<&'a str> {
+        let slug = input.strip_prefix("fs:").unwrap_or(input);
+        let plain = !slug.is_empty() && slug != "." && slug != ".." && !slug.contains(['/', '\\']);
+        (plain && self.item_dir(slug).exists()).then_some(slug)
+    }
 
-        let json_line = serde_json::to_string(event)?;
-        writeln!(file, "{json_line}")?;
+    /// The one slug starting with `input`, or failing that containing it,
+    /// ignoring case.
+    fn match_slug(&self, input: &str) -> Result<String> {
+        let needle = input.strip_prefix("fs:").unwrap_or(input).trim().to_lowercase();
+        if needle.is_empty() {
+            return Err(FsError::ItemNotFound(input.to_string()));
+        }
 
-        debug!(slug = %slug, event_type = %event.event_type, "Appended event");
+        let (mut prefixed, mut containing) = (Vec::new(), Vec::new());
+        for slug in self.slugs()? {
+            let lower = slug.to_lowercase();
+            if lower.starts_with(&needle) {
+                prefixed.push(slug);
+            } else if lower.contains(&needle) {
+                containing.push(slug);
+            }
+        }
 
-        Ok(())
+        let mut matches = if prefixed.is_empty() { containing } else { prefixed };
+        match matches.len() {
+            0 => Err(FsError::ItemNotFound(input.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => {
+                matches.sort();
+                Err(FsError::AmbiguousUid {
+                    input: input.to_string(),
+                    candidates: matches.iter().map(|slug| format!("fs:{slug}")).collect(),
+                })
+            }
+        }
     }
 
-    /// Read events for an item, optionally filtered by time.
-    pub fn read_events(&self, uid: &str, since: Option<DateTime<Utc>>) -> Result<Vec<WorkEvent>> {
-        let slug = Self::slug_from_uid(uid)?;
-        let events_path = self.item_dir(slug).join(EVENTS_FILE);
+    /// The item whose `external_id` is `external_id`.
+    ///
+    /// With an index (see [`Self::reindex`]) the item it maps the ID to is
+    /// read first. Failing that, only items whose entry is stale or missing,
+    /// e.g. written by hand or by another process since, are read, so a
+    /// lost entry can't hide a match. Without one, items are read until a
+    /// match.
+    ///
+    /// # Errors
+    /// Returns `FsError::ItemNotFound` if no item has that external ID, or
+    /// error if the items directory cannot be read.
+    pub fn find_by_external_id(&self, external_id: &str) -> Result<WorkItem> {
+        let not_found = || FsError::ItemNotFound(external_id.to_string());
+        let matches = |item: &WorkItem| item.external_id.as_deref() == Some(external_id);
 
-        if !events_path.exists() {
-            return Ok(Vec::new());
+        let index = ItemIndex::load(&self.index_path());
+        if let Some(item) = index
+            .as_ref()
+            .and_then(|index| index.slug_for_external_id(external_id))
+            .and_then(|slug| self.read_meta(slug).ok())
+            .filter(matches)
+        {
+            return Ok(item);
         }
 
-        let file = File::open(&events_path)?;
-        let reader = BufReader::new(file);
-        let mut events = Vec::new();
-
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
+        for slug in self.slugs()? {
+            let entry = index
+                .as_ref()
+                .and_then(|index| index.fresh(&slug, &self.item_dir(&slug).join(META_FILE)));
+            if entry.is_some_and(|entry| entry.external_id.as_deref() != Some(external_id)) {
                 continue;
             }
-
-            let event: WorkEvent = serde_json::from_str(&line)?;
-
-            if since.is_none_or(|s| event.timestamp >= s) {
-                events.push(event);
+            match self.read_meta(&slug) {
+                Ok(item) if matches(&item) => return Ok(item),
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(slug = %slug, error = %e, "Skipping unreadable item; run `worky doctor`");
+                }
             }
         }
 
-        Ok(events)
+        Err(not_found())
     }
 
-    /// Add a comment/log entry to an item.
-    pub fn add_comment(&self, uid: &str, message: impl Into<String>) -> Result<()> {
-        let slug = Self::slug_from_uid(uid)?;
+    /// Check that `item`'s external ID, if it has one, isn't blank or used
+    /// by another item.
+    fn check_external_id(&self, item: &WorkItem) -> Result<()> {
+        let Some(external_id) = &item.external_id else {
+            return Ok(());
+        };
+        if external_id.trim().is_empty() {
+            return Err(CoreError::Validation("external_id can't be blank".to_string()).into());
+        }
 
-        if !self.item_dir(slug).exists() {
-            return Err(FsError::ItemNotFound(uid.to_string()));
+        match self.find_by_external_id(external_id) {
+            Ok(other) if other.uid != item.uid => Err(CoreError::Validation(format!(
+                "external_id '{external_id}' is already used by {}",
+                other.uid
+            ))
+            .into()),
+            Ok(_) | Err(FsError::ItemNotFound(_)) => Ok(()),
+            Err(e) => Err(e),
         }
+    }
 
-        let event = WorkEvent::comment(message);
-        self.append_event(slug, &event)?;
+    /// Check that moving `item` into its state keeps the state within its
+    /// WIP limit, counting every other item already there.
+    fn check_wip_limit(&self, item: &WorkItem) -> Result<()> {
+        if !self.enforce_wip_limits {
+            return Ok(());
+        }
+        let Some(limit) = self.config.workflow.wip_limit(&item.state) else {
+            return Ok(());
+        };
 
+        let count = self
+            .iter_items(None)?
+            .filter(|other| other.uid != item.uid && other.state.eq_ignore_ascii_case(&item.state))
+            .count();
+        if count >= limit {
+            return Err(FsError::WipLimitReached {
+                state: item.state.clone(),
+                limit,
+            });
+        }
         Ok(())
     }
 
-    // Private helpers
+    /// Permanently remove an item's directory, with its history, notes, and
+    /// artifacts, returning its UID.
+    ///
+    /// There is no undoing this, so only the exact UID or slug is accepted,
+    /// never an external ID or partial match.
+    ///
+    /// # Errors
+    /// Returns error if no item has exactly that UID, or IO fails.
+    pub fn delete_item(&self, uid: &str) -> Result<String> {
+        let slug = self
+            .exact_slug(uid)
+            .ok_or_else(|| FsError::ItemNotFound(uid.to_string()))?;
+        let item_dir = self.item_dir(slug);
 
-    fn read_meta(&self, slug: &str) -> Result<WorkItem> {
-        let meta_path = self.item_dir(slug).join(META_FILE);
-        let content = fs::read_to_string(&meta_path)?;
-        let item: WorkItem = serde_yaml::from_str(&content)?;
-        Ok(item)
-    }
+        if !item_dir.join(META_FILE).exists() {
+            return Err(FsError::ItemNotFound(uid.to_string()));
+        }
 
-    fn write_meta(&self, slug: &str, item: &WorkItem) -> Result<()> {
-        let meta_path = self.item_dir(slug).join(META_FILE);
-        let content = serde_yaml::to_string(item)?;
-        fs::write(&meta_path, content)?;
-        Ok(())
+        fs::remove_dir_all(&item_dir)?;
+        let uid = format!("fs:{slug}");
+        info!(uid = %uid, "Deleted work item");
+        Ok(uid)
     }
-}
 
-/// Filter criteria for listing work items.
-#[derive(Debug, Default)]
-pub struct ItemFilter {
-    /// Filter by state.
-    pub state: Option<String>,
-    /// Filter by assignee.
-    pub assignee: Option<String>,
-    /// Filter by label (item must have this label).
-    pub label: Option<String>,
-}
+    /// Move an item into the workspace `target`, returning it as it is there.
+    ///
+    /// The item's directory (meta, events, notes, and artifacts) is copied
+    /// into `target`'s `.worky` directory, compared file by file with the
+    /// original, and renamed into place before the original is removed. A
+    /// failure up to the rename leaves the source as it was, and one after
+    /// it leaves the item in both workspaces, so it is never lost.
+    ///
+    /// The slug is kept unless `target` has it already, in which case the
+    /// first free `-2`, `-3`, ... suffix is used whatever `target`'s
+    /// `slug.on_conflict` says. Both logs get a `TRANSFERRED` event naming
+    /// the other side; the source's outlives the item only in the audit log.
+    /// Items here that the moved one blocks keep its old UID.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, `target` is this workspace,
+    /// `target` has an item with the same external ID or no such state in its
+    /// workflow, or IO fails.
+    pub fn move_item_to_workspace(&self, uid: &str, target: &Self) -> Result<WorkItem> {
+        let mut item = self.get_item(uid)?;
+        let old_uid = item.uid.clone();
+        let slug = Self::slug_from_uid(&old_uid)?;
 
-impl ItemFilter {
-    /// Check if an item matches this filter.
-    #[must_use]
-    pub fn matches(&self, item: &WorkItem) -> bool {
-        if let Some(state) = &self.state {
-            if !item.state.eq_ignore_ascii_case(state) {
-                return false;
-            }
+        let source_root = fs::canonicalize(&self.root)?;
+        let target_root = fs::canonicalize(&target.root)?;
+        if source_root == target_root {
+            return Err(CoreError::Validation(format!(
+                "{old_uid} is already in {}",
+                target_root.display()
+            ))
+            .into());
         }
 
-        if let Some(assignee) = &self.assignee {
-            match &item.assignee {
-                Some(a) if a.eq_ignore_ascii_case(assignee) => {}
-                _ => return false,
-            }
+        let new_slug = target.free_slug(slug);
+        item.uid = format!("fs:{new_slug}");
+        target.check_external_id(&item)?;
+        let workflow = &target.config.workflow;
+        if !workflow.states.iter().any(|s| s.eq_ignore_ascii_case(&item.state)) {
+            return Err(CoreError::Validation(format!(
+                "{old_uid} is in {}, which isn't a state in {}",
+                item.state,
+                target_root.display()
+            ))
+            .into());
         }
 
-        if let Some(label) = &self.label {
-            if !item.has_label(label) {
-                return false;
+        let source_dir = self.item_dir(slug);
+        let staging = target.root.join(WORKY_DIR).join(format!("transfer-{new_slug}"));
+        let staged = copy_dir(&source_dir, &staging)
+            .and_then(|()| verify_copy(&source_dir, &staging))
+            .and_then(|()| {
+                fs::create_dir_all(target.items_dir())?;
+                Ok(fs::rename(&staging, target.item_dir(&new_slug))?)
+            });
+        if let Err(e) = staged {
+            if staging.exists()
+                && let Err(cleanup) = fs::remove_dir_all(&staging)
+            {
+                warn!(path = %staging.display(), error = %cleanup, "Failed to remove staged copy");
             }
+            return Err(e);
         }
 
-        true
+        target.write_meta(&new_slug, &item)?;
+        let from = source_root.display().to_string();
+        target.append_event(&new_slug, &WorkEvent::transferred_from(&from, &old_uid))?;
+
+        let to = target_root.display().to_string();
+        self.append_event(slug, &WorkEvent::transferred_to(&to, &item.uid))?;
+        fs::remove_dir_all(&source_dir)?;
+
+        info!(uid = %old_uid, to = %to, new_uid = %item.uid, "Transferred work item");
+        Ok(item)
     }
-}
+
+    /// Contents of an item's `notes.md`, split from any front matter, if it
+    /// has one.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or the file cannot be read.
+    pub fn read_notes(&self, uid: &str) -> Result<Option<Notes>> {
+        let slug = &self.resolve_slug(uid)?;
+        let item_dir = self.item_dir(slug);
+
+        if !item_dir.exists() {
+            return Err(FsError::ItemNotFound(uid.to_string()));
+        }
+
+        let notes_path = item_dir.join(NOTES_FILE);
+        notes_path
+            .exists()
+            .then(|| read_item_text(&notes_path, uid).map(Notes::parse))
+            .transpose()
+    }
+
+    /// Merge the front matter of an item's notes into its custom fields,
+    /// each key becoming `fields.<key>`. Writes nothing if the fields
+    /// already match.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if the notes have no front matter or
+    /// it is malformed, or error if the update fails.
+    pub fn sync_notes(&self, uid: &str) -> Result<WorkItem> {
+        let slug = &self.resolve_slug(uid)?;
+        let plan = self.plan_sync_notes(uid)?;
+        if plan.events.is_empty() {
+            return self.read_meta(slug);
+        }
+        self.commit_plan(slug, &plan)?;
+
+        info!(uid = %uid, "Synced fields from notes");
+
+        Ok(plan.item)
+    }
+
+    /// Compute what [`Self::sync_notes`] would do without writing anything.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::sync_notes`].
+    pub fn plan_sync_notes(&self, uid: &str) -> Result<UpdatePlan> {
+        let notes = self.read_notes(uid)?;
+        let Some(front_matter) = notes.as_ref().map(Notes::front_matter).transpose()?.flatten()
+        else {
+            return Err(CoreError::Validation(format!("{uid} has no notes front matter")).into());
+        };
+
+        let operations = front_matter
+            .iter()
+            .map(|(key, value)| {
+                if key.is_empty() || key.contains('.') {
+                    return Err(CoreError::Validation(format!(
+                        "front matter key '{key}' can't be used as a field name"
+                    )));
+                }
+                Ok(SetOperation::new(format!("fields.{key}"), value.clone()))
+            })
+            .collect::<worky_core::Result<Vec<_>>>()?;
+
+        self.plan_update(uid, &operations)
+    }
+
+    /// List all work items, optionally filtered, most recently updated first.
+    ///
+    /// Collects [`Self::iter_items`]; prefer that when order doesn't matter.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn list_items(&self, filter: Option<&ItemFilter>) -> Result<Vec<WorkItem>> {
+        let mut items: Vec<WorkItem> = self.iter_items(filter)?.collect();
+
+        // Sort by updated_at descending, then by manual rank within each state
+        items.sort_by_key(|item| std::cmp::Reverse(item.updated_at));
+        order_by_rank(&mut items);
+
+        Ok(items)
+    }
+
+    /// Work items matching `filter`, read one at a time in directory order.
+    ///
+    /// Directories are read as the iterator advances and nothing is sorted,
+    /// so callers can start on the first item before the last one is read.
+    /// Unreadable entries are skipped with a warning, and items matched by
+    /// `.workyignore` are skipped silently.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be opened or
+    /// `.workyignore` is invalid.
+    pub fn iter_items<'a>(
+        &'a self,
+        filter: Option<&'a ItemFilter>,
+    ) -> Result<impl Iterator<Item = WorkItem> + 'a> {
+        let ignore = self.load_ignore()?;
+        // The index only helps when there is something to rule items out by
+        let index = filter
+            .filter(|f| !f.is_empty())
+            .and_then(|_| ItemIndex::load(&self.index_path()));
+
+        Ok(self.slugs()?.filter_map(move |slug| {
+            if ignore.is_ignored(&slug) {
+                return None;
+            }
+
+            if let (Some(index), Some(filter)) = (&index, filter)
+                && index
+                    .fresh(&slug, &self.item_dir(&slug).join(META_FILE))
+                    .is_some_and(|entry| filter.rules_out(entry))
+            {
+                return None;
+            }
+
+            match self.read_meta(&slug) {
+                Ok(item) => filter.is_none_or(|f| f.matches(&item)).then_some(item),
+                Err(e) => {
+                    warn!(slug = %slug, error = %e, "Skipping unreadable item; run `worky doctor`");
+                    None
+                }
+            }
+        }))
+    }
+
+    /// Slugs of the item directories that have a `meta.yml`, in directory
+    /// order.
+    fn slugs(&self) -> Result<impl Iterator<Item = String>> {
+        let items_dir = self.items_dir();
+        let entries = if items_dir.exists() {
+            Some(fs::read_dir(&items_dir)?)
+        } else {
+            None
+        };
+
+        Ok(entries.into_iter().flatten().filter_map(|entry| match entry {
+            Ok(entry) if entry.path().join(META_FILE).exists() => {
+                Some(entry.file_name().to_string_lossy().to_string())
+            }
+            Ok(_) => None,
+            Err(e) => {
+                warn!(error = %e, "Skipping unreadable directory entry");
+                None
+            }
+        }))
+    }
+
+    /// Get the path of `.worky/index.json`.
+    #[must_use]
+    pub fn index_path(&self) -> PathBuf {
+        self.root.join(WORKY_DIR).join(INDEX_FILE)
+    }
+
+    /// Rebuild `.worky/index.json` from every item's `meta.yml`, returning
+    /// how many items were indexed. Unreadable items are left out.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read or the index
+    /// cannot be written.
+    pub fn reindex(&self) -> Result<usize> {
+        let mut index = ItemIndex::default();
+
+        for slug in self.slugs()? {
+            // Stat before reading, so a write in between leaves the entry stale
+            let stamp = Stamp::of(&self.item_dir(&slug).join(META_FILE))?;
+            match self.read_meta(&slug) {
+                Ok(item) => index.insert(slug, IndexEntry::new(&item, stamp)),
+                Err(e) => {
+                    warn!(
+                        slug = %slug,
+                        error = %e,
+                        "Not indexing unreadable item; run `worky doctor`"
+                    );
+                }
+            }
+        }
+
+        index.save(&self.index_path())?;
+        info!(items = index.len(), "Rebuilt index");
+        Ok(index.len())
+    }
+
+    /// Patterns from `.workyignore` at the workspace root, if there is one.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read or has an invalid pattern.
+    pub fn load_ignore(&self) -> Result<WorkyIgnore> {
+        let path = self.root.join(IGNORE_FILE);
+        if !path.exists() {
+            return Ok(WorkyIgnore::default());
+        }
+
+        WorkyIgnore::parse(&self.items_dir(), &fs::read_to_string(path)?)
+    }
+
+    /// List open items whose blockers are all in a terminal state.
+    ///
+    /// Blockers that no longer exist are treated as resolved.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn ready_items(&self, filter: Option<&ItemFilter>) -> Result<Vec<WorkItem>> {
+        let all = self.list_items(None)?;
+        let states: HashMap<&str, &str> = all
+            .iter()
+            .map(|item| (item.uid.as_str(), item.state.as_str()))
+            .collect();
+
+        let ready = all
+            .iter()
+            .filter(|item| !self.is_terminal(&item.state))
+            .filter(|item| {
+                item.blocked_by
+                    .iter()
+                    .all(|b| states.get(b.as_str()).is_none_or(|s| self.is_terminal(s)))
+            })
+            .filter(|item| filter.is_none_or(|f| f.matches(item)))
+            .cloned()
+            .collect();
+
+        Ok(ready)
+    }
+
+    /// Open items assigned to `assignee`, most pressing first.
+    ///
+    /// Items are ordered by priority (highest first), then by `fields.due`
+    /// (an ISO date, earliest first; items without one come after), then by
+    /// most recent update. A non-empty `states` narrows the result further.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn workload(&self, assignee: &str, states: &[String]) -> Result<Vec<WorkItem>> {
+        let mut filter = ItemFilter::new().with(Predicate::Assignee(assignee.to_string()));
+        if !states.is_empty() {
+            filter = filter.with(Predicate::StateIn(states.to_vec()));
+        }
+
+        let mut items = self.list_items(Some(&filter))?;
+        items.retain(|item| !self.is_terminal(&item.state));
+
+        // Items arrive newest first; a stable sort keeps that as the last tiebreaker
+        items.sort_by(|a, b| {
+            let due = |item: &WorkItem| {
+                let due = item.fields.get("due").and_then(Value::as_str);
+                (due.is_none(), due.map(str::to_string))
+            };
+            b.priority.cmp(&a.priority).then_with(|| due(a).cmp(&due(b)))
+        });
+
+        Ok(items)
+    }
+
+    /// The item `assignee` should work on next: the highest-priority ready
+    /// item in one of `states` that is assigned to them or to no one, oldest
+    /// first among equals. `None` if there is nothing to pick.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn next_item(&self, assignee: &str, states: &[String]) -> Result<Option<WorkItem>> {
+        let filter = ItemFilter::new().with(Predicate::StateIn(states.to_vec()));
+        let mut items = self.ready_items(Some(&filter))?;
+        items.retain(|item| {
+            item.assignee
+                .as_ref()
+                .is_none_or(|a| a.eq_ignore_ascii_case(assignee))
+        });
+
+        // `None` sorts below every priority, so unprioritized items come last
+        items.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Items in a terminal state last updated before `cutoff`, oldest first;
+    /// what `worky purge` removes. With `states`, only items in one of them.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if one of `states` isn't terminal, or
+    /// error if the items directory cannot be read.
+    pub fn purge_candidates(
+        &self,
+        cutoff: DateTime<Utc>,
+        states: &[String],
+    ) -> Result<Vec<WorkItem>> {
+        if let Some(state) = states.iter().find(|s| !self.is_terminal(s)) {
+            return Err(CoreError::Validation(format!(
+                "only items in a terminal state can be purged, not '{state}'"
+            ))
+            .into());
+        }
+
+        let mut items: Vec<WorkItem> = self
+            .iter_items(None)?
+            .filter(|item| self.is_terminal(&item.state) && item.updated_at < cutoff)
+            .filter(|item| {
+                states.is_empty() || states.iter().any(|s| item.state.eq_ignore_ascii_case(s))
+            })
+            .collect();
+        items.sort_by_key(|item| item.updated_at);
+        Ok(items)
+    }
+
+    /// Summarize item counts by state, assignee, and label.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn stats(&self) -> Result<WorkspaceStats> {
+        let items = self.list_items(None)?;
+        Ok(WorkspaceStats::from_items(&items, Utc::now(), |s| {
+            self.is_terminal(s)
+        }))
+    }
+
+    /// Every label in use with the number of items carrying it, most used
+    /// first. Labels that differ only in case are counted together under
+    /// the first spelling seen.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn all_labels(&self) -> Result<Vec<(String, usize)>> {
+        let items = self.iter_items(None)?;
+        Ok(tally(items.flat_map(|item| item.labels)))
+    }
+
+    /// Every assignee with the number of items assigned to them, most
+    /// first. Names are grouped ignoring case, like labels.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read.
+    pub fn all_assignees(&self) -> Result<Vec<(String, usize)>> {
+        let items = self.iter_items(None)?;
+        Ok(tally(items.filter_map(|item| item.assignee)))
+    }
+
+    /// Record that `uid` is blocked by `blocker_uid`.
+    ///
+    /// Linking an existing blocker again is a no-op.
+    ///
+    /// # Errors
+    /// Returns error if either item doesn't exist or the link would create a cycle.
+    pub fn add_blocker(&self, uid: &str, blocker_uid: &str) -> Result<WorkItem> {
+        // Either may be given by external ID; links always name UIDs
+        let item = self.get_item(uid)?;
+        let blocker = self.get_item(blocker_uid)?;
+        let (uid, blocker_uid) = (item.uid.as_str(), blocker.uid.as_str());
+
+        if item.blocked_by.iter().any(|b| b == blocker_uid) {
+            return Ok(item);
+        }
+
+        if self.depends_on(blocker_uid, uid)? {
+            return Err(FsError::DependencyCycle(format!(
+                "{blocker_uid} already depends on {uid}"
+            )));
+        }
+
+        let mut blocked_by = item.blocked_by;
+        blocked_by.push(blocker_uid.to_string());
+
+        self.update_item(uid, &[SetOperation::new("blocked_by", blocked_by)])
+    }
+
+    /// Append a step to an item's checklist.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, `text` is blank, or IO fails.
+    pub fn add_checklist_item(&self, uid: &str, text: &str) -> Result<WorkItem> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(CoreError::Validation("checklist step cannot be empty".into()).into());
+        }
+
+        self.edit_checklist(uid, |checklist| {
+            checklist.push(ChecklistItem::new(text));
+            Ok(WorkEvent::checklist_updated("add", checklist.len(), text))
+        })
+    }
+
+    /// Check off (or, with `done` false, uncheck) step `number`, counting
+    /// from 1.
+    ///
+    /// # Errors
+    /// Returns error if the item or step doesn't exist, or IO fails.
+    pub fn set_checklist_done(&self, uid: &str, number: usize, done: bool) -> Result<WorkItem> {
+        self.edit_checklist(uid, |checklist| {
+            let index = checklist_step(checklist, number)?;
+            let step = &mut checklist[index];
+            step.done = done;
+            let action = if done { "done" } else { "undone" };
+            Ok(WorkEvent::checklist_updated(action, number, &step.text))
+        })
+    }
+
+    /// Remove step `number`, counting from 1. Later steps move up one.
+    ///
+    /// # Errors
+    /// Returns error if the item or step doesn't exist, or IO fails.
+    pub fn remove_checklist_item(&self, uid: &str, number: usize) -> Result<WorkItem> {
+        self.edit_checklist(uid, |checklist| {
+            let removed = checklist.remove(checklist_step(checklist, number)?);
+            Ok(WorkEvent::checklist_updated("remove", number, removed.text))
+        })
+    }
+
+    /// Apply `edit` to an item's checklist, save it, and log the event it returns.
+    fn edit_checklist(
+        &self,
+        uid: &str,
+        edit: impl FnOnce(&mut Vec<ChecklistItem>) -> Result<WorkEvent>,
+    ) -> Result<WorkItem> {
+        let slug = &self.resolve_slug(uid)?;
+        let mut item = self.get_item(uid)?;
+
+        let event = edit(&mut item.checklist)?;
+        item.touch();
+        self.write_meta(slug, &item)?;
+        self.append_event(slug, &event)?;
+
+        info!(uid = %uid, "Updated checklist");
+        Ok(item)
+    }
+
+    /// Check whether `uid` transitively depends on `target` via `blocked_by` links.
+    fn depends_on(&self, uid: &str, target: &str) -> Result<bool> {
+        let mut stack = vec![uid.to_string()];
+        let mut seen = HashSet::new();
+
+        while let Some(current) = stack.pop() {
+            if current == target {
+                return Ok(true);
+            }
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+
+            match self.get_linked_item(&current) {
+                Ok(item) => stack.extend(item.blocked_by),
+                Err(FsError::ItemNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Move an item directly before or after another item in the same state.
+    ///
+    /// The new rank is the midpoint between the new neighbours. If a neighbour
+    /// has no rank yet, or the gap is too small, the whole column is renumbered.
+    /// Only the moved item records the change; the others keep their place
+    /// in the order, so their new ranks are saved without an event or a new
+    /// `updated_at`.
+    ///
+    /// # Errors
+    /// Returns error if either item doesn't exist or they are in different states.
+    pub fn set_rank(&self, uid: &str, placement: &Placement) -> Result<WorkItem> {
+        let (target_uid, after) = match placement {
+            Placement::Before(target) => (target.as_str(), false),
+            Placement::After(target) => (target.as_str(), true),
+        };
+
+        if target_uid == uid {
+            return Err(
+                CoreError::Validation(format!("cannot move {uid} relative to itself")).into(),
+            );
+        }
+
+        let item = self.get_item(uid)?;
+        let target = self.get_item(target_uid)?;
+        if !item.state.eq_ignore_ascii_case(&target.state) {
+            return Err(CoreError::Validation(format!(
+                "{uid} is in {} but {target_uid} is in {}; items can only be reordered within a state",
+                item.state, target.state
+            ))
+            .into());
+        }
+
+        let filter = ItemFilter::new().state(target.state);
+        let mut column: Vec<WorkItem> = self
+            .list_items(Some(&filter))?
+            .into_iter()
+            .filter(|i| i.uid != uid)
+            .collect();
+
+        let target_idx = column
+            .iter()
+            .position(|i| i.uid == target_uid)
+            .ok_or_else(|| FsError::ItemNotFound(target_uid.to_string()))?;
+        let insert_at = if after { target_idx + 1 } else { target_idx };
+
+        let prev = insert_at.checked_sub(1).map(|i| column[i].rank);
+        let next = column.get(insert_at).map(|i| i.rank);
+        let rank = match (prev, next) {
+            (Some(Some(a)), Some(Some(b))) => {
+                Some(f64::midpoint(a, b)).filter(|r| a < *r && *r < b)
+            }
+            (Some(Some(a)), None) => Some(a + RANK_STEP),
+            (None, Some(Some(b))) => Some(b - RANK_STEP),
+            _ => None,
+        };
+
+        if let Some(rank) = rank {
+            return self.update_item(uid, &[SetOperation::new("rank", rank)]);
+        }
+
+        // Renumber the column in its new order
+        column.insert(insert_at, item);
+        let mut moved_rank = RANK_STEP;
+        let mut rank = 0.0;
+        for mut entry in column {
+            rank += RANK_STEP;
+            if entry.uid == uid {
+                moved_rank = rank;
+            } else if !entry.rank.is_some_and(|r| (r - rank).abs() < f64::EPSILON) {
+                entry.rank = Some(rank);
+                self.write_meta(Self::slug_from_uid(&entry.uid)?, &entry)?;
+            }
+        }
+
+        self.update_item(uid, &[SetOperation::new("rank", moved_rank)])
+    }
+
+    /// Run `f`, undoing its changes to the items in `uids` if it fails.
+    ///
+    /// Each item's `meta.yml` is copied and its event log's length noted
+    /// first. If `f` returns an error, the copies are written back and the
+    /// logs (and the audit log) cut back to those lengths, so either all of
+    /// `f`'s changes to these items stand or none do. Changes to other items
+    /// aren't undone, and other processes writing the same items at the same
+    /// time can still interleave with `f`.
+    ///
+    /// # Errors
+    /// Returns error if an item doesn't exist or can't be read, or `f`'s
+    /// error after rolling back.
+    pub fn transaction<T>(
+        &self,
+        uids: &[impl AsRef<str>],
+        f: impl FnOnce(&Self) -> Result<T>,
+    ) -> Result<T> {
+        let mut snapshots = Vec::new();
+        for uid in uids {
+            let item = self.get_item(uid.as_ref())?;
+            let dir = self.item_dir(Self::slug_from_uid(&item.uid)?);
+            snapshots.push(Snapshot::take(&item.uid, &dir)?);
+        }
+
+        let ws = Self {
+            in_transaction: true,
+            ..self.clone()
+        };
+        let result = f(&ws);
+        if let Err(e) = &result {
+            debug!(error = %e, "Rolling back transaction");
+            let restored = snapshots.iter().try_for_each(|s| self.roll_back(s));
+            if let Err(restore_error) = restored {
+                warn!(
+                    error = %restore_error,
+                    "Failed to roll back transaction; run `worky doctor`"
+                );
+            }
+        }
+        result
+    }
+
+    /// Restore `snapshot`. The audit log is shared and append-only, so the
+    /// events undone are recorded there rather than cut from it.
+    fn roll_back(&self, snapshot: &Snapshot) -> Result<()> {
+        let undone = snapshot.new_event_ids()?;
+        snapshot.restore()?;
+
+        if self.config.audit.enabled && !undone.is_empty() {
+            let event = WorkEvent::rolled_back(&undone).with_actor(&self.actor);
+            audit_log::append(&self.audit_path(), &snapshot.uid, &[event])?;
+        }
+        Ok(())
+    }
+
+    /// Apply the same set operations to several items, all or none.
+    ///
+    /// # Errors
+    /// Returns error if any item doesn't exist or its update fails, in which
+    /// case no item is changed.
+    pub fn update_items(
+        &self,
+        uids: &[impl AsRef<str>],
+        operations: &[SetOperation],
+    ) -> Result<Vec<WorkItem>> {
+        self.transaction(uids, |ws| {
+            uids.iter()
+                .map(|uid| ws.update_item(uid.as_ref(), operations))
+                .collect()
+        })
+    }
+
+    /// Update a work item with set operations.
+    ///
+    /// # Errors
+    /// Returns error if item doesn't exist, a `fields.*` operation violates the
+    /// configured field schema, or the update fails.
+    pub fn update_item(&self, uid: &str, operations: &[SetOperation]) -> Result<WorkItem> {
+        self.update_item_if(uid, operations, None)
+    }
+
+    /// Update a work item with set operations, provided it is still at
+    /// `expected_version` (from [`Self::item_version`]), if given.
+    ///
+    /// This guards against overwriting changes made since the caller last
+    /// read the item, not against a write landing between the check and
+    /// this one.
+    ///
+    /// # Errors
+    /// Returns [`FsError::VersionMismatch`] if the item has changed, or the
+    /// errors of [`Self::update_item`].
+    pub fn update_item_if(
+        &self,
+        uid: &str,
+        operations: &[SetOperation],
+        expected_version: Option<&str>,
+    ) -> Result<WorkItem> {
+        let slug = &self.resolve_slug(uid)?;
+        if let Some(expected) = expected_version {
+            let version = self.version_of(slug)?;
+            if version != expected {
+                return Err(FsError::VersionMismatch {
+                    uid: format!("fs:{slug}"),
+                    expected: expected.to_string(),
+                    version,
+                    current: Box::new(self.read_meta(slug)?),
+                });
+            }
+        }
+
+        let plan = self.plan_update(uid, operations)?;
+        self.commit_plan(slug, &plan)?;
+
+        info!(uid = %uid, "Updated work item");
+
+        Ok(plan.item)
+    }
+
+    /// Compute what [`Self::update_item`] would do without writing anything.
+    ///
+    /// # Errors
+    /// Returns error if item doesn't exist, a `fields.*` operation violates the
+    /// configured field schema, or an operation fails.
+    pub fn plan_update(&self, uid: &str, operations: &[SetOperation]) -> Result<UpdatePlan> {
+        let slug = &self.resolve_slug(uid)?;
+        self.plan_operations(self.read_meta(slug)?, operations)
+    }
+
+    /// What applying `operations` to `item` gives, checked like an update.
+    fn plan_operations(
+        &self,
+        mut item: WorkItem,
+        operations: &[SetOperation],
+    ) -> Result<UpdatePlan> {
+        let old_item = item.clone();
+
+        let operations: Vec<SetOperation> = operations
+            .iter()
+            .cloned()
+            .map(SetOperation::normalized)
+            .collect();
+
+        // Convert to JSON for patching
+        let mut json_value = serde_json::to_value(&item)?;
+
+        for op in &operations {
+            apply_set_operation(&mut json_value, op)?;
+        }
+
+        // Deserialize back
+        item = serde_json::from_value(json_value)?;
+        item.touch();
+        self.config.validate_fields(&old_item.fields, &item.fields)?;
+        if item.external_id != old_item.external_id {
+            self.check_external_id(&item)?;
+        }
+        if !item.state.eq_ignore_ascii_case(&old_item.state) {
+            self.check_wip_limit(&item)?;
+        }
+
+        let old_json = serde_json::to_value(&old_item)?;
+        let new_json = serde_json::to_value(&item)?;
+        let events = change_events(&old_json, &new_json, item.updated_at);
+
+        Ok(UpdatePlan { item, events })
+    }
+
+    /// Append a plan's events and write its item.
+    fn commit_plan(&self, slug: &str, plan: &UpdatePlan) -> Result<()> {
+        self.append_events(slug, &plan.events)?;
+        self.write_meta(slug, &plan.item)
+    }
+
+    /// Assign an item to `assignee`, or unassign it with `None` or a blank name.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or the update fails.
+    pub fn assign(&self, uid: &str, assignee: Option<&str>) -> Result<WorkItem> {
+        self.update_item(uid, &[SetOperation::assignee(assignee)])
+    }
+
+    /// Move a finished item straight back to an active state.
+    ///
+    /// Records a single state change. Returns `None` if the item is already active.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or `state` is itself terminal.
+    pub fn reopen_item(&self, uid: &str, state: &str) -> Result<Option<WorkItem>> {
+        if self.is_terminal(state) {
+            return Err(CoreError::Validation(format!(
+                "cannot reopen into terminal state '{state}'"
+            ))
+            .into());
+        }
+
+        let item = self.get_item(uid)?;
+        if !self.is_terminal(&item.state) {
+            return Ok(None);
+        }
+
+        self.update_item(uid, &[SetOperation::new("state", state)])
+            .map(Some)
+    }
+
+    /// Apply a JSON merge patch to a work item.
+    ///
+    /// # Errors
+    /// Returns error if item doesn't exist or patch fails.
+    pub fn patch_item(&self, uid: &str, patch: &Value) -> Result<WorkItem> {
+        let plan = self.plan_patch(uid, patch)?;
+        self.commit_patch(uid, plan)
+    }
+
+    /// Compute what [`Self::patch_item`] would do without writing anything.
+    ///
+    /// # Errors
+    /// Returns error if item doesn't exist or the result is not a valid item.
+    pub fn plan_patch(&self, uid: &str, patch: &Value) -> Result<UpdatePlan> {
+        let slug = &self.resolve_slug(uid)?;
+        let item = self.read_meta(slug)?;
+
+        let old_json = serde_json::to_value(&item)?;
+        let mut new_json = old_json.clone();
+        apply_merge_patch(&mut new_json, patch);
+
+        self.patch_plan(&old_json, new_json)
+    }
+
+    /// Apply a JSON Patch (RFC 6902) to a work item.
+    ///
+    /// Nothing is written unless every operation succeeds.
+    ///
+    /// # Errors
+    /// Returns `CoreError::InvalidPath` or `CoreError::Validation` if an
+    /// operation fails or the result is not a valid item, or error if the
+    /// item doesn't exist.
+    pub fn json_patch_item(&self, uid: &str, ops: &[PatchOp]) -> Result<WorkItem> {
+        let plan = self.plan_json_patch(uid, ops)?;
+        self.commit_patch(uid, plan)
+    }
+
+    /// Compute what [`Self::json_patch_item`] would do without writing anything.
+    ///
+    /// # Errors
+    /// Same as [`Self::json_patch_item`].
+    pub fn plan_json_patch(&self, uid: &str, ops: &[PatchOp]) -> Result<UpdatePlan> {
+        let slug = &self.resolve_slug(uid)?;
+        let item = self.read_meta(slug)?;
+
+        let old_json = serde_json::to_value(&item)?;
+        let mut new_json = old_json.clone();
+        apply_json_patch(&mut new_json, ops)?;
+
+        self.patch_plan(&old_json, new_json)
+    }
+
+    /// Replace a work item with `edited`, the whole item as JSON (e.g. a
+    /// `meta.yml` edited by hand), recording an event for each change.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if `edited` changes the UID or is not
+    /// a valid item, or error if the item doesn't exist.
+    pub fn replace_item(&self, uid: &str, edited: &Value) -> Result<WorkItem> {
+        let plan = self.plan_replace(uid, edited)?;
+        self.commit_patch(uid, plan)
+    }
+
+    /// Compute what [`Self::replace_item`] would do without writing anything.
+    ///
+    /// # Errors
+    /// Same as [`Self::replace_item`].
+    pub fn plan_replace(&self, uid: &str, edited: &Value) -> Result<UpdatePlan> {
+        let item = self.get_item(uid)?;
+
+        if edited.get("uid").and_then(Value::as_str) != Some(item.uid.as_str()) {
+            return Err(
+                CoreError::Validation(format!("the uid must stay '{}'", item.uid)).into(),
+            );
+        }
+
+        self.patch_plan(&serde_json::to_value(&item)?, edited.clone())
+    }
+
+    /// Build the item and per-field events for a patched item.
+    fn patch_plan(&self, old_json: &Value, mut new_json: Value) -> Result<UpdatePlan> {
+        // Ensure updated_at is refreshed
+        new_json["updated_at"] = serde_json::to_value(Utc::now())?;
+
+        let item: WorkItem = serde_json::from_value(new_json.clone()).map_err(|e| {
+            CoreError::Validation(format!("patch produces an invalid item: {e}"))
+        })?;
+        let old_fields: BTreeMap<String, Value> = old_json
+            .get("fields")
+            .map(|fields| serde_json::from_value(fields.clone()))
+            .transpose()?
+            .unwrap_or_default();
+        self.config.validate_fields(&old_fields, &item.fields)?;
+        if new_json.get("external_id") != old_json.get("external_id") {
+            self.check_external_id(&item)?;
+        }
+        let old_state = old_json.get("state").and_then(Value::as_str).unwrap_or_default();
+        if !item.state.eq_ignore_ascii_case(old_state) {
+            self.check_wip_limit(&item)?;
+        }
+
+        let events = change_events(old_json, &new_json, item.updated_at);
+
+        Ok(UpdatePlan { item, events })
+    }
+
+    /// Write a patched item and log an event for each changed field.
+    fn commit_patch(&self, uid: &str, plan: UpdatePlan) -> Result<WorkItem> {
+        let slug = &self.resolve_slug(uid)?;
+        self.commit_plan(slug, &plan)?;
+
+        info!(uid = %uid, "Patched work item");
+
+        Ok(plan.item)
+    }
+
+    /// Reverse the most recent change to an item's state, assignee, labels,
+    /// or fields. The events of one update share a timestamp and actor, so
+    /// `worky set a=1 b=2` is undone as a whole.
+    ///
+    /// History is never rewritten: the inverse change is applied as a new
+    /// patch, logged with the same typed events as an update, followed by an
+    /// `AI_ACTION` "undo" event naming the reversed events.
+    /// Because that marker is now the latest event, undo does not chain.
+    ///
+    /// # Errors
+    /// Returns `FsError::NotReversible` if the latest event is not a state,
+    /// assignee, label, or field change, or error if the item doesn't exist.
+    pub fn undo_last(&self, uid: &str) -> Result<WorkItem> {
+        let slug = &self.resolve_slug(uid)?;
+        let current = self.get_item(uid)?;
+
+        // An `AI_ACTION` only says who made the change before it, so it isn't
+        // the change; an undo marker is, and stops undo from chaining
+        let events: Vec<WorkEvent> = self
+            .read_events(uid, None, None, None)?
+            .into_iter()
+            .filter(|e| {
+                e.event_type != EventType::AiAction
+                    || matches!(&e.payload, EventPayload::AiAction(p) if p.action == "undo")
+            })
+            .collect();
+        let Some(last) = events.last() else {
+            return Err(FsError::NotReversible(format!("{uid} has no history")));
+        };
+        let change: Vec<&WorkEvent> = events
+            .iter()
+            .rev()
+            .take_while(|e| e.timestamp == last.timestamp && e.actor == last.actor)
+            .collect();
+
+        // Newest first, so a path changed twice ends at its oldest value
+        let mut inverse = serde_json::json!({});
+        let mut labels = current.labels;
+        for event in &change {
+            let (path, previous) = match (event.event_type, &event.payload) {
+                (EventType::StateChanged, EventPayload::StateChange(p)) => {
+                    ("state".to_string(), Value::String(p.from.clone()))
+                }
+                (EventType::Assigned, EventPayload::AssigneeChange(p)) => (
+                    "assignee".to_string(),
+                    p.from.clone().map_or(Value::Null, Value::String),
+                ),
+                (EventType::FieldChanged, EventPayload::FieldChange(p)) => {
+                    (p.path.clone(), p.old_value.clone().unwrap_or(Value::Null))
+                }
+                (EventType::LabelAdded, EventPayload::Label(p)) => {
+                    if let Some(pos) = labels.iter().position(|l| *l == p.label) {
+                        labels.remove(pos);
+                    }
+                    ("labels".to_string(), serde_json::to_value(&labels)?)
+                }
+                (EventType::LabelRemoved, EventPayload::Label(p)) => {
+                    labels.push(p.label.clone());
+                    ("labels".to_string(), serde_json::to_value(&labels)?)
+                }
+                (event_type, _) => {
+                    return Err(FsError::NotReversible(format!(
+                        "latest event on {uid} is {event_type}"
+                    )));
+                }
+            };
+
+            // A merge patch with null removes the key, restoring values that were unset
+            let nested = path
+                .rsplit('.')
+                .fold(previous, |value, key| serde_json::json!({ key: value }));
+            merge_values(&mut inverse, nested);
+        }
+        let item = self.patch_item(uid, &inverse)?;
+
+        let ids: Vec<&str> = change.iter().rev().map(|e| e.id.as_str()).collect();
+        let marker = WorkEvent::ai_action("worky", "undo")
+            .with_details(serde_json::json!({ "events": ids }));
+        self.append_event(slug, &marker)?;
+
+        info!(uid = %uid, events = ids.len(), "Undid last change");
+
+        Ok(item)
+    }
+
+    /// Who last changed each of an item's fields, and when, keyed by path.
+    ///
+    /// See [`FieldProvenance::from_events`].
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or its events cannot be read.
+    pub fn field_provenance(&self, uid: &str) -> Result<BTreeMap<String, FieldProvenance>> {
+        let item = self.get_item(uid)?;
+        let events = self.read_events(uid, None, None, None)?;
+        Ok(FieldProvenance::from_events(&item, &events))
+    }
+
+    /// Append an event to an item's event log.
+    ///
+    /// # Errors
+    /// Returns error if the event cannot be serialized or written.
+    pub fn append_event(&self, slug: &str, event: &WorkEvent) -> Result<()> {
+        self.append_events(slug, std::slice::from_ref(event))
+    }
+
+    /// Append events to an item's event log in a single write, synced to
+    /// disk first when `durability` is `synced`. Events without an actor
+    /// are attributed to [`Self::actor`].
+    ///
+    /// # Errors
+    /// Returns error if an event cannot be serialized or the log written.
+    pub fn append_events(&self, slug: &str, events: &[WorkEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let events: Vec<WorkEvent> = events
+            .iter()
+            .map(|event| match event.actor {
+                Some(_) => event.clone(),
+                None => event.clone().with_actor(&self.actor),
+            })
+            .collect();
+
+        let mut lines = String::new();
+        for event in &events {
+            lines.push_str(&serde_json::to_string(event)?);
+            lines.push('\n');
+        }
+
+        let events_path = self.item_dir(slug).join(EVENTS_FILE);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&events_path)?;
+        file.write_all(lines.as_bytes())?;
+        if self.config.durability == Durability::Synced {
+            file.sync_all()?;
+        }
+        let log_len = file.metadata()?.len();
+
+        debug!(slug = %slug, count = events.len(), "Appended events");
+
+        if self.config.audit.enabled
+            && let Err(e) = audit_log::append(&self.audit_path(), &format!("fs:{slug}"), &events)
+        {
+            warn!(slug = %slug, error = %e, "Failed to write audit log");
+        }
+
+        if let Some(threshold) = self.config.events.compact_threshold
+            && log_len > threshold as u64 * MIN_EVENT_LEN
+        {
+            self.check_log_size(slug, &events_path, threshold, events.len());
+        }
+
+        Ok(())
+    }
+
+    /// Suggest compacting a log with more than `threshold` events, or with
+    /// `events.auto_compact` compact it. Failures only warn, since the
+    /// events were already written.
+    ///
+    /// This happens when the `appended` events take the log past a multiple
+    /// of `threshold`, not on every write after that, so a log compaction
+    /// can't shrink below it is handled again only at the next multiple.
+    fn check_log_size(&self, slug: &str, events_path: &Path, threshold: usize, appended: usize) {
+        let count = match count_lines(events_path) {
+            Ok(count) => count,
+            Err(e) => {
+                warn!(slug = %slug, error = %e, "Failed to read event log");
+                return;
+            }
+        };
+        let before = count.saturating_sub(appended);
+        let crossed = |n: usize| n.saturating_sub(1) / threshold.max(1);
+        if count <= threshold || crossed(before) == crossed(count) {
+            return;
+        }
+
+        let uid = format!("fs:{slug}");
+        if !self.config.events.auto_compact {
+            warn!(
+                uid = %uid,
+                events = count,
+                "Event log is over events.compact_threshold; run `worky compact {uid}`"
+            );
+        } else if !self.in_transaction
+            && let Err(e) = self.compact_slug(slug)
+        {
+            warn!(uid = %uid, error = %e, "Failed to compact event log");
+        }
+    }
+
+    /// Get the path of an item's `events.ndjson`.
+    ///
+    /// # Errors
+    /// Returns error if the UID is invalid.
+    pub fn events_path(&self, uid: &str) -> Result<PathBuf> {
+        Ok(self.item_dir(&self.resolve_slug(uid)?).join(EVENTS_FILE))
+    }
+
+    /// Get the path of `.worky/audit.ndjson`.
+    #[must_use]
+    pub fn audit_path(&self) -> PathBuf {
+        self.root.join(WORKY_DIR).join(AUDIT_FILE)
+    }
+
+    /// Read the workspace audit log, oldest first, keeping entries at or
+    /// after `since`. Empty unless `audit.enabled` is or was set.
+    ///
+    /// # Errors
+    /// Returns error if the audit log exists but cannot be read.
+    pub fn read_audit(&self, since: Option<DateTime<Utc>>) -> Result<Vec<AuditEntry>> {
+        audit_log::read(&self.audit_path(), since)
+    }
+
+    /// Read events for an item, optionally filtered by time and event type.
+    ///
+    /// The time window includes `since` and excludes `until`, so consecutive
+    /// windows don't overlap. Only events that pass every filter have their
+    /// payloads deserialized.
+    ///
+    /// Lines that don't parse (e.g. one cut short by a crash mid-write) are
+    /// skipped with a warning, so the rest of the history stays readable;
+    /// `worky doctor --fix` moves them aside.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if `since` is after `until`, or error if
+    /// the UID is invalid or the event log cannot be read.
+    pub fn read_events(
+        &self,
+        uid: &str,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        types: Option<&[EventType]>,
+    ) -> Result<Vec<WorkEvent>> {
+        if let (Some(since), Some(until)) = (since, until)
+            && since > until
+        {
+            return Err(CoreError::Validation(format!(
+                "since ({}) must not be after until ({})",
+                since.to_rfc3339(),
+                until.to_rfc3339()
+            ))
+            .into());
+        }
+
+        let slug = &self.resolve_slug(uid)?;
+        let events_path = self.item_dir(slug).join(EVENTS_FILE);
+
+        if !events_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&events_path)?;
+        let reader = BufReader::new(file);
+        let mut events = Vec::new();
+        let mut skipped = 0;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let header: EventHeader = match serde_json::from_str(&line) {
+                Ok(header) => header,
+                Err(e) => {
+                    debug!(uid = %uid, line = i + 1, error = %e, "Skipping unreadable event");
+                    skipped += 1;
+                    continue;
+                }
+            };
+            if since.is_some_and(|s| header.timestamp < s)
+                || until.is_some_and(|u| header.timestamp >= u)
+                || types.is_some_and(|t| !t.contains(&header.event_type))
+            {
+                continue;
+            }
+
+            match serde_json::from_str(&line) {
+                Ok(event) => events.push(event),
+                Err(e) => {
+                    debug!(uid = %uid, line = i + 1, error = %e, "Skipping unreadable event");
+                    skipped += 1;
+                }
+            }
+        }
+
+        if skipped > 0 {
+            warn!(
+                uid = %uid,
+                skipped,
+                "Skipped unreadable event lines; run `worky doctor --fix` to move them aside"
+            );
+        }
+
+        Ok(events)
+    }
+
+    /// The most recent events across all items, newest first, paired with
+    /// the UID of the item each belongs to.
+    ///
+    /// Event logs are appended in time order, so only the last `limit` lines
+    /// of each are read. Unreadable lines are skipped; `worky doctor` reports
+    /// them.
+    ///
+    /// # Errors
+    /// Returns error if the items directory or an event log cannot be read.
+    pub fn recent_events(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<(String, WorkEvent)>> {
+        let mut feed = Vec::new();
+        if limit == 0 {
+            return Ok(feed);
+        }
+
+        for slug in self.slugs()? {
+            let events_path = self.item_dir(&slug).join(EVENTS_FILE);
+            if !events_path.exists() {
+                continue;
+            }
+
+            let uid = format!("fs:{slug}");
+            for line in read_tail_lines(&events_path, limit)? {
+                match serde_json::from_str::<WorkEvent>(&line) {
+                    Ok(event) if since.is_none_or(|s| event.timestamp >= s) => {
+                        feed.push((uid.clone(), event));
+                    }
+                    Ok(_) => {}
+                    Err(e) => debug!(uid = %uid, error = %e, "Skipping unreadable event"),
+                }
+            }
+        }
+
+        feed.sort_by_key(|(_, event)| std::cmp::Reverse(event.timestamp));
+        feed.truncate(limit);
+
+        Ok(feed)
+    }
+
+    /// Write every item, with its events and notes, to a single bundle.
+    ///
+    /// Items are read and written one at a time in slug order, so only one
+    /// item's event log is held in memory.
+    ///
+    /// # Errors
+    /// Returns error if an item cannot be read or the writer fails.
+    pub fn export(&self, mut writer: impl Write, format: ExportFormat) -> Result<()> {
+        let mut slugs: Vec<String> = self.slugs()?.collect();
+        slugs.sort();
+
+        if format == ExportFormat::Json {
+            writer.write_all(b"[")?;
+        }
+
+        for (i, slug) in slugs.iter().enumerate() {
+            let item = self.read_meta(slug)?;
+            let events = self.read_events(&item.uid, None, None, None)?;
+            let notes = self.read_notes(&item.uid)?.map(|notes| notes.text);
+
+            let exported = ExportedItem {
+                item,
+                events,
+                notes,
+            };
+
+            match format {
+                ExportFormat::Json => {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    writer.write_all(b"\n")?;
+                    serde_json::to_writer(&mut writer, &exported)?;
+                }
+                ExportFormat::Ndjson => {
+                    serde_json::to_writer(&mut writer, &exported)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+        }
+
+        if format == ExportFormat::Json {
+            writer.write_all(b"\n]\n")?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// An iCalendar feed of every item with a due date, named after the
+    /// workspace; see [`ical`](crate::ical).
+    ///
+    /// # Errors
+    /// Returns error if the items cannot be read.
+    pub fn calendar(&self) -> Result<String> {
+        let mut items = self.list_items(None)?;
+        items.sort_by(|a, b| a.uid.cmp(&b.uid));
+        Ok(ical::calendar(self.config.workspace.name.as_deref(), &items))
+    }
+
+    /// Recreate items from a bundle produced by [`Workspace::export`].
+    ///
+    /// Accepts either layout: a JSON array or one item per line. Each item's
+    /// meta, notes, and event log are written as they were exported. Items with
+    /// an invalid UID are counted as failed and skipped.
+    ///
+    /// # Errors
+    /// Returns error if the bundle cannot be parsed, an item cannot be written,
+    /// or an item already exists in `ImportMode::ErrorOnConflict`.
+    pub fn import(&self, mut reader: impl BufRead, mode: ImportMode) -> Result<ImportSummary> {
+        let mut summary = ImportSummary::default();
+
+        // Skip leading whitespace to see which layout this is
+        let is_array = loop {
+            let buf = reader.fill_buf()?;
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(pos) => {
+                    let is_array = buf[pos] == b'[';
+                    reader.consume(pos);
+                    break is_array;
+                }
+                None if buf.is_empty() => return Ok(summary),
+                None => {
+                    let len = buf.len();
+                    reader.consume(len);
+                }
+            }
+        };
+
+        if is_array {
+            let mut content = String::new();
+            reader.read_to_string(&mut content)?;
+            let items: Vec<ExportedItem> = serde_json::from_str(&content)?;
+            for exported in items {
+                self.import_item(&exported, mode, &mut summary)?;
+            }
+        } else {
+            for exported in serde_json::Deserializer::from_reader(reader).into_iter() {
+                self.import_item(&exported?, mode, &mut summary)?;
+            }
+        }
+
+        info!(
+            created = summary.created,
+            skipped = summary.skipped,
+            failed = summary.failed,
+            "Imported bundle"
+        );
+
+        Ok(summary)
+    }
+
+    fn import_item(
+        &self,
+        exported: &ExportedItem,
+        mode: ImportMode,
+        summary: &mut ImportSummary,
+    ) -> Result<()> {
+        let uid = exported.item.uid.clone();
+        // Reject anything slugify would change, including path separators
+        let slug = Self::slug_from_uid(&uid).and_then(|slug| {
+            if !slug.is_empty() && slugify(slug) == slug {
+                Ok(slug)
+            } else {
+                Err(FsError::InvalidSlug(slug.to_string()))
+            }
+        });
+        let slug = match slug {
+            Ok(slug) => slug,
+            Err(e) => {
+                warn!(uid = %uid, error = %e, "Skipping item with invalid UID");
+                summary.failed += 1;
+                return Ok(());
+            }
+        };
+
+        let item_dir = self.item_dir(slug);
+        if item_dir.exists() {
+            match mode {
+                ImportMode::Skip => {
+                    summary.skipped += 1;
+                    return Ok(());
+                }
+                ImportMode::Overwrite => fs::remove_dir_all(&item_dir)?,
+                ImportMode::ErrorOnConflict => return Err(FsError::ItemExists(uid)),
+            }
+        }
+
+        fs::create_dir_all(item_dir.join(ARTIFACTS_DIR))?;
+        self.write_meta(slug, &exported.item)?;
+
+        if let Some(notes) = &exported.notes {
+            fs::write(item_dir.join(NOTES_FILE), notes)?;
+        }
+
+        let mut log = String::new();
+        for event in &exported.events {
+            log.push_str(&serde_json::to_string(event)?);
+            log.push('\n');
+        }
+        fs::write(item_dir.join(EVENTS_FILE), log)?;
+
+        summary.created += 1;
+        Ok(())
+    }
+
+    /// Check every item directory for missing or unreadable files.
+    ///
+    /// With `fix`, missing `events.ndjson`/`notes.md` are recreated and
+    /// unparseable event lines are moved to `events.ndjson.bad`. Orphaned
+    /// directories and invalid `meta.yml` files are only reported.
+    ///
+    /// # Errors
+    /// Returns error if the items directory cannot be read or a fix fails.
+    pub fn doctor(&self, fix: bool) -> Result<DoctorReport> {
+        let mut report = DoctorReport::default();
+
+        let items_dir = self.items_dir();
+        if !items_dir.exists() {
+            return Ok(report);
+        }
+
+        let mut dirs: Vec<PathBuf> = fs::read_dir(&items_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+
+        for dir in dirs {
+            let slug = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            report.checked += 1;
+
+            let mut found = |problem: Problem, fixed: bool| {
+                report.findings.push(Finding {
+                    slug: slug.clone(),
+                    problem,
+                    fixed,
+                });
+            };
+
+            if !dir.join(META_FILE).exists() {
+                found(Problem::Orphaned, false);
+                continue;
+            }
+
+            let title = match self.read_meta(&slug) {
+                Ok(item) => item.title,
+                Err(FsError::NotUtf8 { .. }) => {
+                    found(Problem::NotUtf8 { file: META_FILE.into() }, false);
+                    slug.clone()
+                }
+                Err(e) => {
+                    found(Problem::InvalidMeta { error: e.to_string() }, false);
+                    slug.clone()
+                }
+            };
+
+            let events_path = dir.join(EVENTS_FILE);
+            if events_path.exists() {
+                let bad = Self::check_events(&events_path, fix)?;
+                if !bad.is_empty() {
+                    found(Problem::BadEventLines { lines: bad }, fix);
+                }
+            } else {
+                if fix {
+                    File::create(&events_path)?;
+                }
+                found(Problem::MissingEvents, fix);
+            }
+
+            let notes_path = dir.join(NOTES_FILE);
+            if !notes_path.exists() {
+                if fix {
+                    fs::write(&notes_path, format!("# {title}\n\n"))?;
+                }
+                found(Problem::MissingNotes, fix);
+            } else if std::str::from_utf8(&fs::read(&notes_path)?).is_err() {
+                found(Problem::NotUtf8 { file: NOTES_FILE.into() }, false);
+            }
+        }
+
+        info!(
+            checked = report.checked,
+            problems = report.findings.len(),
+            remaining = report.remaining(),
+            "Checked workspace"
+        );
+
+        Ok(report)
+    }
+
+    /// Line numbers of events that don't parse; with `fix`, move them to the
+    /// quarantine file and rewrite the log without them.
+    fn check_events(events_path: &Path, fix: bool) -> Result<Vec<usize>> {
+        let content = fs::read_to_string(events_path)?;
+        let mut good = String::new();
+        let mut bad = String::new();
+        let mut bad_lines = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if serde_json::from_str::<WorkEvent>(line).is_ok() {
+                good.push_str(line);
+                good.push('\n');
+            } else {
+                bad_lines.push(i + 1);
+                bad.push_str(line);
+                bad.push('\n');
+            }
+        }
+
+        if fix && !bad_lines.is_empty() {
+            let bad_path = events_path.with_file_name(BAD_EVENTS_FILE);
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&bad_path)?
+                .write_all(bad.as_bytes())?;
+            fs::write(events_path, good)?;
+            warn!(path = %events_path.display(), count = bad_lines.len(), "Quarantined bad event lines");
+        }
+
+        Ok(bad_lines)
+    }
+
+    /// Fold runs of field changes in an item's event log (see
+    /// [`crate::compact`]), dropping intermediate values.
+    ///
+    /// The original lines are appended to `events.ndjson.archive` first,
+    /// skipping ones an earlier compaction already archived, so it holds the
+    /// full history. A log with nothing to fold is left as it is.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, its log has unreadable lines
+    /// (`worky doctor --fix` moves them aside), or IO fails.
+    pub fn compact_events(&self, uid: &str) -> Result<CompactSummary> {
+        self.compact_slug(&self.resolve_slug(uid)?)
+    }
+
+    /// Compact every item's event log, returning those that shrank.
+    ///
+    /// # Errors
+    /// Returns error if a log can't be compacted; logs before it stay
+    /// compacted.
+    pub fn compact_all(&self) -> Result<Vec<CompactSummary>> {
+        let mut slugs: Vec<String> = self.slugs()?.collect();
+        slugs.sort();
+
+        let mut compacted = Vec::new();
+        for slug in slugs {
+            let summary = self.compact_slug(&slug)?;
+            if summary.changed() {
+                compacted.push(summary);
+            }
+        }
+        Ok(compacted)
+    }
+
+    fn compact_slug(&self, slug: &str) -> Result<CompactSummary> {
+        let uid = format!("fs:{slug}");
+        let item_dir = self.item_dir(slug);
+        if !item_dir.join(META_FILE).exists() {
+            return Err(FsError::ItemNotFound(uid));
+        }
+        let events_path = item_dir.join(EVENTS_FILE);
+        if !events_path.exists() {
+            return Ok(CompactSummary { uid, before: 0, after: 0 });
+        }
+
+        let content = fs::read_to_string(&events_path)?;
+        let mut lines = Vec::new();
+        let mut events = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: WorkEvent = serde_json::from_str(line).map_err(|_| {
+                CoreError::Validation(format!(
+                    "{uid} has an unreadable event on line {}; run `worky doctor --fix` first",
+                    i + 1
+                ))
+            })?;
+            lines.push((event.id.clone(), line));
+            events.push(event);
+        }
+
+        let before = events.len();
+        let events = compact::compact(events);
+        let summary = CompactSummary { uid, before, after: events.len() };
+        if !summary.changed() {
+            return Ok(summary);
+        }
+
+        // Archive first, so a failure below loses nothing
+        let archive_path = item_dir.join(ARCHIVE_EVENTS_FILE);
+        let archived = archived_ids(&archive_path)?;
+        let mut archive = String::new();
+        for (_, line) in lines.iter().filter(|(id, _)| !archived.contains(id)) {
+            archive.push_str(line);
+            archive.push('\n');
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&archive_path)?;
+        file.write_all(archive.as_bytes())?;
+        file.sync_all()?;
+
+        let mut compacted = String::new();
+        for event in &events {
+            compacted.push_str(&serde_json::to_string(event)?);
+            compacted.push('\n');
+        }
+        // Replace the log in one step, so readers never see it half written.
+        // Events appended since the read are carried over as they are, checked
+        // for once more right before the rename.
+        let staged = item_dir.join(format!("{EVENTS_FILE}.tmp"));
+        fs::write(&staged, compacted)?;
+        let mut read_len = content.len() as u64;
+        loop {
+            let tail = read_complete_lines(&events_path, read_len)?;
+            if tail.is_empty() {
+                break;
+            }
+            OpenOptions::new().append(true).open(&staged)?.write_all(tail.as_bytes())?;
+            read_len += tail.len() as u64;
+        }
+        fs::rename(&staged, &events_path)?;
+
+        info!(
+            uid = %summary.uid,
+            before = summary.before,
+            after = summary.after,
+            "Compacted event log"
+        );
+        Ok(summary)
+    }
+
+    /// Add a comment/log entry to an item.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or the event cannot be written.
+    pub fn add_comment(&self, uid: &str, message: impl Into<String>) -> Result<()> {
+        let slug = &self.resolve_slug(uid)?;
+
+        if !self.item_dir(slug).exists() {
+            return Err(FsError::ItemNotFound(uid.to_string()));
+        }
+
+        let event = WorkEvent::comment(message);
+        self.append_event(slug, &event)?;
+
+        Ok(())
+    }
+
+    /// Correct a comment by appending a new version that replaces it.
+    ///
+    /// The original stays in the log; [`Self::comments`] shows only the latest
+    /// version. Editing a comment that was already edited replaces its latest
+    /// version. Returns the new comment event.
+    ///
+    /// # Errors
+    /// Returns `FsError::CommentNotFound` if `event_id` is not a comment on the
+    /// item, or error if the item doesn't exist or the event cannot be written.
+    pub fn edit_comment(
+        &self,
+        uid: &str,
+        event_id: &str,
+        message: impl Into<String>,
+    ) -> Result<WorkEvent> {
+        let slug = &self.resolve_slug(uid)?;
+        let events = self.read_events(uid, None, None, Some(&[EventType::CommentAdded]))?;
+
+        if !events.iter().any(|e| e.id == event_id) {
+            return Err(FsError::CommentNotFound(format!("{uid}/{event_id}")));
+        }
+
+        let mut target = event_id.to_string();
+        while let Some(newer) = events.iter().find(|e| replaced_id(e) == Some(&target)) {
+            target.clone_from(&newer.id);
+        }
+
+        let event = WorkEvent::comment_edit(target, message);
+        self.append_event(slug, &event)?;
+
+        info!(uid = %uid, replaces = %event_id, "Edited comment");
+
+        Ok(event)
+    }
+
+    /// Comments on an item in the order they were first made, each at its
+    /// latest edited version.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or its events cannot be read.
+    pub fn comments(&self, uid: &str) -> Result<Vec<WorkEvent>> {
+        let events = self.read_events(uid, None, None, Some(&[EventType::CommentAdded]))?;
+
+        let ids: HashSet<&str> = events.iter().map(|e| e.id.as_str()).collect();
+        let mut newest: HashMap<&str, usize> = HashMap::new();
+        for (i, event) in events.iter().enumerate() {
+            if let Some(replaces) = replaced_id(event) {
+                newest.insert(replaces, i);
+            }
+        }
+
+        let mut comments = Vec::new();
+        for event in &events {
+            // Edits appear in place of the comment they replace
+            if replaced_id(event).is_some_and(|r| ids.contains(r)) {
+                continue;
+            }
+
+            let mut latest = event;
+            // Bounded in case a hand-edited log has a cycle of replacements
+            for _ in 0..events.len() {
+                match newest.get(latest.id.as_str()) {
+                    Some(&i) => latest = &events[i],
+                    None => break,
+                }
+            }
+            comments.push(latest.clone());
+        }
+
+        Ok(comments)
+    }
+
+    /// Add `minutes` to the time spent on an item, optionally with a note.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, `minutes` is zero, or IO fails.
+    pub fn log_time(&self, uid: &str, minutes: u64, note: Option<&str>) -> Result<WorkItem> {
+        if minutes == 0 {
+            return Err(
+                CoreError::Validation("logged time must be at least a minute".into()).into(),
+            );
+        }
+        let note = note.map(str::trim).filter(|n| !n.is_empty());
+
+        let slug = &self.resolve_slug(uid)?;
+        let mut item = self.get_item(uid)?;
+        item.time_spent_minutes =
+            Some(item.time_spent_minutes.unwrap_or(0).saturating_add(minutes));
+        item.touch();
+        self.write_meta(slug, &item)?;
+        self.append_event(slug, &WorkEvent::time_logged(minutes, note))?;
+
+        info!(uid = %uid, minutes, "Logged time");
+        Ok(item)
+    }
+
+    /// Time logged on an item, oldest first, read from its event history.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or its events cannot be read.
+    pub fn time_log(&self, uid: &str) -> Result<Vec<TimeEntry>> {
+        let events = self.read_events(uid, None, None, Some(&[EventType::TimeLogged]))?;
+
+        Ok(events
+            .into_iter()
+            .filter_map(|event| {
+                let EventPayload::Generic(payload) = &event.payload else {
+                    return None;
+                };
+                Some(TimeEntry {
+                    at: event.timestamp,
+                    minutes: payload.get("minutes")?.as_u64()?,
+                    note: payload.get("note").and_then(Value::as_str).map(String::from),
+                    actor: event.actor,
+                })
+            })
+            .collect())
+    }
+
+    /// Copy a file into the item's `artifacts/` directory.
+    ///
+    /// Returns the stored path. If a file with identical content is already
+    /// attached, that path is returned instead of storing a second copy.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, `src` has no usable file name,
+    /// or IO fails.
+    pub fn add_artifact(&self, uid: &str, src: &Path) -> Result<PathBuf> {
+        let name = src
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| FsError::InvalidArtifactName(src.display().to_string()))?;
+        let data = fs::read(src)?;
+
+        self.add_artifact_data(uid, &name, &data)
+    }
+
+    /// Store `data` as an artifact named `name`. See [`Self::add_artifact`].
+    ///
+    /// A different file already using `name` is kept; the new one gets a
+    /// numbered name such as `trace-1.log`.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist, the name is invalid, or IO fails.
+    pub fn add_artifact_data(&self, uid: &str, name: &str, data: &[u8]) -> Result<PathBuf> {
+        artifact::validate_name(name)?;
+        let (slug, dir) = self.artifacts_dir(uid)?;
+        let hash = artifact::sha256_hex(data);
+
+        for existing in self.list_artifacts(uid)? {
+            let path = dir.join(&existing.name);
+            if artifact::sha256_hex(&fs::read(&path)?) == hash {
+                debug!(uid = %uid, name = %existing.name, "Artifact already attached");
+                return Ok(path);
+            }
+        }
+
+        let mut stored_name = name.to_string();
+        let mut n = 0;
+        while dir.join(&stored_name).exists() {
+            n += 1;
+            stored_name = artifact::numbered_name(name, n);
+        }
+
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(&stored_name);
+        fs::write(&path, data)?;
+
+        self.append_event(&slug, &WorkEvent::artifact_added(&stored_name, hash))?;
+        info!(uid = %uid, name = %stored_name, "Attached artifact");
+
+        Ok(path)
+    }
+
+    /// Files attached to an item, sorted by name.
+    ///
+    /// # Errors
+    /// Returns error if the item doesn't exist or the directory cannot be read.
+    pub fn list_artifacts(&self, uid: &str) -> Result<Vec<Artifact>> {
+        let (_, dir) = self.artifacts_dir(uid)?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut artifacts = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                artifacts.push(Artifact {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                });
+            }
+        }
+
+        artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(artifacts)
+    }
+
+    /// Path of an attached file, for reading it back.
+    ///
+    /// # Errors
+    /// Returns error if the item or artifact doesn't exist or the name is invalid.
+    pub fn artifact_path(&self, uid: &str, name: &str) -> Result<PathBuf> {
+        artifact::validate_name(name)?;
+        let (_, dir) = self.artifacts_dir(uid)?;
+
+        let path = dir.join(name);
+        if !path.is_file() {
+            return Err(FsError::ArtifactNotFound(format!("{uid}/{name}")));
+        }
+
+        Ok(path)
+    }
+
+    /// Delete an attached file.
+    ///
+    /// # Errors
+    /// Returns error if the item or artifact doesn't exist or IO fails.
+    pub fn remove_artifact(&self, uid: &str, name: &str) -> Result<()> {
+        let path = self.artifact_path(uid, name)?;
+        fs::remove_file(&path)?;
+
+        let slug = &self.resolve_slug(uid)?;
+        self.append_event(slug, &WorkEvent::artifact_removed(name))?;
+        info!(uid = %uid, name = %name, "Removed artifact");
+
+        Ok(())
+    }
+
+    // Private helpers
+
+    /// Slug and `artifacts/` path for an existing item.
+    fn artifacts_dir(&self, uid: &str) -> Result<(String, PathBuf)> {
+        let slug = self.resolve_slug(uid)?;
+        let item_dir = self.item_dir(&slug);
+        if !item_dir.join(META_FILE).exists() {
+            return Err(FsError::ItemNotFound(uid.to_string()));
+        }
+
+        Ok((slug, item_dir.join(ARTIFACTS_DIR)))
+    }
+
+    fn read_meta(&self, slug: &str) -> Result<WorkItem> {
+        let meta_path = self.item_dir(slug).join(META_FILE);
+        let content = read_item_text(&meta_path, &format!("fs:{slug}"))?;
+        let item: WorkItem = serde_yaml::from_str(&content)?;
+        Ok(item)
+    }
+
+    fn write_meta(&self, slug: &str, item: &WorkItem) -> Result<()> {
+        let meta_path = self.item_dir(slug).join(META_FILE);
+        let content = serde_yaml::to_string(item)?;
+        fs::write(&meta_path, content)?;
+
+        // Keep an index, once there is one, current. Failing that, the entry
+        // is just stale and the item is read from disk as usual.
+        let index_path = self.index_path();
+        if index_path.exists()
+            && let Some(mut index) = ItemIndex::load(&index_path)
+        {
+            let saved = Stamp::of(&meta_path).and_then(|stamp| {
+                index.insert(slug, IndexEntry::new(item, stamp));
+                index.save(&index_path)
+            });
+            if let Err(e) = saved {
+                warn!(slug = %slug, error = %e, "Failed to update index");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An item's `meta.yml` and the length of its event log, as they were when
+/// a transaction started.
+struct Snapshot {
+    uid: String,
+    dir: PathBuf,
+    meta: Vec<u8>,
+    events_len: Option<u64>,
+}
+
+impl Snapshot {
+    fn take(uid: &str, dir: &Path) -> Result<Self> {
+        Ok(Self {
+            uid: uid.to_string(),
+            dir: dir.to_path_buf(),
+            meta: fs::read(dir.join(META_FILE))?,
+            events_len: file_len(&dir.join(EVENTS_FILE))?,
+        })
+    }
+
+    /// IDs of the events appended since the snapshot was taken.
+    fn new_event_ids(&self) -> Result<Vec<String>> {
+        let mut file = match File::open(self.dir.join(EVENTS_FILE)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        file.seek(SeekFrom::Start(self.events_len.unwrap_or(0)))?;
+        let mut appended = String::new();
+        file.read_to_string(&mut appended)?;
+
+        Ok(appended
+            .lines()
+            .filter_map(|line| serde_json::from_str::<WorkEvent>(line).ok())
+            .map(|event| event.id)
+            .collect())
+    }
+
+    fn restore(&self) -> Result<()> {
+        fs::write(self.dir.join(META_FILE), &self.meta)?;
+        truncate(&self.dir.join(EVENTS_FILE), self.events_len)
+    }
+}
+
+/// IDs of the events in the compaction archive at `path`, if there is one.
+fn archived_ids(path: &Path) -> Result<HashSet<String>> {
+    #[derive(Deserialize)]
+    struct EventId {
+        id: String,
+    }
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<EventId>(line).ok())
+        .map(|event| event.id)
+        .collect())
+}
+
+/// Number of lines in the file at `path`, counted without decoding it or
+/// holding more than a line in memory.
+fn count_lines(path: &Path) -> Result<usize> {
+    let mut count = 0;
+    for line in BufReader::new(File::open(path)?).split(b'\n') {
+        line?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// The whole lines of the file at `path` after its first `offset` bytes.
+/// A line still being written is left for the next read.
+fn read_complete_lines(path: &Path, offset: u64) -> Result<String> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut tail = String::new();
+    file.read_to_string(&mut tail)?;
+    tail.truncate(tail.rfind('\n').map_or(0, |i| i + 1));
+    Ok(tail)
+}
+
+/// Length of the file at `path`, or `None` if there is none.
+fn file_len(path: &Path) -> Result<Option<u64>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some(metadata.len())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace the file at `path` with `contents` in one step, so a failed
+/// write never leaves it half written.
+fn replace_file(path: &Path, contents: &str) -> Result<()> {
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".tmp");
+    fs::write(&staged, contents)?;
+    fs::rename(&staged, path)?;
+    Ok(())
+}
+
+/// Cut the file at `path` back to `len` bytes, or remove it if it didn't
+/// exist (`None`).
+fn truncate(path: &Path, len: Option<u64>) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    match len {
+        Some(len) => OpenOptions::new().write(true).open(path)?.set_len(len)?,
+        None => fs::remove_file(path)?,
+    }
+    Ok(())
+}
+
+/// Read a text file belonging to item `uid`, naming both if it isn't UTF-8.
+fn read_item_text(path: &Path, uid: &str) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::InvalidData => FsError::NotUtf8 {
+            uid: uid.to_string(),
+            path: path.to_path_buf(),
+        },
+        _ => e.into(),
+    })
+}
+
+/// Copy the directory `from` and everything in it to `to`, which must not
+/// exist yet.
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to.parent().unwrap_or(to))?;
+    fs::create_dir(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that every file under `original` has an identical twin under `copy`.
+fn verify_copy(original: &Path, copy: &Path) -> Result<()> {
+    for entry in fs::read_dir(original)? {
+        let entry = entry?;
+        let twin = copy.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            verify_copy(&entry.path(), &twin)?;
+        } else if fs::read(entry.path())? != fs::read(&twin)? {
+            return Err(std::io::Error::other(format!(
+                "copy of {} differs from the original",
+                entry.path().display()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Distinct `values`, ignoring case, with how often each occurs; most
+/// frequent first, then alphabetical.
+fn tally(values: impl Iterator<Item = String>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, (String, usize)> = HashMap::new();
+    for value in values {
+        counts.entry(value.to_lowercase()).or_insert((value, 0)).1 += 1;
+    }
+
+    let mut tally: Vec<(String, usize)> = counts.into_values().collect();
+    tally.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tally
+}
+
+/// Index of 1-based checklist step `number`.
+fn checklist_step(checklist: &[ChecklistItem], number: usize) -> Result<usize> {
+    if (1..=checklist.len()).contains(&number) {
+        Ok(number - 1)
+    } else {
+        Err(CoreError::Validation(format!(
+            "no checklist step {number} (the checklist has {})",
+            checklist.len()
+        ))
+        .into())
+    }
+}
+
+/// Time logged on an item by one `TIME_LOGGED` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeEntry {
+    /// When it was logged.
+    pub at: DateTime<Utc>,
+    /// Who logged it, if recorded.
+    pub actor: Option<String>,
+    /// Minutes spent.
+    pub minutes: u64,
+    /// What the time went on.
+    pub note: Option<String>,
+}
+
+/// The result of an update computed without writing it.
+#[derive(Debug, Clone)]
+pub struct UpdatePlan {
+    /// The item as it would be saved.
+    pub item: WorkItem,
+    /// Events that would be appended, in order.
+    pub events: Vec<WorkEvent>,
+}
+
+/// Optional field values applied to a newly created item.
+#[derive(Debug, Default, Clone)]
+pub struct NewItemFields {
+    /// Initial state (workspace default if not set).
+    pub state: Option<String>,
+    /// Initial assignee.
+    pub assignee: Option<String>,
+    /// Initial labels.
+    pub labels: Vec<String>,
+    /// Description stored in `fields.description`.
+    pub description: Option<String>,
+    /// ID of the item in another system, e.g. a Jira key.
+    pub external_id: Option<String>,
+    /// Further custom fields, by name.
+    pub fields: BTreeMap<String, Value>,
+}
+
+impl NewItemFields {
+    /// Convert into the set operations applied to the new item.
+    #[must_use]
+    pub fn to_operations(&self) -> Vec<SetOperation> {
+        let mut operations = Vec::new();
+
+        if let Some(state) = &self.state {
+            operations.push(SetOperation::new("state", state.clone()));
+        }
+
+        if let Some(assignee) = &self.assignee {
+            operations.push(SetOperation::assignee(Some(assignee)));
+        }
+
+        if !self.labels.is_empty() {
+            operations.push(SetOperation::new("labels", self.labels.clone()));
+        }
+
+        if let Some(description) = &self.description {
+            operations.push(SetOperation::new("fields.description", description.clone()));
+        }
+
+        if let Some(external_id) = &self.external_id {
+            operations.push(SetOperation::new("external_id", external_id.clone()));
+        }
+
+        for (name, value) in &self.fields {
+            operations.push(SetOperation::new(format!("fields.{name}"), value.clone()));
+        }
+
+        operations
+    }
+}
+
+/// ID of the comment an edited comment replaces.
+fn replaced_id(event: &WorkEvent) -> Option<&str> {
+    match &event.payload {
+        EventPayload::Comment(p) => p.replaces.as_deref(),
+        _ => None,
+    }
+}
+
+/// Event for one element of a `labels` diff: removed if it had an old value.
+/// Merge `patch` into `target` key by key. Unlike a merge patch, nulls are
+/// kept as values rather than removing keys.
+fn merge_values(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_values(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+/// One event per change between two versions of an item, typed by what
+/// changed, so updates and patches are logged alike.
+fn change_events(old_json: &Value, new_json: &Value, timestamp: DateTime<Utc>) -> Vec<WorkEvent> {
+    diff_values(old_json, new_json)
+        .into_iter()
+        .filter(|(path, _, _)| path != "updated_at")
+        .map(|(path, old_val, new_val)| {
+            let event = match path.as_str() {
+                "state" => WorkEvent::state_changed(
+                    old_val.as_ref().and_then(Value::as_str).unwrap_or(""),
+                    new_val.as_str().unwrap_or(""),
+                ),
+                "labels" => label_event(old_val.as_ref(), &new_val),
+                "assignee" => WorkEvent::assigned(
+                    old_val.as_ref().and_then(Value::as_str).map(String::from),
+                    new_val.as_str().map(String::from),
+                ),
+                _ => WorkEvent::field_changed(path, old_val, new_val),
+            };
+
+            // One change, one timestamp; `undo_last` reverses them together
+            WorkEvent { timestamp, ..event }
+        })
+        .collect()
+}
+
+fn label_event(old_val: Option<&Value>, new_val: &Value) -> WorkEvent {
+    let label = |v: &Value| v.as_str().map_or_else(|| v.to_string(), String::from);
+    old_val.map_or_else(
+        || WorkEvent::label_added(label(new_val)),
+        |removed| WorkEvent::label_removed(label(removed)),
+    )
+}
+
+/// The last `max` non-empty lines of a file, in file order.
+///
+/// The file is read backwards in blocks, so a long log costs no more than
+/// its tail.
+#[allow(clippy::naive_bytecount)] // Blocks are small; not worth a dependency
+fn read_tail_lines(path: &Path, max: usize) -> Result<Vec<String>> {
+    const BLOCK_SIZE: u64 = 8 * 1024;
+
+    let mut file = File::open(path)?;
+    let mut start = file.metadata()?.len();
+    let mut tail: Vec<u8> = Vec::new();
+    let mut newlines = 0;
+
+    // `max` whole lines need a newline in front of them as well as their own
+    while start > 0 && newlines <= max {
+        let len = BLOCK_SIZE.min(start);
+        start -= len;
+
+        let mut block = Vec::new();
+        file.seek(SeekFrom::Start(start))?;
+        (&mut file).take(len).read_to_end(&mut block)?;
+        newlines += block.iter().filter(|&&b| b == b'\n').count();
+        block.append(&mut tail);
+        tail = block;
+    }
+
+    // Unless we reached the start, the first line is cut off
+    if start > 0 {
+        let first_newline = tail.iter().position(|&b| b == b'\n').map_or(0, |i| i + 1);
+        tail.drain(..first_newline);
+    }
+
+    let text = String::from_utf8_lossy(&tail);
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let skip = lines.len().saturating_sub(max);
+
+    Ok(lines[skip..].iter().map(ToString::to_string).collect())
+}
+
+/// Reorder items by rank within each state, leaving each state's positions in place.
+///
+/// Ranked items come first in ascending order; unranked items keep their
+/// existing relative order after them.
+fn order_by_rank(items: &mut [WorkItem]) {
+    let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        positions.entry(item.state.clone()).or_default().push(i);
+    }
+
+    for slots in positions.values() {
+        if slots.iter().all(|&i| items[i].rank.is_none()) {
+            continue;
+        }
+
+        let mut column: Vec<WorkItem> = slots.iter().map(|&i| items[i].clone()).collect();
+        column.sort_by(|a, b| match (a.rank, b.rank) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        for (&i, item) in slots.iter().zip(column) {
+            items[i] = item;
+        }
+    }
+}
+
+/// Where to move an item relative to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    /// Directly before the given UID.
+    Before(String),
+    /// Directly after the given UID.
+    After(String),
+}
+
+/// The fields of an event log line needed to filter it.
+#[derive(Deserialize)]
+struct EventHeader {
+    #[serde(rename = "type")]
+    event_type: EventType,
+    timestamp: DateTime<Utc>,
+}
+
+/// A single condition in an [`ItemFilter`]. Text comparisons ignore case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// State is any of these.
+    StateIn(Vec<String>),
+    /// Assigned to this person.
+    Assignee(String),
+    /// Not assigned to this person; unassigned items match.
+    NotAssignee(String),
+    /// Has no assignee.
+    Unassigned,
+    /// Has this label.
+    Label(String),
+    /// Doesn't have this label.
+    NotLabel(String),
+    /// Not updated for at least this long.
+    StaleFor(Duration),
+    /// Title or `fields.description` contains this text.
+    Text(TextQuery),
+}
+
+impl Predicate {
+    /// Check if an item satisfies this predicate.
+    #[must_use]
+    pub fn matches(&self, item: &WorkItem) -> bool {
+        let assigned_to =
+            |name: &str| item.assignee.as_ref().is_some_and(|a| a.eq_ignore_ascii_case(name));
+
+        match self {
+            Self::StateIn(states) => states.iter().any(|s| item.state.eq_ignore_ascii_case(s)),
+            Self::Assignee(name) => assigned_to(name),
+            Self::NotAssignee(name) => !assigned_to(name),
+            Self::Unassigned => item.assignee.is_none(),
+            Self::Label(label) => item.has_label(label),
+            Self::NotLabel(label) => !item.has_label(label),
+            Self::StaleFor(idle) => item.staleness() >= *idle,
+            Self::Text(query) => {
+                query.matches(&item.title)
+                    || item
+                        .fields
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .is_some_and(|d| query.matches(d))
+            }
+        }
+    }
+}
+
+impl Predicate {
+    /// Check an index entry against this predicate, or `None` if the entry
+    /// doesn't have the fields to tell.
+    #[must_use]
+    pub fn matches_indexed(&self, entry: &IndexEntry) -> Option<bool> {
+        let assigned_to =
+            |name: &str| entry.assignee.as_ref().is_some_and(|a| a.eq_ignore_ascii_case(name));
+
+        Some(match self {
+            Self::StateIn(states) => states.iter().any(|s| entry.state.eq_ignore_ascii_case(s)),
+            Self::Assignee(name) => assigned_to(name),
+            Self::NotAssignee(name) => !assigned_to(name),
+            Self::Unassigned => entry.assignee.is_none(),
+            Self::Label(label) => entry.has_label(label),
+            Self::NotLabel(label) => !entry.has_label(label),
+            Self::StaleFor(idle) => Utc::now() - entry.updated_at >= *idle,
+            Self::Text(_) => return None,
+        })
+    }
+}
+
+/// Text to look for in items, ignoring case.
+#[derive(Debug, Clone)]
+pub enum TextQuery {
+    /// A plain substring.
+    Contains(String),
+    /// A regular expression; `(?-i)` makes it case-sensitive.
+    Regex(Regex),
+}
+
+impl TextQuery {
+    /// Match `text` as a plain substring.
+    #[must_use]
+    pub fn contains(text: &str) -> Self {
+        Self::Contains(text.to_lowercase())
+    }
+
+    /// Match the regular expression `pattern`.
+    ///
+    /// # Errors
+    /// Returns `CoreError::Validation` if `pattern` is not a valid regex.
+    pub fn regex(pattern: &str) -> Result<Self> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Self::Regex)
+            .map_err(|e| CoreError::Validation(format!("invalid regex '{pattern}': {e}")).into())
+    }
+
+    /// Whether `text` matches.
+    #[must_use]
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Contains(needle) => text.to_lowercase().contains(needle),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+impl PartialEq for TextQuery {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Contains(a), Self::Contains(b)) => a == b,
+            (Self::Regex(a), Self::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TextQuery {}
+
+/// Filter criteria for listing work items. An item must satisfy every predicate.
+#[derive(Debug, Default, Clone)]
+pub struct ItemFilter {
+    /// Conditions; all must hold.
+    pub predicates: Vec<Predicate>,
+}
+
+impl ItemFilter {
+    /// A filter that matches everything.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Add a predicate.
+    #[must_use]
+    pub fn with(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Require the item to be in `state`.
+    #[must_use]
+    pub fn state(self, state: impl Into<String>) -> Self {
+        self.with(Predicate::StateIn(vec![state.into()]))
+    }
+
+    /// Whether the filter has no predicates.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.predicates.is_empty()
+    }
+
+    /// Check if an item matches this filter.
+    #[must_use]
+    pub fn matches(&self, item: &WorkItem) -> bool {
+        self.predicates.iter().all(|p| p.matches(item))
+    }
+
+    /// Whether an index entry alone shows the item can't match.
+    #[must_use]
+    pub fn rules_out(&self, entry: &IndexEntry) -> bool {
+        self.predicates
+            .iter()
+            .any(|p| p.matches_indexed(entry) == Some(false))
+    }
+}
+
+/// Filter options as accepted in request bodies and tool arguments.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct FilterQuery {
+    /// States to include; a single string or a list matching any of them.
+    #[serde(deserialize_with = "one_or_many")]
+    #[cfg_attr(feature = "schema", schemars(with = "OneOrMany"))]
+    pub state: Vec<String>,
+    /// Only items assigned to this person.
+    pub assignee: Option<String>,
+    /// Only unassigned items.
+    pub no_assignee: bool,
+    /// Exclude items assigned to this person.
+    pub not_assignee: Option<String>,
+    /// Only items with this label.
+    pub label: Option<String>,
+    /// Exclude items with any of these labels.
+    #[serde(deserialize_with = "one_or_many")]
+    #[cfg_attr(feature = "schema", schemars(with = "OneOrMany"))]
+    pub not_label: Vec<String>,
+    /// Only items whose title or `fields.description` contains this text,
+    /// ignoring case.
+    pub query: Option<String>,
+    /// Treat `query` as a regular expression.
+    pub regex: bool,
+}
+
+impl TryFrom<FilterQuery> for ItemFilter {
+    type Error = FsError;
+
+    fn try_from(query: FilterQuery) -> Result<Self> {
+        let mut filter = Self::new();
+
+        if !query.state.is_empty() {
+            filter = filter.with(Predicate::StateIn(query.state));
+        }
+        if let Some(assignee) = query.assignee {
+            filter = filter.with(Predicate::Assignee(assignee));
+        }
+        if query.no_assignee {
+            filter = filter.with(Predicate::Unassigned);
+        }
+        if let Some(assignee) = query.not_assignee {
+            filter = filter.with(Predicate::NotAssignee(assignee));
+        }
+        if let Some(label) = query.label {
+            filter = filter.with(Predicate::Label(label));
+        }
+        for label in query.not_label {
+            filter = filter.with(Predicate::NotLabel(label));
+        }
+        if let Some(text) = query.query.filter(|q| !q.is_empty()) {
+            let text = if query.regex {
+                TextQuery::regex(&text)?
+            } else {
+                TextQuery::contains(&text)
+            };
+            filter = filter.with(Predicate::Text(text));
+        }
+
+        Ok(filter)
+    }
+}
+
+/// A single string or a list of them.
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Accept either `"x"` or `["x", "y"]`.
+fn one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
 
 #[cfg(test)]
 mod tests {
@@ -447,7 +3308,7 @@ mod tests {
 
         assert!(tmp.path().join(".worky/config.yml").exists());
         assert!(tmp.path().join("work/items").exists());
-        assert_eq!(ws.config().version, 1);
+        assert_eq!(ws.config().version, CONFIG_VERSION);
     }
 
     #[test]
@@ -460,42 +3321,714 @@ mod tests {
     }
 
     #[test]
-    fn test_open_workspace() {
-        let tmp = TempDir::new().unwrap();
-        Workspace::init(tmp.path()).unwrap();
+    fn test_open_workspace() {
+        let tmp = TempDir::new().unwrap();
+        Workspace::init(tmp.path()).unwrap();
+
+        let ws = Workspace::open(tmp.path()).unwrap();
+        assert_eq!(ws.config().version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_config() {
+        let (tmp, _) = setup();
+        let path = tmp.path().join(".worky/config.yml");
+        let old = "# Team board\nversion: 1\nworkflow:\n  states: [OPEN, CLOSED]\n";
+        fs::write(&path, old).unwrap();
+
+        // Opening reads the old file as current but leaves it as it is
+        let mut ws = Workspace::open(tmp.path()).unwrap();
+        assert_eq!(ws.config().version, CONFIG_VERSION);
+        assert_eq!(ws.config().workflow.terminal, ["DONE"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+
+        let changes = ws.migrate_config().unwrap();
+        assert!(changes.contains(&"v1 -> v2: added workflow.terminal".to_string()));
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("version: 2"));
+        assert!(ws.migrate_config().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discover_from_nested_directory() {
+        let tmp = TempDir::new().unwrap();
+        let outer = tmp.path().join("outer");
+        let nested = outer.join("src/deeply/nested");
+        fs::create_dir_all(&nested).unwrap();
+
+        assert!(matches!(
+            Workspace::discover(&nested),
+            Err(FsError::WorkspaceNotFound(_))
+        ));
+
+        Workspace::init(&outer).unwrap();
+        assert_eq!(Workspace::discover(&nested).unwrap().root(), outer);
+        assert_eq!(Workspace::discover(&outer).unwrap().root(), outer);
+
+        // The closest workspace wins
+        let inner = outer.join("src");
+        Workspace::init(&inner).unwrap();
+        assert_eq!(Workspace::discover(&nested).unwrap().root(), inner);
+    }
+
+    #[test]
+    fn test_create_and_get_item() {
+        let (_tmp, ws) = setup();
+
+        let item = ws.create_item("Implement auth redirect").unwrap();
+        assert_eq!(item.uid, "fs:implement-auth-redirect");
+        assert_eq!(item.title, "Implement auth redirect");
+        assert_eq!(item.state, "TODO");
+
+        let fetched = ws.get_item("fs:implement-auth-redirect").unwrap();
+        assert_eq!(fetched.uid, item.uid);
+        assert_eq!(fetched.title, item.title);
+    }
+
+    #[test]
+    fn test_slug_conflicts() {
+        let (_tmp, mut ws) = setup();
+
+        let first = ws.create_item("Fix bug").unwrap();
+        assert!(matches!(ws.create_item("Fix bug"), Err(FsError::ItemExists(_))));
+
+        ws.config.slug.on_conflict = SlugConflict::Suffix;
+        let second = ws.create_item("Fix bug").unwrap();
+        let third = ws.create_item("Fix bug").unwrap();
+
+        assert_eq!(first.uid, "fs:fix-bug");
+        assert_eq!(second.uid, "fs:fix-bug-2");
+        assert_eq!(third.uid, "fs:fix-bug-3");
+        assert_eq!(ws.get_item("fs:fix-bug-3").unwrap().title, "Fix bug");
+    }
+
+    #[test]
+    fn test_create_item_with_fields() {
+        let (_tmp, ws) = setup();
+
+        let fields = NewItemFields {
+            assignee: Some("alice".to_string()),
+            labels: vec!["backend".to_string()],
+            description: Some("Details".to_string()),
+            ..Default::default()
+        };
+
+        let item = ws.create_item_with("With fields", &fields).unwrap();
+        assert_eq!(item.state, "TODO");
+        assert_eq!(item.assignee, Some("alice".to_string()));
+        assert_eq!(item.labels, vec!["backend"]);
+        assert_eq!(item.fields.get("description"), Some(&Value::from("Details")));
+    }
+
+    #[test]
+    fn test_update_item() {
+        let (_tmp, ws) = setup();
+
+        ws.create_item("Test task").unwrap();
+
+        let ops = vec![
+            SetOperation::new("state", "IN_PROGRESS"),
+            SetOperation::new("assignee", "alice"),
+        ];
+
+        let updated = ws.update_item("fs:test-task", &ops).unwrap();
+        assert_eq!(updated.state, "IN_PROGRESS");
+        assert_eq!(updated.assignee, Some("alice".to_string()));
+
+        let op = SetOperation::parse("priority=high").unwrap();
+        let updated = ws.update_item("fs:test-task", &[op]).unwrap();
+        assert_eq!(updated.priority, Some(worky_core::Priority::High));
+
+        let op = SetOperation::parse("priority=someday").unwrap();
+        assert!(ws.update_item("fs:test-task", &[op]).is_err());
+    }
+
+    #[test]
+    fn test_update_item_if() {
+        let (_tmp, ws) = setup();
+        let item = ws.create_item("Fix login").unwrap();
+        let ops = |op: &str| [SetOperation::parse(op).unwrap()];
+
+        let version = ws.item_version(&item.uid).unwrap();
+        assert_eq!(ws.item_version("fix-login").unwrap(), version);
+        ws.update_item_if(&item.uid, &ops("priority=high"), Some(&version))
+            .unwrap();
+        let newer = ws.item_version(&item.uid).unwrap();
+        assert_ne!(newer, version);
+
+        // An update based on the old version is refused and changes nothing
+        match ws.update_item_if(&item.uid, &ops("priority=low"), Some(&version)) {
+            Err(FsError::VersionMismatch {
+                version, current, ..
+            }) => {
+                assert_eq!(version, newer);
+                assert_eq!(current.priority, Some(worky_core::Priority::High));
+            }
+            other => panic!("expected a version mismatch, got {other:?}"),
+        }
+        assert_eq!(ws.item_version(&item.uid).unwrap(), newer);
+
+        ws.update_item_if(&item.uid, &ops("priority=low"), None).unwrap();
+    }
+
+    #[test]
+    fn test_update_item_appends_events_in_one_write() {
+        let (_tmp, mut ws) = setup();
+        ws.set_config("durability", &"synced".into()).unwrap();
+        assert_eq!(ws.config().durability, Durability::Synced);
+
+        ws.create_item("Test task").unwrap();
+        let events_path = ws.item_dir("test-task").join(EVENTS_FILE);
+        let before = fs::read_to_string(&events_path).unwrap();
+
+        let ops = [
+            SetOperation::new("state", "IN_PROGRESS"),
+            SetOperation::new("assignee", "alice"),
+            SetOperation::new("priority", "high"),
+        ];
+        ws.update_item("fs:test-task", &ops).unwrap();
+
+        let after = fs::read_to_string(&events_path).unwrap();
+        let appended = after.strip_prefix(&before).unwrap();
+        let types: Vec<EventType> = appended
+            .lines()
+            .map(|line| serde_json::from_str::<WorkEvent>(line).unwrap().event_type)
+            .collect();
+        assert_eq!(
+            types,
+            [EventType::Assigned, EventType::FieldChanged, EventType::StateChanged]
+        );
+    }
+
+    #[test]
+    fn test_update_item_checks_field_schema() {
+        let tmp = TempDir::new().unwrap();
+        Workspace::init(tmp.path()).unwrap();
+        fs::write(
+            tmp.path().join(".worky/config.yml"),
+            "version: 1\nfields:\n  estimate:\n    type: number\nstrict_fields: true\n",
+        )
+        .unwrap();
+        let ws = Workspace::open(tmp.path()).unwrap();
+        ws.create_item("Test task").unwrap();
+
+        let op = SetOperation::parse("fields.estimate=3").unwrap();
+        assert!(ws.update_item("fs:test-task", &[op]).is_ok());
+
+        let op = SetOperation::parse("fields.estimate=soon").unwrap();
+        let err = ws.update_item("fs:test-task", &[op]).unwrap_err();
+        assert!(matches!(err, FsError::Core(CoreError::Validation(_))));
+
+        let op = SetOperation::parse("fields.estmate=3").unwrap();
+        assert!(ws.update_item("fs:test-task", &[op]).is_err());
+        let op = SetOperation::parse("fields.extra.nested=3").unwrap();
+        assert!(ws.update_item("fs:test-task", &[op]).is_err());
+        assert_eq!(ws.get_item("fs:test-task").unwrap().fields.len(), 1);
+
+        // Patches are held to the same schema, and built-in fields pass
+        let patch = serde_json::json!({"fields": {"estimate": "soon"}});
+        assert!(ws.patch_item("fs:test-task", &patch).is_err());
+        let patch = serde_json::json!({"fields": {"description": "Steps"}});
+        assert!(ws.patch_item("fs:test-task", &patch).is_ok());
+        let op = PatchOp::Add {
+            path: "/fields/estmate".into(),
+            value: 3.into(),
+        };
+        assert!(ws.json_patch_item("fs:test-task", &[op]).is_err());
+    }
+
+    #[test]
+    fn test_assign() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test Task").unwrap();
+
+        let item = ws.assign("fs:test-task", Some("alice")).unwrap();
+        assert_eq!(item.assignee.as_deref(), Some("alice"));
+
+        // `assignee=` through the generic path unassigns too
+        let op = SetOperation::parse("assignee=").unwrap();
+        let item = ws.update_item("fs:test-task", &[op]).unwrap();
+        assert_eq!(item.assignee, None);
+
+        let events = ws
+            .read_events("fs:test-task", None, None, Some(&[EventType::Assigned]))
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[1].payload,
+            EventPayload::AssigneeChange(p) if p.to.is_none()
+        ));
+    }
+
+    #[test]
+    fn test_reopen_item() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+
+        // Active items are left alone
+        assert!(ws.reopen_item("fs:test-task", "IN_PROGRESS").unwrap().is_none());
+
+        ws.update_item("fs:test-task", &[SetOperation::new("state", "DONE")])
+            .unwrap();
+        assert!(ws.reopen_item("fs:test-task", "DONE").is_err());
+
+        let before = ws.read_events("fs:test-task", None, None, None).unwrap().len();
+        let item = ws.reopen_item("fs:test-task", "IN_PROGRESS").unwrap().unwrap();
+        assert_eq!(item.state, "IN_PROGRESS");
+        assert_eq!(ws.read_events("fs:test-task", None, None, None).unwrap().len(), before + 1);
+    }
+
+    #[test]
+    fn test_plan_update_writes_nothing() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+        let before = ws.read_events("fs:test-task", None, None, None).unwrap().len();
+
+        let plan = ws
+            .plan_update(
+                "fs:test-task",
+                &[
+                    SetOperation::new("state", "IN_PROGRESS"),
+                    SetOperation::assignee(Some("alice")),
+                ],
+            )
+            .unwrap();
+        assert_eq!(plan.item.state, "IN_PROGRESS");
+        assert_eq!(plan.events.len(), 2);
+        assert!(plan.events.iter().any(|e| e.event_type == EventType::Assigned));
+
+        let plan = ws
+            .plan_patch("fs:test-task", &serde_json::json!({"title": "Renamed"}))
+            .unwrap();
+        assert_eq!(plan.events.len(), 1);
+
+        let item = ws.get_item("fs:test-task").unwrap();
+        assert_eq!(item.state, "TODO");
+        assert!(item.assignee.is_none());
+        let after = ws.read_events("fs:test-task", None, None, None).unwrap().len();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn test_json_patch_item() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+        ws.update_item(
+            "fs:test-task",
+            &[SetOperation::new("labels", serde_json::json!(["bug"]))],
+        )
+        .unwrap();
+        let before = ws.read_events("fs:test-task", None, None, None).unwrap().len();
+
+        let ops: Vec<PatchOp> = serde_json::from_value(serde_json::json!([
+            {"op": "test", "path": "/state", "value": "TODO"},
+            {"op": "replace", "path": "/state", "value": "IN_PROGRESS"},
+            {"op": "add", "path": "/labels/-", "value": "ui"}
+        ]))
+        .unwrap();
+        let item = ws.json_patch_item("fs:test-task", &ops).unwrap();
+        assert_eq!(item.state, "IN_PROGRESS");
+        assert_eq!(item.labels, vec!["bug", "ui"]);
+
+        let events = ws.read_events("fs:test-task", None, None, None).unwrap();
+        assert_eq!(events.len(), before + 2);
+        assert!(events.iter().any(|e| e.event_type == EventType::StateChanged));
+
+        // A failed test leaves the item and its history untouched
+        let ops: Vec<PatchOp> = serde_json::from_value(serde_json::json!([
+            {"op": "replace", "path": "/state", "value": "DONE"},
+            {"op": "test", "path": "/assignee", "value": "alice"}
+        ]))
+        .unwrap();
+        assert!(matches!(
+            ws.json_patch_item("fs:test-task", &ops),
+            Err(FsError::Core(_))
+        ));
+
+        // So does a patch that would leave the item without a title
+        let ops = [PatchOp::Remove {
+            path: "/title".into(),
+        }];
+        assert!(matches!(
+            ws.json_patch_item("fs:test-task", &ops),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+
+        assert_eq!(ws.get_item("fs:test-task").unwrap().state, "IN_PROGRESS");
+        assert_eq!(ws.read_events("fs:test-task", None, None, None).unwrap().len(), before + 2);
+    }
+
+    #[test]
+    fn test_purge_candidates() {
+        let (_tmp, ws) = setup();
+        let old_done = ws.create_item("Old done").unwrap();
+        let new_done = ws.create_item("New done").unwrap();
+        ws.create_item("Old open").unwrap();
+        for uid in [&old_done.uid, &new_done.uid] {
+            ws.update_item(uid, &[SetOperation::new("state", "DONE")]).unwrap();
+        }
+
+        // Nothing is older than a cutoff in the past
+        let cutoff = Utc::now() - Duration::days(90);
+        assert!(ws.purge_candidates(cutoff, &[]).unwrap().is_empty());
+
+        let cutoff = Utc::now() + Duration::seconds(1);
+        let candidates = ws.purge_candidates(cutoff, &["done".to_string()]).unwrap();
+        let titles: Vec<&str> = candidates.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Old done", "New done"]);
+
+        assert!(matches!(
+            ws.purge_candidates(cutoff, &["TODO".to_string()]),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+
+        // Deleting takes the exact UID or slug, never a partial match
+        assert!(matches!(ws.delete_item("old"), Err(FsError::ItemNotFound(_))));
+        assert_eq!(ws.delete_item(&old_done.uid).unwrap(), old_done.uid);
+        assert!(matches!(ws.get_item(&old_done.uid), Err(FsError::ItemNotFound(_))));
+        assert!(matches!(ws.delete_item(&old_done.uid), Err(FsError::ItemNotFound(_))));
+        assert_eq!(ws.list_items(None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_replace_item() {
+        let (_tmp, ws) = setup();
+        let item = ws.create_item("Test task").unwrap();
+        let before = ws.read_events(&item.uid, None, None, None).unwrap().len();
+
+        let mut edited = serde_json::to_value(&item).unwrap();
+        edited["state"] = "IN_PROGRESS".into();
+        edited["labels"] = serde_json::json!(["bug"]);
+        edited["fields"] = serde_json::json!({ "estimate": 3 });
+
+        let updated = ws.replace_item(&item.uid, &edited).unwrap();
+        assert_eq!(updated.state, "IN_PROGRESS");
+        assert_eq!(updated.labels, vec!["bug"]);
+        let events = ws.read_events(&item.uid, None, None, None).unwrap();
+        assert_eq!(events.len(), before + 3);
+
+        // The UID is fixed, and the result must still be an item
+        edited["uid"] = "fs:other".into();
+        assert!(matches!(
+            ws.replace_item(&item.uid, &edited),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+        edited["uid"] = item.uid.clone().into();
+
+        // The item can be named any way it resolves
+        edited["state"] = "DONE".into();
+        assert_eq!(ws.replace_item("test-task", &edited).unwrap().state, "DONE");
+
+        edited["title"] = Value::Null;
+        assert!(ws.plan_replace(&item.uid, &edited).is_err());
+    }
+
+    #[test]
+    fn test_label_events() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+
+        ws.update_item("fs:test-task", &[SetOperation::new("labels", vec!["a", "b"])])
+            .unwrap();
+        ws.update_item("fs:test-task", &[SetOperation::new("labels", vec!["c", "a"])])
+            .unwrap();
+
+        let events = ws.read_events("fs:test-task", None, None, None).unwrap();
+        let labels: Vec<(EventType, String)> = events
+            .iter()
+            .filter_map(|e| match &e.payload {
+                EventPayload::Label(p) => Some((e.event_type, p.label.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                (EventType::LabelAdded, "a".to_string()),
+                (EventType::LabelAdded, "b".to_string()),
+                (EventType::LabelRemoved, "b".to_string()),
+                (EventType::LabelAdded, "c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_comment() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+        ws.add_comment("fs:test-task", "frist").unwrap();
+        ws.add_comment("fs:test-task", "second").unwrap();
+
+        let first = ws.comments("fs:test-task").unwrap()[0].id.clone();
+        let edit = ws.edit_comment("fs:test-task", &first, "fisrt").unwrap();
+        // Editing the original again replaces the latest version
+        let again = ws.edit_comment("fs:test-task", &first, "first").unwrap();
+
+        let message = |e: &WorkEvent| match &e.payload {
+            EventPayload::Comment(p) => (p.message.clone(), p.replaces.clone()),
+            _ => panic!("not a comment"),
+        };
+        assert_eq!(message(&again).1, Some(edit.id));
+
+        let comments = ws.comments("fs:test-task").unwrap();
+        let messages: Vec<String> = comments.iter().map(|e| message(e).0).collect();
+        assert_eq!(messages, vec!["first", "second"]);
+
+        // The log keeps every version
+        let all = ws
+            .read_events("fs:test-task", None, None, Some(&[EventType::CommentAdded]))
+            .unwrap();
+        assert_eq!(all.len(), 4);
+
+        assert!(matches!(
+            ws.edit_comment("fs:test-task", "evt_missing", "x"),
+            Err(FsError::CommentNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_undo_last() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Test task").unwrap();
+
+        // Only the CREATED event so far
+        assert!(matches!(
+            ws.undo_last("fs:test-task"),
+            Err(FsError::NotReversible(_))
+        ));
+
+        ws.update_item("fs:test-task", &[SetOperation::new("state", "IN_PROGRESS")])
+            .unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert_eq!(item.state, "TODO");
+
+        // The undo marker is now the latest event, so undo doesn't chain
+        assert!(ws.undo_last("fs:test-task").is_err());
+
+        ws.update_item("fs:test-task", &[SetOperation::new("fields.size", "m")])
+            .unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert!(!item.fields.contains_key("size"));
+
+        ws.update_item("fs:test-task", &[SetOperation::new("assignee", "alice")])
+            .unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert_eq!(item.assignee, None);
+
+        // Undoing is logged like any other change: a reassignment, not a field
+        let events = ws.read_events("fs:test-task", None, None, None).unwrap();
+        assert!(matches!(
+            &events[events.len() - 2].payload,
+            EventPayload::AssigneeChange(p) if p.from.as_deref() == Some("alice") && p.to.is_none()
+        ));
+
+        ws.update_item("fs:test-task", &[SetOperation::new("labels", vec!["bug", "ui"])])
+            .unwrap();
+        ws.update_item("fs:test-task", &[SetOperation::new("labels", vec!["ui"])])
+            .unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert_eq!(item.labels, vec!["ui", "bug"]);
+        let events = ws.read_events("fs:test-task", None, None, None).unwrap();
+        assert_eq!(events[events.len() - 2].event_type, EventType::LabelAdded);
+
+        // Everything one update changed is undone together
+        let ops = [
+            SetOperation::new("state", "IN_PROGRESS"),
+            SetOperation::new("fields.size", "l"),
+            SetOperation::new("labels", vec!["api"]),
+        ];
+        ws.update_item("fs:test-task", &ops).unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert_eq!(item.state, "TODO");
+        assert!(!item.fields.contains_key("size"));
+        let mut labels = item.labels;
+        labels.sort();
+        assert_eq!(labels, vec!["bug", "ui"]);
+
+        let events = ws.read_events("fs:test-task", None, None, None).unwrap();
+        assert_eq!(events.last().unwrap().event_type, EventType::AiAction);
+
+        // A tool's audit marker after a change is not itself the change
+        ws.update_item("fs:test-task", &[SetOperation::new("state", "IN_REVIEW")])
+            .unwrap();
+        ws.append_event("test-task", &WorkEvent::ai_action("worky-mcp", "update_item"))
+            .unwrap();
+        let item = ws.undo_last("fs:test-task").unwrap();
+        assert_eq!(item.state, "TODO");
+        assert!(ws.undo_last("fs:test-task").is_err());
+    }
+
+    #[test]
+    fn test_set_rank() {
+        let (_tmp, ws) = setup();
+        for title in ["Task A", "Task B", "Task C"] {
+            ws.create_item(title).unwrap();
+        }
+        let order = |ws: &Workspace| -> Vec<String> {
+            ws.list_items(None)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.uid)
+                .collect()
+        };
+
+        // First move renumbers the unranked column, recording only the move
+        let events = |uid: &str| ws.read_events(uid, None, None, None).unwrap().len();
+        let before = events("fs:task-b");
+        ws.set_rank("fs:task-a", &Placement::Before("fs:task-c".into()))
+            .unwrap();
+        assert_eq!(events("fs:task-b"), before);
+        assert!(ws.get_item("fs:task-b").unwrap().rank.is_some());
+        let first = order(&ws);
+        assert_eq!(first.len(), 3);
+        let a = first.iter().position(|u| u == "fs:task-a").unwrap();
+        let c = first.iter().position(|u| u == "fs:task-c").unwrap();
+        assert_eq!(a + 1, c);
+
+        // Later moves take the midpoint between ranked neighbours
+        let last = first.last().unwrap().clone();
+        let moved = ws
+            .set_rank(&last, &Placement::After(first[0].clone()))
+            .unwrap();
+        let ranks: Vec<f64> = ws
+            .list_items(None)
+            .unwrap()
+            .iter()
+            .filter_map(|i| i.rank)
+            .collect();
+        assert!(ranks.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(order(&ws)[1], moved.uid);
+
+        ws.update_item("fs:task-b", &[SetOperation::new("state", "DONE")])
+            .unwrap();
+        assert!(ws
+            .set_rank("fs:task-b", &Placement::After("fs:task-a".into()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_templates() {
+        let (tmp, ws) = setup();
+        assert!(ws.list_templates().unwrap().is_empty());
+
+        let dir = tmp.path().join(".worky/templates");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bug.yml"), "labels: [bug]\ndescription: |\n  Steps:\n").unwrap();
+        fs::write(dir.join("feature.yml"), "state: IN_REVIEW\n").unwrap();
+
+        assert_eq!(ws.list_templates().unwrap(), vec!["bug", "feature"]);
+
+        let bug = ws.load_template("bug").unwrap();
+        assert_eq!(bug.labels, vec!["bug"]);
+        assert_eq!(bug.description.as_deref(), Some("Steps:\n"));
+
+        let err = ws.load_template("chore").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "template 'chore' not found (available: bug, feature)"
+        );
+        assert!(ws.load_template("../config").is_err());
+    }
+
+    #[test]
+    fn test_artifacts() {
+        let (tmp, ws) = setup();
+        let item = ws.create_item("Attach logs").unwrap();
+
+        let src = tmp.path().join("trace.log");
+        fs::write(&src, "first").unwrap();
+        let stored = ws.add_artifact(&item.uid, &src).unwrap();
+        assert!(stored.ends_with("artifacts/trace.log"));
+
+        // Same content is not stored twice
+        assert_eq!(ws.add_artifact(&item.uid, &src).unwrap(), stored);
+
+        // Different content under the same name gets a numbered name
+        fs::write(&src, "second").unwrap();
+        let second = ws.add_artifact(&item.uid, &src).unwrap();
+        assert!(second.ends_with("artifacts/trace-1.log"));
+
+        let names: Vec<_> = ws
+            .list_artifacts(&item.uid)
+            .unwrap()
+            .into_iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, vec!["trace-1.log", "trace.log"]);
+
+        assert!(matches!(
+            ws.add_artifact_data(&item.uid, "../meta.yml", b"x"),
+            Err(FsError::InvalidArtifactName(_))
+        ));
+        assert!(matches!(
+            ws.artifact_path(&item.uid, "missing.txt"),
+            Err(FsError::ArtifactNotFound(_))
+        ));
+
+        ws.remove_artifact(&item.uid, "trace.log").unwrap();
+        assert_eq!(ws.list_artifacts(&item.uid).unwrap().len(), 1);
+
+        let types = [EventType::ArtifactAdded, EventType::ArtifactRemoved];
+        let events = ws.read_events(&item.uid, None, None, Some(&types)).unwrap();
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_doctor() {
+        let (tmp, ws) = setup();
+        ws.create_item("Healthy").unwrap();
+        ws.create_item("Broken").unwrap();
 
-        let ws = Workspace::open(tmp.path()).unwrap();
-        assert_eq!(ws.config().version, 1);
-    }
+        let items = tmp.path().join("work/items");
+        fs::create_dir_all(items.join("orphan")).unwrap();
+        fs::remove_file(items.join("broken/notes.md")).unwrap();
+        let mut events = OpenOptions::new()
+            .append(true)
+            .open(items.join("broken/events.ndjson"))
+            .unwrap();
+        writeln!(events, "{{not json").unwrap();
 
-    #[test]
-    fn test_create_and_get_item() {
-        let (_tmp, ws) = setup();
+        let report = ws.doctor(false).unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.remaining(), 3);
+        assert!(report.findings.iter().any(|f| f.slug == "orphan" && f.problem == Problem::Orphaned));
+        assert!(report.findings.contains(&Finding {
+            slug: "broken".into(),
+            problem: Problem::BadEventLines { lines: vec![2] },
+            fixed: false,
+        }));
 
-        let item = ws.create_item("Implement auth redirect").unwrap();
-        assert_eq!(item.uid, "fs:implement-auth-redirect");
-        assert_eq!(item.title, "Implement auth redirect");
-        assert_eq!(item.state, "TODO");
+        let report = ws.doctor(true).unwrap();
+        assert_eq!(report.remaining(), 1); // the orphan
+        assert!(items.join("broken/notes.md").exists());
+        assert_eq!(
+            fs::read_to_string(items.join("broken/events.ndjson.bad")).unwrap(),
+            "{not json\n"
+        );
+        assert_eq!(ws.read_events("fs:broken", None, None, None).unwrap().len(), 1);
 
-        let fetched = ws.get_item("fs:implement-auth-redirect").unwrap();
-        assert_eq!(fetched.uid, item.uid);
-        assert_eq!(fetched.title, item.title);
+        assert_eq!(ws.doctor(false).unwrap().remaining(), 1);
     }
 
     #[test]
-    fn test_update_item() {
+    fn test_non_utf8_meta() {
         let (_tmp, ws) = setup();
+        ws.create_item("Garbled").unwrap();
 
-        ws.create_item("Test task").unwrap();
+        let meta_path = ws.item_dir("garbled").join(META_FILE);
+        let mut bytes = fs::read(&meta_path).unwrap();
+        bytes.extend_from_slice(b"title: caf\xe9\n");
+        fs::write(&meta_path, bytes).unwrap();
 
-        let ops = vec![
-            SetOperation::new("state", "IN_PROGRESS"),
-            SetOperation::new("assignee", "alice"),
-        ];
+        let err = ws.get_item("fs:garbled").unwrap_err();
+        assert!(matches!(
+            &err,
+            FsError::NotUtf8 { uid, path } if uid == "fs:garbled" && *path == meta_path
+        ));
+        assert!(err.to_string().contains("meta.yml of fs:garbled is not valid UTF-8"));
 
-        let updated = ws.update_item("fs:test-task", &ops).unwrap();
-        assert_eq!(updated.state, "IN_PROGRESS");
-        assert_eq!(updated.assignee, Some("alice".to_string()));
+        let report = ws.doctor(false).unwrap();
+        assert_eq!(report.findings[0].problem, Problem::NotUtf8 { file: META_FILE.into() });
     }
 
     #[test]
@@ -521,14 +4054,174 @@ mod tests {
         .unwrap();
         ws.create_item("Task 2").unwrap();
 
-        let filter = ItemFilter {
-            state: Some("IN_PROGRESS".to_string()),
-            ..Default::default()
-        };
+        let filter = ItemFilter::new().state("IN_PROGRESS");
 
         let items = ws.list_items(Some(&filter)).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].state, "IN_PROGRESS");
+
+        let streamed: Vec<_> = ws
+            .iter_items(Some(&filter))
+            .unwrap()
+            .map(|i| i.uid)
+            .collect();
+        assert_eq!(streamed, ["fs:task-1"]);
+        assert_eq!(ws.iter_items(None).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_workyignore_hides_items() {
+        let (tmp, ws) = setup();
+        ws.create_item("Fix login").unwrap();
+        ws.create_item("Scratch notes").unwrap();
+        ws.create_item("Scratch keep").unwrap();
+
+        fs::write(tmp.path().join(IGNORE_FILE), "scratch-*\n!scratch-keep\n").unwrap();
+
+        let mut uids: Vec<_> = ws
+            .list_items(None)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.uid)
+            .collect();
+        uids.sort();
+        assert_eq!(uids, ["fs:fix-login", "fs:scratch-keep"]);
+        assert_eq!(ws.stats().unwrap().total, 2);
+
+        // Still reachable directly
+        let hidden = ws.get_item("fs:scratch-notes").unwrap();
+        assert_eq!(hidden.title, "Scratch notes");
+    }
+
+    #[test]
+    fn test_compound_filter() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Task 1").unwrap();
+        ws.update_item(
+            "fs:task-1",
+            &[
+                SetOperation::new("state", "IN_PROGRESS"),
+                SetOperation::new("assignee", "bob"),
+            ],
+        )
+        .unwrap();
+        ws.create_item("Task 2").unwrap();
+        ws.update_item("fs:task-2", &[SetOperation::new("labels", vec!["wontfix"])])
+            .unwrap();
+        ws.create_item("Task 3").unwrap();
+        ws.update_item("fs:task-3", &[SetOperation::new("state", "DONE")])
+            .unwrap();
+
+        let uids = |filter: ItemFilter| -> Vec<String> {
+            let mut uids: Vec<_> = ws
+                .list_items(Some(&filter))
+                .unwrap()
+                .into_iter()
+                .map(|i| i.uid)
+                .collect();
+            uids.sort();
+            uids
+        };
+
+        let query: FilterQuery =
+            serde_json::from_str(r#"{"state": ["todo", "IN_PROGRESS"]}"#).unwrap();
+        assert_eq!(uids(query.try_into().unwrap()), vec!["fs:task-1", "fs:task-2"]);
+
+        let query: FilterQuery =
+            serde_json::from_str(r#"{"not_assignee": "bob", "not_label": "wontfix"}"#).unwrap();
+        assert_eq!(uids(query.try_into().unwrap()), vec!["fs:task-3"]);
+
+        let filter = ItemFilter::new()
+            .with(Predicate::Unassigned)
+            .with(Predicate::StateIn(vec!["TODO".into()]));
+        assert_eq!(uids(filter), vec!["fs:task-2"]);
+
+        let stale = |idle| ItemFilter::new().with(Predicate::StaleFor(idle));
+        assert!(uids(stale(Duration::days(1))).is_empty());
+        assert_eq!(uids(stale(Duration::zero())).len(), 3);
+    }
+
+    #[test]
+    fn test_text_filter() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Task 1").unwrap();
+        ws.create_item("Task 2").unwrap();
+        ws.create_item("Login crash").unwrap();
+        ws.update_item(
+            "fs:task-2",
+            &[SetOperation::new("fields.description", "Happens after LOGIN")],
+        )
+        .unwrap();
+
+        let uids = |query: &str| -> Vec<String> {
+            let query: FilterQuery = serde_json::from_str(query).unwrap();
+            let filter = ItemFilter::try_from(query).unwrap();
+            let mut uids: Vec<_> = ws
+                .list_items(Some(&filter))
+                .unwrap()
+                .into_iter()
+                .map(|i| i.uid)
+                .collect();
+            uids.sort();
+            uids
+        };
+
+        assert_eq!(uids(r#"{"query": "login"}"#), ["fs:login-crash", "fs:task-2"]);
+        assert_eq!(
+            uids(r#"{"query": "task [12]$", "regex": true}"#),
+            ["fs:task-1", "fs:task-2"]
+        );
+        // Without `regex`, metacharacters are plain text
+        assert!(uids(r#"{"query": "task [12]$"}"#).is_empty());
+
+        let query: FilterQuery = serde_json::from_str(r#"{"query": "[", "regex": true}"#).unwrap();
+        assert!(matches!(
+            ItemFilter::try_from(query),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+    }
+
+    #[test]
+    fn test_recent_events() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Task A").unwrap();
+        ws.create_item("Task B").unwrap();
+        ws.add_comment("fs:task-a", "first").unwrap();
+        ws.add_comment("fs:task-b", "second").unwrap();
+
+        let feed = ws.recent_events(None, 10).unwrap();
+        assert_eq!(feed.len(), 4);
+        assert!(feed.windows(2).all(|w| w[0].1.timestamp >= w[1].1.timestamp));
+        assert_eq!(feed[0].0, "fs:task-b");
+        assert_eq!(feed[0].1.event_type, EventType::CommentAdded);
+
+        let feed = ws.recent_events(None, 2).unwrap();
+        assert_eq!(feed.len(), 2);
+        assert!(feed.iter().all(|(_, e)| e.event_type == EventType::CommentAdded));
+
+        let since = feed[1].1.timestamp;
+        let feed = ws.recent_events(Some(since), 10).unwrap();
+        assert_eq!(feed.len(), 2);
+
+        assert!(ws.recent_events(None, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_tail_lines() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("log");
+
+        // Several read blocks' worth of lines
+        let lines: Vec<String> = (0..3000).map(|i| format!("line {i}")).collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        assert_eq!(
+            read_tail_lines(&path, 3).unwrap(),
+            vec!["line 2997", "line 2998", "line 2999"]
+        );
+        assert_eq!(read_tail_lines(&path, 5000).unwrap().len(), 3000);
+
+        fs::write(&path, "only\n\n").unwrap();
+        assert_eq!(read_tail_lines(&path, 3).unwrap(), vec!["only"]);
     }
 
     #[test]
@@ -542,7 +4235,711 @@ mod tests {
         )
         .unwrap();
 
-        let events = ws.read_events("fs:event-test", None).unwrap();
+        let events = ws.read_events("fs:event-test", None, None, None).unwrap();
         assert!(events.len() >= 2); // CREATED + STATE_CHANGED
+
+        ws.add_comment("fs:event-test", "note").unwrap();
+        let types = [EventType::StateChanged, EventType::CommentAdded];
+        let events = ws.read_events("fs:event-test", None, None, Some(&types)).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_type, EventType::CommentAdded);
+
+        // The window includes `since` and excludes `until`
+        let all = ws.read_events("fs:event-test", None, None, None).unwrap();
+        let comment_at = all.last().unwrap().timestamp;
+        let events = ws
+            .read_events("fs:event-test", None, Some(comment_at), None)
+            .unwrap();
+        assert_eq!(events.len(), all.len() - 1);
+        let events = ws
+            .read_events("fs:event-test", Some(comment_at), Some(comment_at), None)
+            .unwrap();
+        assert!(events.is_empty());
+
+        let earlier = comment_at - Duration::hours(1);
+        assert!(matches!(
+            ws.read_events("fs:event-test", Some(comment_at), Some(earlier), None),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+    }
+
+    #[test]
+    fn test_read_events_skips_corrupt_lines() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Torn").unwrap();
+
+        let mut events = OpenOptions::new()
+            .append(true)
+            .open(ws.item_dir("torn").join(EVENTS_FILE))
+            .unwrap();
+        writeln!(events, r#"{{"id": "01", "timestamp": "2025-01"#).unwrap();
+        writeln!(events, "{}", serde_json::to_string(&WorkEvent::comment("after")).unwrap())
+            .unwrap();
+
+        let events = ws.read_events("fs:torn", None, None, None).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, EventType::Created);
+        assert_eq!(events[1].event_type, EventType::CommentAdded);
+        assert_eq!(ws.get_item("fs:torn").unwrap().title, "Torn");
+    }
+
+    #[test]
+    fn test_export() {
+        let (_tmp, ws) = setup();
+
+        ws.create_item("Task B").unwrap();
+        ws.create_item("Task A").unwrap();
+        ws.add_comment("fs:task-a", "hello").unwrap();
+
+        let mut out = Vec::new();
+        ws.export(&mut out, ExportFormat::Json).unwrap();
+        let bundle: Vec<Value> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(bundle.len(), 2);
+        assert_eq!(bundle[0]["uid"], "fs:task-a");
+        assert_eq!(bundle[0]["events"].as_array().unwrap().len(), 2);
+        assert_eq!(bundle[0]["notes"], "# Task A\n\n");
+
+        let mut out = Vec::new();
+        ws.export(&mut out, ExportFormat::Ndjson).unwrap();
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["uid"], "fs:task-b");
+    }
+
+    #[test]
+    fn test_import_round_trip() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Task A").unwrap();
+        ws.update_item("fs:task-a", &[SetOperation::new("state", "IN_PROGRESS")])
+            .unwrap();
+        ws.add_comment("fs:task-a", "hello").unwrap();
+
+        // Enough fields that an unordered map would shuffle them
+        ws.create_item("Task B").unwrap();
+        let ops: Vec<SetOperation> = [
+            "fields.size=m",
+            "fields.n=null",
+            "fields.team.name=core",
+            "fields.estimate=3",
+            "fields.area=api",
+            "fields.zone=eu",
+            "labels=[\"ui\",\"bug\"]",
+        ]
+        .iter()
+        .map(|op| SetOperation::parse(op).unwrap())
+        .collect();
+        ws.update_item("fs:task-b", &ops).unwrap();
+        ws.add_blocker("fs:task-b", "fs:task-a").unwrap();
+        fs::write(ws.item_dir("task-b").join(NOTES_FILE), "  indented\nnotes\n").unwrap();
+
+        let mut bundle = Vec::new();
+        ws.export(&mut bundle, ExportFormat::Ndjson).unwrap();
+
+        let (_tmp2, fresh) = setup();
+        let summary = fresh.import(bundle.as_slice(), ImportMode::Skip).unwrap();
+        assert_eq!(summary.created, 2);
+
+        for slug in ["task-a", "task-b"] {
+            for file in [META_FILE, EVENTS_FILE, NOTES_FILE] {
+                let original = fs::read(ws.item_dir(slug).join(file)).unwrap();
+                let imported = fs::read(fresh.item_dir(slug).join(file)).unwrap();
+                assert_eq!(original, imported, "{slug}/{file} differs");
+            }
+        }
+
+        let summary = fresh.import(bundle.as_slice(), ImportMode::Skip).unwrap();
+        assert_eq!(summary.skipped, 2);
+        assert!(fresh.import(bundle.as_slice(), ImportMode::ErrorOnConflict).is_err());
+
+        let mut array = Vec::new();
+        ws.export(&mut array, ExportFormat::Json).unwrap();
+        let summary = fresh.import(array.as_slice(), ImportMode::Overwrite).unwrap();
+        assert_eq!(summary.created, 2);
+
+        let bad = br#"{"uid":"fs:../escape","title":"x","state":"TODO","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}"#;
+        let summary = fresh.import(&bad[..], ImportMode::Skip).unwrap();
+        assert_eq!(summary.failed, 1);
+    }
+
+    #[test]
+    fn test_add_blocker_rejects_cycles() {
+        let (_tmp, ws) = setup();
+
+        ws.create_item("Task A").unwrap();
+        ws.create_item("Task B").unwrap();
+        ws.create_item("Task C").unwrap();
+
+        let a = ws.add_blocker("fs:task-a", "fs:task-b").unwrap();
+        assert_eq!(a.blocked_by, vec!["fs:task-b"]);
+        ws.add_blocker("fs:task-b", "fs:task-c").unwrap();
+
+        let result = ws.add_blocker("fs:task-c", "fs:task-a");
+        assert!(matches!(result, Err(FsError::DependencyCycle(_))));
+
+        let result = ws.add_blocker("fs:task-a", "fs:task-a");
+        assert!(matches!(result, Err(FsError::DependencyCycle(_))));
+
+        let result = ws.add_blocker("fs:task-a", "fs:missing");
+        assert!(matches!(result, Err(FsError::ItemNotFound(_))));
+
+        // A dangling link doesn't resolve to an item whose slug starts the same
+        // (through `fs:fix`, `fs:task-c` would seem to depend on `fs:docs`)
+        for title in ["Fix", "Fix login", "Docs"] {
+            ws.create_item(title).unwrap();
+        }
+        ws.add_blocker("fs:fix-login", "fs:docs").unwrap();
+        ws.add_blocker("fs:task-c", "fs:fix").unwrap();
+        ws.delete_item("fs:fix").unwrap();
+        ws.add_blocker("fs:docs", "fs:task-c").unwrap();
+        assert!(matches!(ws.get_linked_item("fs:fix"), Err(FsError::ItemNotFound(_))));
+    }
+
+    #[test]
+    fn test_checklist() {
+        let (_tmp, ws) = setup();
+        let item = ws.create_item("Release").unwrap();
+
+        ws.add_checklist_item(&item.uid, "Tag").unwrap();
+        ws.add_checklist_item(&item.uid, "Build").unwrap();
+        ws.add_checklist_item(&item.uid, "Announce").unwrap();
+        ws.set_checklist_done(&item.uid, 1, true).unwrap();
+        let item = ws.remove_checklist_item(&item.uid, 2).unwrap();
+
+        assert_eq!(
+            item.checklist,
+            [
+                ChecklistItem { text: "Tag".into(), done: true },
+                ChecklistItem::new("Announce"),
+            ]
+        );
+        assert_eq!(ws.get_item(&item.uid).unwrap().checklist_progress(), (1, 2));
+
+        assert!(ws.set_checklist_done(&item.uid, 3, true).is_err());
+        assert!(ws.remove_checklist_item(&item.uid, 0).is_err());
+        assert!(ws.add_checklist_item(&item.uid, "  ").is_err());
+
+        let events = ws.read_events(&item.uid, None, None, None).unwrap();
+        let updates = events
+            .iter()
+            .filter(|e| e.event_type == EventType::ChecklistUpdated)
+            .count();
+        assert_eq!(updates, 5);
+
+        // Empty checklists stay out of meta.yml
+        let other = ws.create_item("Other").unwrap();
+        let meta = fs::read_to_string(ws.item_dir("other").join(META_FILE)).unwrap();
+        assert!(other.checklist.is_empty() && !meta.contains("checklist"));
+    }
+
+    #[test]
+    fn test_log_time() {
+        let (_tmp, ws) = setup();
+        let item = ws.create_item("Fix login").unwrap();
+
+        ws.log_time(&item.uid, 30, Some("repro")).unwrap();
+        let item = ws.log_time(&item.uid, 45, None).unwrap();
+        assert_eq!(item.time_spent_minutes, Some(75));
+        assert_eq!(ws.get_item(&item.uid).unwrap().time_spent_minutes, Some(75));
+
+        assert!(ws.log_time(&item.uid, 0, None).is_err());
+
+        let log = ws.time_log(&item.uid).unwrap();
+        let entries: Vec<(u64, Option<&str>)> =
+            log.iter().map(|e| (e.minutes, e.note.as_deref())).collect();
+        assert_eq!(entries, [(30, Some("repro")), (45, None)]);
+    }
+
+    #[test]
+    fn test_all_labels_and_assignees() {
+        let (_tmp, ws) = setup();
+        for (title, labels, assignee) in [
+            ("A", r#"["bug", "ui"]"#, "alice"),
+            ("B", r#"["Bug"]"#, "bob"),
+            ("C", r#"["api"]"#, "Alice"),
+            ("D", "[]", ""),
+        ] {
+            let item = ws.create_item(title).unwrap();
+            let ops = [
+                SetOperation::parse(&format!("labels={labels}")).unwrap(),
+                SetOperation::assignee(Some(assignee)),
+            ];
+            ws.update_item(&item.uid, &ops).unwrap();
+        }
+
+        let labels = ws.all_labels().unwrap();
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[0].1, 2);
+        assert!(labels[0].0.eq_ignore_ascii_case("bug"));
+        assert_eq!(labels[1..], [("api".to_string(), 1), ("ui".to_string(), 1)]);
+
+        let assignees = ws.all_assignees().unwrap();
+        assert_eq!(assignees.len(), 2);
+        assert_eq!(assignees[0].1, 2);
+        assert_eq!(assignees[1], ("bob".to_string(), 1));
+    }
+
+    #[test]
+    fn test_ready_items() {
+        let (_tmp, ws) = setup();
+
+        ws.create_item("Task A").unwrap();
+        ws.create_item("Task B").unwrap();
+        ws.add_blocker("fs:task-a", "fs:task-b").unwrap();
+
+        let ready = ws.ready_items(None).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].uid, "fs:task-b");
+
+        ws.update_item("fs:task-b", &[SetOperation::new("state", "DONE")])
+            .unwrap();
+
+        let ready = ws.ready_items(None).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].uid, "fs:task-a");
+    }
+
+    #[test]
+    fn test_create_item_slug_limits() {
+        let (_tmp, ws) = setup();
+
+        // Nothing to build a slug from
+        for title in ["!!!", ".", "..", "   "] {
+            let err = ws.create_item(title).unwrap_err();
+            assert!(matches!(err, FsError::InvalidSlug(_)), "{title}: {err}");
+        }
+
+        // Emoji are transliterated to their names rather than dropped
+        let item = ws.create_item("🎉🎉").unwrap();
+        assert_eq!(item.uid, "fs:tada-tada");
+
+        let long = "word ".repeat(40);
+        let item = ws.create_item(&long).unwrap();
+        let slug = item.uid.strip_prefix("fs:").unwrap();
+        assert_eq!(slug.len(), MAX_SLUG_LEN);
+        assert!(slug.starts_with("word-word-"));
+
+        // The hash suffix is stable and tells apart titles with a shared prefix
+        assert_eq!(Workspace::generate_slug(&long).unwrap(), slug);
+        let other = Workspace::generate_slug(&format!("{long} other")).unwrap();
+        assert_ne!(other, slug);
+        assert_eq!(other.len(), MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn test_sync_notes() {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        let item = ws.create_item("Fix login").unwrap();
+        let notes_path = ws.item_dir("fix-login").join(NOTES_FILE);
+
+        // New items have notes but no front matter
+        assert!(ws.sync_notes(&item.uid).is_err());
+
+        fs::write(&notes_path, "---\nteam: web\npoints: 3\n---\n# Fix login\n").unwrap();
+        let synced = ws.sync_notes(&item.uid).unwrap();
+        assert_eq!(synced.fields["team"], "web");
+        assert_eq!(synced.fields["points"], 3);
+        let notes = ws.read_notes(&item.uid).unwrap().unwrap();
+        assert_eq!(notes.body(), "# Fix login\n");
+
+        // Nothing left to change, so nothing is written
+        assert!(ws.plan_sync_notes(&item.uid).unwrap().events.is_empty());
+        assert_eq!(ws.sync_notes(&item.uid).unwrap(), synced);
+
+        // Malformed front matter is an error and leaves the notes untouched
+        let bad = "---\nteam: [web\n---\n# Fix login\n";
+        fs::write(&notes_path, bad).unwrap();
+        assert!(ws.sync_notes(&item.uid).is_err());
+        assert_eq!(fs::read_to_string(&notes_path).unwrap(), bad);
+        assert_eq!(ws.get_item(&item.uid).unwrap(), synced);
+    }
+
+    #[test]
+    fn test_compact_events() {
+        let (_tmp, mut ws) = setup();
+        let item = ws.create_item("Fix login").unwrap();
+        let progress = |n: i64| [SetOperation::new("fields.progress", n)];
+        for n in 1..=5 {
+            ws.update_item(&item.uid, &progress(n)).unwrap();
+        }
+        ws.add_comment(&item.uid, "Halfway").unwrap();
+
+        // The first change adds the whole `fields` map, which ends the run
+        let summary = ws.compact_events("login").unwrap();
+        assert_eq!((summary.before, summary.after), (7, 4));
+        let events = ws.read_events(&item.uid, None, None, None).unwrap();
+        let EventPayload::FieldChange(change) = &events[2].payload else {
+            panic!("expected a field change, got {:?}", events[2]);
+        };
+        assert_eq!(change.old_value, Some(1.into()));
+        assert_eq!(change.new_value, 5);
+        assert_eq!(ws.get_item(&item.uid).unwrap().fields["progress"], 5);
+
+        // The archive keeps every original line once, across compactions
+        ws.update_item(&item.uid, &progress(6)).unwrap();
+        ws.update_item(&item.uid, &progress(7)).unwrap();
+        assert_eq!(ws.compact_all().unwrap().len(), 1);
+        assert!(!ws.compact_events(&item.uid).unwrap().changed());
+        let archive = ws.item_dir("fix-login").join(ARCHIVE_EVENTS_FILE);
+        assert_eq!(fs::read_to_string(archive).unwrap().lines().count(), 9);
+
+        // Writes compact the log themselves when it passes a multiple of the
+        // threshold, not on every write while it stays over it
+        ws.set_config("events.compact_threshold", &4.into()).unwrap();
+        ws.set_config("events.auto_compact", &true.into()).unwrap();
+        let count = |ws: &Workspace| ws.read_events(&item.uid, None, None, None).unwrap().len();
+        assert_eq!(count(&ws), 5);
+        for n in 8..=10 {
+            ws.update_item(&item.uid, &progress(n)).unwrap();
+        }
+        assert_eq!(count(&ws), 8);
+        ws.update_item(&item.uid, &progress(11)).unwrap();
+        assert_eq!(count(&ws), 5);
+    }
+
+    #[test]
+    fn test_wip_limits() {
+        let (_tmp, mut ws) = setup();
+        ws.set_config("workflow.wip_limits.IN_PROGRESS", &1.into()).unwrap();
+        let first = ws.create_item("Fix login").unwrap();
+        let second = ws.create_item("Fix logout").unwrap();
+        let to_progress = [SetOperation::new("state", "IN_PROGRESS")];
+
+        ws.update_item(&first.uid, &to_progress).unwrap();
+        // Updating an item already in the state doesn't count it twice
+        ws.update_item(&first.uid, &[SetOperation::new("priority", "high")]).unwrap();
+
+        let err = ws.update_item(&second.uid, &to_progress).unwrap_err();
+        assert!(matches!(err, FsError::WipLimitReached { limit: 1, .. }));
+        let patch = serde_json::json!({ "state": "IN_PROGRESS" });
+        assert!(ws.patch_item(&second.uid, &patch).is_err());
+        assert_eq!(ws.get_item(&second.uid).unwrap().state, "TODO");
+
+        // A new item that can't start in the full state isn't created at all
+        let fields = NewItemFields {
+            state: Some("IN_PROGRESS".to_string()),
+            ..Default::default()
+        };
+        let err = ws.create_item_with("Fix signup", &fields).unwrap_err();
+        assert!(matches!(err, FsError::WipLimitReached { .. }));
+        assert!(matches!(ws.get_item("fs:fix-signup"), Err(FsError::ItemNotFound(_))));
+
+        let ws = ws.ignoring_wip_limits();
+        ws.update_item(&second.uid, &to_progress).unwrap();
+        let item = ws.create_item_with("Fix signup", &fields).unwrap();
+        assert_eq!(item.state, "IN_PROGRESS");
+        let events = ws.read_events(&item.uid, None, None, None).unwrap();
+        assert_eq!(events[0].event_type, EventType::Created);
+        assert_eq!(events[1].event_type, EventType::StateChanged);
+    }
+
+    #[test]
+    fn test_transaction_rolls_back() {
+        let (_tmp, mut ws) = setup();
+        ws.set_config("audit.enabled", &true.into()).unwrap();
+        let first = ws.create_item("Fix login").unwrap();
+        let second = ws.create_item("Fix logout").unwrap();
+        let meta_path = ws.item_dir(Workspace::slug_from_uid(&first.uid).unwrap()).join(META_FILE);
+        let meta_before = fs::read_to_string(&meta_path).unwrap();
+        let events_before = ws.read_events(&first.uid, None, None, None).unwrap().len();
+
+        let result = ws.transaction(&[&first.uid, &second.uid], |ws| {
+            ws.update_item(&first.uid, &[SetOperation::new("priority", "high")])?;
+            ws.update_item(&second.uid, &[SetOperation::new("priority", "someday")])
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&meta_path).unwrap(), meta_before);
+        assert_eq!(ws.read_events(&first.uid, None, None, None).unwrap().len(), events_before);
+        assert_eq!(ws.get_item(&first.uid).unwrap().priority, first.priority);
+
+        // The audit log keeps the undone event and records the rollback
+        let audit = ws.read_audit(None).unwrap();
+        let [.., undone, rollback] = audit.as_slice() else {
+            panic!("expected the update and its rollback in the audit log");
+        };
+        assert_eq!(rollback.event.event_type, EventType::RolledBack);
+        assert_eq!(rollback.uid, first.uid);
+        let EventPayload::Generic(payload) = &rollback.event.payload else {
+            panic!("expected a generic payload");
+        };
+        assert_eq!(payload["events"], serde_json::json!([undone.event.id]));
+
+        let updated = ws
+            .update_items(&[&first.uid, &second.uid], &[SetOperation::new("priority", "low")])
+            .unwrap();
+        assert!(updated.iter().all(|item| item.priority == Some(worky_core::Priority::Low)));
+    }
+
+    #[test]
+    fn test_next_item() {
+        let (_tmp, ws) = setup();
+        let states = ["TODO".to_string(), "IN_PROGRESS".to_string()];
+        assert!(ws.next_item("alice", &states).unwrap().is_none());
+
+        let oldest = ws.create_item("Oldest").unwrap();
+        let urgent = ws.create_item("Urgent").unwrap();
+        let theirs = ws.create_item("Theirs").unwrap();
+        let blocker = ws.create_item("Blocker").unwrap();
+        ws.create_item("Newest").unwrap();
+        for (item, op) in [
+            (&urgent, "priority=critical"),
+            (&theirs, "priority=critical"),
+            (&theirs, "assignee=bob"),
+            (&blocker, "priority=high"),
+            (&blocker, "state=BLOCKED"),
+        ] {
+            ws.update_item(&item.uid, &[SetOperation::parse(op).unwrap()])
+                .unwrap();
+        }
+
+        let pick = |ws: &Workspace| ws.next_item("alice", &states).unwrap().unwrap().uid;
+        assert_eq!(pick(&ws), urgent.uid);
+
+        // Blocked items wait, then the oldest of the rest goes first
+        ws.add_blocker(&urgent.uid, &blocker.uid).unwrap();
+        assert_eq!(pick(&ws), oldest.uid);
+    }
+
+    #[test]
+    fn test_external_id() {
+        let (_tmp, ws) = setup();
+        let login = ws.create_item("Fix login").unwrap();
+        let signup = ws.create_item("Fix signup").unwrap();
+        ws.reindex().unwrap();
+
+        let set = |uid: &str, value: &str| {
+            ws.update_item(uid, &[SetOperation::new("external_id", value)])
+        };
+        set(&login.uid, "JIRA-123").unwrap();
+        assert_eq!(ws.get_item("JIRA-123").unwrap().uid, login.uid);
+        assert_eq!(ws.get_item(&login.uid).unwrap().external_id.as_deref(), Some("JIRA-123"));
+        assert!(matches!(ws.get_item("JIRA-999"), Err(FsError::ItemNotFound(_))));
+
+        // Writes resolve the same way reads do
+        let item = ws.update_item("JIRA-123", &[SetOperation::new("assignee", "alice")]).unwrap();
+        assert_eq!(item.uid, login.uid);
+        assert_eq!(ws.resolve_uid("JIRA-123").unwrap(), login.uid);
+
+        // Unique across the workspace, whichever way it is set
+        assert!(set(&signup.uid, "JIRA-123").is_err());
+        let patch = serde_json::json!({ "external_id": "JIRA-123" });
+        assert!(ws.patch_item(&signup.uid, &patch).is_err());
+        set(&login.uid, "JIRA-123").unwrap();
+        let fields = NewItemFields {
+            external_id: Some("JIRA-123".to_string()),
+            ..NewItemFields::default()
+        };
+        assert!(ws.create_item_with("Fix logout", &fields).is_err());
+        assert!(matches!(ws.get_item("fs:fix-logout"), Err(FsError::ItemNotFound(_))));
+
+        // Ticket numbers stay strings, and a blank ID clears it
+        let number = SetOperation::parse("external_id=1234").unwrap();
+        let item = ws.update_item(&signup.uid, &[number]).unwrap();
+        assert_eq!(item.external_id.as_deref(), Some("1234"));
+        assert_eq!(set(&signup.uid, " ").unwrap().external_id, None);
+        assert!(matches!(ws.find_by_external_id("1234"), Err(FsError::ItemNotFound(_))));
+
+        // An ID written behind worky's back is found without a reindex
+        let meta = ws.item_dir("fix-signup").join(META_FILE);
+        let edited = fs::read_to_string(&meta).unwrap() + "external_id: OPS-7\n";
+        fs::write(&meta, edited).unwrap();
+        assert_eq!(ws.find_by_external_id("OPS-7").unwrap().uid, signup.uid);
+        ws.reindex().unwrap();
+        assert_eq!(ws.find_by_external_id("OPS-7").unwrap().uid, signup.uid);
+
+        // So is one whose index entry another process's write dropped
+        let index = fs::read(ws.index_path()).unwrap();
+        let fields = NewItemFields {
+            external_id: Some("OPS-8".to_string()),
+            ..NewItemFields::default()
+        };
+        let ops = ws.create_item_with("Page ops", &fields).unwrap();
+        fs::write(ws.index_path(), index).unwrap();
+        assert_eq!(ws.find_by_external_id("OPS-8").unwrap().uid, ops.uid);
+        assert!(ws.create_item_with("Page ops again", &fields).is_err());
+
+        // Without one, every item is looked at
+        fs::remove_file(ws.index_path()).unwrap();
+        assert_eq!(ws.find_by_external_id("JIRA-123").unwrap().uid, login.uid);
+    }
+
+    #[test]
+    fn test_resolve_uid() {
+        let (_tmp, ws) = setup();
+        ws.create_item("Implement auth").unwrap();
+        ws.create_item("Implement auth tokens").unwrap();
+        ws.create_item("Write docs").unwrap();
+
+        // Exact forms win, even when they are also a prefix of another slug
+        assert_eq!(ws.resolve_uid("fs:implement-auth").unwrap(), "fs:implement-auth");
+        assert_eq!(ws.resolve_uid("implement-auth").unwrap(), "fs:implement-auth");
+
+        // A unique prefix beats substrings, and case is ignored
+        assert_eq!(ws.resolve_uid("Write").unwrap(), "fs:write-docs");
+        assert_eq!(ws.resolve_uid("tokens").unwrap(), "fs:implement-auth-tokens");
+        assert_eq!(ws.get_item("docs").unwrap().uid, "fs:write-docs");
+
+        match ws.resolve_uid("impl") {
+            Err(FsError::AmbiguousUid { candidates, .. }) => assert_eq!(
+                candidates,
+                ["fs:implement-auth", "fs:implement-auth-tokens"]
+            ),
+            other => panic!("expected an ambiguous match, got {other:?}"),
+        }
+        assert!(matches!(ws.resolve_uid("deploy"), Err(FsError::ItemNotFound(_))));
+        assert!(matches!(ws.resolve_uid("../items"), Err(FsError::ItemNotFound(_))));
+
+        // Writes resolve the same way
+        let item = ws.update_item("docs", &[SetOperation::new("priority", "high")]).unwrap();
+        assert_eq!(item.uid, "fs:write-docs");
+        ws.add_comment("tokens", "Rotate daily").unwrap();
+        assert_eq!(ws.comments("fs:implement-auth-tokens").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_clone_item() {
+        let (_tmp, ws) = setup();
+
+        let source = ws.create_item("Fix login").unwrap();
+        let ops = [
+            "state=IN_PROGRESS",
+            "assignee=alice",
+            r#"labels=["bug"]"#,
+            "fields.estimate=3",
+        ]
+        .map(|op| SetOperation::parse(op).unwrap());
+        ws.update_item(&source.uid, &ops).unwrap();
+        ws.add_blocker(&source.uid, &ws.create_item("Other").unwrap().uid)
+            .unwrap();
+        let notes = ws.read_notes(&source.uid).unwrap().unwrap().text;
+        fs::write(
+            ws.item_dir("fix-login").join(NOTES_FILE),
+            format!("{notes}Steps to reproduce\n"),
+        )
+        .unwrap();
+
+        let copy = ws.clone_item(&source.uid, "Fix signup").unwrap();
+        assert_eq!(copy.uid, "fs:fix-signup");
+        assert_eq!(copy.state, "TODO");
+        assert_eq!(copy.assignee.as_deref(), Some("alice"));
+        assert_eq!(copy.labels, ["bug"]);
+        assert_eq!(copy.fields["estimate"], 3);
+        assert!(copy.blocked_by.is_empty());
+        assert_eq!(ws.get_item(&copy.uid).unwrap(), copy);
+
+        let notes = ws.read_notes(&copy.uid).unwrap().unwrap().text;
+        assert_eq!(notes, "# Fix signup\n\nSteps to reproduce\n");
+
+        // Only the creation is carried in the copy's history
+        let events = ws.read_events(&copy.uid, None, None, None).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, EventType::Created);
+        assert!(matches!(
+            &events[0].payload,
+            EventPayload::Created(p) if p.cloned_from.as_deref() == Some("fs:fix-login")
+        ));
+
+        assert!(matches!(
+            ws.clone_item("fs:missing", "Copy"),
+            Err(FsError::ItemNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_move_item_to_workspace() {
+        let (_tmp, ws) = setup();
+        let (_other_tmp, other) = setup();
+
+        let item = ws.create_item("Fix login").unwrap();
+        ws.update_item(&item.uid, &[SetOperation::parse("assignee=alice").unwrap()])
+            .unwrap();
+        ws.add_artifact_data(&item.uid, "log.txt", b"trace").unwrap();
+        other.create_item("Fix login").unwrap();
+
+        // The target has the slug already, so the item gets a suffix
+        let moved = ws.move_item_to_workspace("fix-login", &other).unwrap();
+        assert_eq!(moved.uid, "fs:fix-login-2");
+        assert_eq!(moved.assignee.as_deref(), Some("alice"));
+        assert_eq!(other.get_item(&moved.uid).unwrap(), moved);
+        assert!(matches!(ws.get_item(&item.uid), Err(FsError::ItemNotFound(_))));
+
+        let path = other.artifact_path(&moved.uid, "log.txt").unwrap();
+        assert_eq!(fs::read(path).unwrap(), b"trace");
+        assert!(other.read_notes(&moved.uid).unwrap().is_some());
+
+        // The history comes along, ending with where the item came from
+        let events = other.read_events(&moved.uid, None, None, None).unwrap();
+        assert_eq!(events[0].event_type, EventType::Created);
+        let last = events.last().unwrap();
+        assert_eq!(last.event_type, EventType::Transferred);
+        assert!(matches!(&last.payload, EventPayload::Generic(p) if p["uid"] == "fs:fix-login"));
+
+        // Nothing is left staged, and moving within a workspace is refused
+        assert!(!other.root.join(WORKY_DIR).join("transfer-fix-login-2").exists());
+        assert!(matches!(
+            other.move_item_to_workspace(&moved.uid, &other),
+            Err(FsError::Core(CoreError::Validation(_)))
+        ));
+        assert!(matches!(
+            ws.move_item_to_workspace("fs:missing", &other),
+            Err(FsError::ItemNotFound(_))
+        ));
+
+        // Nor is a move the target would hold invalid; the source keeps it
+        let ticket = ws.create_item("Sync tickets").unwrap();
+        ws.update_item(&ticket.uid, &[SetOperation::parse("external_id=JIRA-1").unwrap()])
+            .unwrap();
+        other
+            .update_item(&moved.uid, &[SetOperation::parse("external_id=JIRA-1").unwrap()])
+            .unwrap();
+        let paused = ws.create_item("Paused").unwrap();
+        ws.update_item(&paused.uid, &[SetOperation::new("state", "ON_HOLD")])
+            .unwrap();
+        for uid in [&ticket.uid, &paused.uid] {
+            assert!(matches!(
+                ws.move_item_to_workspace(uid, &other),
+                Err(FsError::Core(CoreError::Validation(_)))
+            ));
+            assert!(ws.get_item(uid).is_ok());
+        }
+        assert_eq!(other.list_items(None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_workload() {
+        let (_tmp, ws) = setup();
+
+        for (title, ops) in [
+            ("Later", vec!["priority=high", "fields.due=2025-03-01"]),
+            ("Sooner", vec!["priority=high", "fields.due=2025-02-01"]),
+            ("No due", vec!["priority=high"]),
+            ("Low", vec!["priority=low", "fields.due=2025-01-01"]),
+            ("Unprioritized", vec![]),
+            ("Finished", vec!["priority=critical", "state=DONE"]),
+        ] {
+            let item = ws.create_item(title).unwrap();
+            let mut ops: Vec<SetOperation> =
+                ops.iter().map(|op| SetOperation::parse(op).unwrap()).collect();
+            ops.push(SetOperation::assignee(Some("alice")));
+            ws.update_item(&item.uid, &ops).unwrap();
+        }
+        ws.create_item("Someone else's").unwrap();
+
+        let uids: Vec<String> = ws
+            .workload("Alice", &[])
+            .unwrap()
+            .into_iter()
+            .map(|item| item.uid)
+            .collect();
+        assert_eq!(
+            uids,
+            ["fs:sooner", "fs:later", "fs:no-due", "fs:low", "fs:unprioritized"]
+        );
+
+        assert!(ws.workload("alice", &["IN_REVIEW".to_string()]).unwrap().is_empty());
     }
 }
@@ -0,0 +1,77 @@
+//! `.workyignore`: item directories to leave out of listings.
+//!
+//! The file sits at the workspace root and uses gitignore syntax, matched
+//! against item slugs (the directory names under `work/items`). Ignored
+//! items are hidden from listings but can still be read by UID.
+
+use crate::error::{FsError, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+/// File name of the ignore file, relative to the workspace root.
+pub const IGNORE_FILE: &str = ".workyignore";
+
+/// Parsed `.workyignore` patterns.
+#[derive(Debug, Clone)]
+pub struct WorkyIgnore {
+    matcher: Gitignore,
+}
+
+impl Default for WorkyIgnore {
+    fn default() -> Self {
+        Self {
+            matcher: Gitignore::empty(),
+        }
+    }
+}
+
+impl WorkyIgnore {
+    /// Parse gitignore-style `content`, with patterns anchored at `items_dir`.
+    ///
+    /// Matching is case-sensitive; `!pattern` re-includes an item excluded by
+    /// an earlier line.
+    ///
+    /// # Errors
+    /// Returns `FsError::InvalidIgnorePattern` if a line is not a valid glob.
+    pub fn parse(items_dir: &Path, content: &str) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(items_dir);
+        for line in content.lines() {
+            builder
+                .add_line(None, line)
+                .map_err(|e| FsError::InvalidIgnorePattern(e.to_string()))?;
+        }
+
+        let matcher = builder
+            .build()
+            .map_err(|e| FsError::InvalidIgnorePattern(e.to_string()))?;
+        Ok(Self { matcher })
+    }
+
+    /// Whether the item directory `slug` is excluded.
+    #[must_use]
+    pub fn is_ignored(&self, slug: &str) -> bool {
+        self.matcher.matched(slug, true).is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_patterns() {
+        let content = "# scratch work\nscratch-*\n!scratch-keep\n/template\n";
+        let ignore = WorkyIgnore::parse(Path::new("/ws/work/items"), content).unwrap();
+
+        assert!(ignore.is_ignored("scratch-1"));
+        assert!(ignore.is_ignored("template"));
+        assert!(!ignore.is_ignored("scratch-keep"));
+        assert!(!ignore.is_ignored("fix-login"));
+
+        // Case-sensitive, like git on Linux
+        assert!(!ignore.is_ignored("Scratch-1"));
+
+        assert!(!WorkyIgnore::default().is_ignored("scratch-1"));
+        assert!(WorkyIgnore::parse(Path::new("/ws"), "scratch-{a").is_err());
+    }
+}
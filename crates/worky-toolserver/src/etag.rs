@@ -0,0 +1,118 @@
+//! Conditional requests.
+//!
+//! Responses carry an `ETag`; a request whose `If-None-Match` lists it gets
+//! an empty 304 instead of the body. Clients that don't send the header see
+//! no difference. Items are tagged with their version, which updates take
+//! back in `If-Match` to avoid overwriting changes made in the meantime.
+
+use crate::AppError;
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// `value` as JSON with a weak `ETag` hashed from the body, or 304 if
+/// `headers` show the client already has it.
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, value: &T) -> Response {
+    let respond = || {
+        let body = serde_json::to_vec(value)?;
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        respond(headers, &format!("W/\"{:016x}\"", hasher.finish()), body)
+    };
+    respond().unwrap_or_else(IntoResponse::into_response)
+}
+
+/// An item (or anything else with a version) as JSON with its `version`
+/// as a strong `ETag`, or 304 if `headers` show the client already has it.
+pub fn versioned_json<T: Serialize>(headers: &HeaderMap, version: &str, value: &T) -> Response {
+    serde_json::to_vec(value)
+        .map_err(AppError::from)
+        .and_then(|body| respond(headers, &version_tag(version), body))
+        .unwrap_or_else(IntoResponse::into_response)
+}
+
+/// `version` as an `ETag` value.
+pub fn version_tag(version: &str) -> String {
+    format!("\"{version}\"")
+}
+
+/// The version an update's `If-Match` header requires, if any. `*` (any
+/// version) counts as none.
+///
+/// # Errors
+/// Returns a 400 error if the header names more than one version or isn't
+/// a quoted tag.
+pub fn if_match(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let mut tags = Vec::new();
+    for value in headers.get_all(header::IF_MATCH) {
+        let value = value
+            .to_str()
+            .map_err(|_| AppError::bad_request("If-Match is not valid text"))?;
+        tags.extend(value.split(',').map(str::trim).filter(|tag| !tag.is_empty()));
+    }
+
+    match tags.as_slice() {
+        [] => Ok(None),
+        tags if tags.contains(&"*") => Ok(None),
+        [tag] => tag
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .map(|version| Some(version.to_string()))
+            .ok_or_else(|| {
+                AppError::bad_request("If-Match must be a quoted ETag from GET /items/{uid}")
+            }),
+        _ => Err(AppError::bad_request("If-Match must name a single version")),
+    }
+}
+
+fn respond(headers: &HeaderMap, tag: &str, body: Vec<u8>) -> Result<Response, AppError> {
+    // Always revalidate, so pollers see changes as soon as they happen
+    let cache_headers = [
+        (header::ETAG, HeaderValue::from_str(tag)?),
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+    ];
+
+    if headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| matches(value, tag))
+    {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+        body,
+    )
+        .into_response())
+}
+
+/// Whether the `If-None-Match` list `value` names `tag`, compared weakly
+/// (RFC 9110 §13.1.2).
+fn matches(value: &str, tag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    value
+        .split(',')
+        .map(opaque)
+        .any(|candidate| candidate == "*" || candidate == opaque(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("\"abc\"", "\"abc\""));
+        assert!(matches("W/\"abc\"", "\"abc\""));
+        assert!(matches("\"x\", W/\"abc\"", "W/\"abc\""));
+        assert!(matches("*", "\"abc\""));
+        assert!(!matches("\"abcd\"", "\"abc\""));
+    }
+}
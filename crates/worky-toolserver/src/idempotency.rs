@@ -0,0 +1,321 @@
+//! Safe retries for mutating routes via the `Idempotency-Key` header.
+//!
+//! The first request with a key runs as usual and its response is kept for
+//! the configured window; a retry with the same key and body gets that
+//! response back instead of applying the change again. Keys live in memory
+//! only, so a restarted server treats every key as new.
+
+use crate::{AppError, AppState};
+use axum::{
+    body::{self, Body, Bytes},
+    extract::{Request, State},
+    http::{header::HeaderName, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Request header carrying the client's key.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Response header set on replayed responses.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Default time a response is kept for replay.
+pub const DEFAULT_IDEMPOTENCY_WINDOW: Duration = Duration::from_mins(10);
+
+/// Number of keys kept; the least recently used is dropped beyond this.
+const CAPACITY: usize = 1024;
+/// Longest accepted key, as with common payment APIs.
+const MAX_KEY_LEN: usize = 255;
+/// Largest body buffered to fingerprint a request; artifact uploads are
+/// the biggest.
+const MAX_BUFFERED_BYTES: usize = crate::MAX_ARTIFACT_BYTES;
+
+/// Recently seen keys and their responses, kept in [`AppState`].
+pub struct IdempotencyCache {
+    window: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    /// Hash of the method, path, and body the key was first used with.
+    fingerprint: u64,
+    /// `None` while the first request is still running.
+    response: Option<CachedResponse>,
+    /// When the response was stored, or the first request started.
+    stored_at: Instant,
+    used_at: Instant,
+}
+
+#[derive(Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    fn replay(self) -> Response {
+        let mut response = (self.status, self.headers, self.body).into_response();
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+/// What to do with a request carrying a key.
+enum Lookup {
+    /// First use: run it and store the response.
+    Run,
+    Replay(CachedResponse),
+    InFlight,
+    Mismatch,
+}
+
+impl IdempotencyCache {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lookup(&self, key: &str, fingerprint: u64, now: Instant) -> Lookup {
+        let mut entries = self.lock();
+        // A request still running after a whole window has most likely hung;
+        // its key is freed so retries aren't refused forever
+        let window = self.window;
+        entries.retain(|_, e| now.duration_since(e.stored_at) < window);
+
+        if let Some(entry) = entries.get_mut(key) {
+            entry.used_at = now;
+            return match (&entry.response, entry.fingerprint == fingerprint) {
+                (_, false) => Lookup::Mismatch,
+                (None, true) => Lookup::InFlight,
+                (Some(response), true) => Lookup::Replay(response.clone()),
+            };
+        }
+
+        if entries.len() >= CAPACITY
+            && let Some(oldest) = entries
+                .iter()
+                .filter(|(_, e)| e.response.is_some())
+                .min_by_key(|(_, e)| e.used_at)
+                .map(|(k, _)| k.clone())
+        {
+            entries.remove(&oldest);
+        }
+        entries.insert(
+            key.to_string(),
+            Entry {
+                fingerprint,
+                response: None,
+                stored_at: now,
+                used_at: now,
+            },
+        );
+        drop(entries);
+        Lookup::Run
+    }
+
+    /// Hold `key` as in flight until the returned guard is finished or
+    /// dropped.
+    const fn pending(&self, key: String) -> Pending<'_> {
+        Pending {
+            cache: self,
+            key: Some(key),
+        }
+    }
+
+    /// Keep `response` for `key`, or forget the key so a retry runs again.
+    fn finish(&self, key: &str, response: Option<CachedResponse>) {
+        let mut entries = self.lock();
+        match response {
+            Some(response) => {
+                if let Some(entry) = entries.get_mut(key) {
+                    entry.stored_at = Instant::now();
+                    entry.response = Some(response);
+                }
+            }
+            None => {
+                entries.remove(key);
+            }
+        }
+    }
+}
+
+/// A key whose first request is running. Dropping it without
+/// [`Pending::finish`], e.g. when the client disconnects and the handler is
+/// cancelled, forgets the key so a retry runs again.
+struct Pending<'a> {
+    cache: &'a IdempotencyCache,
+    key: Option<String>,
+}
+
+impl Pending<'_> {
+    fn finish(mut self, response: Option<CachedResponse>) {
+        if let Some(key) = self.key.take() {
+            self.cache.finish(&key, response);
+        }
+    }
+}
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.cache.finish(&key, None);
+        }
+    }
+}
+
+/// Replay the stored response for a repeated `Idempotency-Key`.
+///
+/// Requests without the header pass straight through. A key reused with a
+/// different request is rejected with 422, and one whose first request is
+/// still running with 409. Server errors aren't kept, so they can be
+/// retried with the same key.
+pub async fn replay(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => key.to_string(),
+        _ => {
+            return AppError::bad_request(format!(
+                "Idempotency-Key must be 1 to {MAX_KEY_LEN} visible ASCII characters"
+            ))
+            .into_response();
+        }
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = body::to_bytes(body, MAX_BUFFERED_BYTES).await else {
+        return AppError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            "request body is too large",
+        )
+        .into_response();
+    };
+
+    let mut hasher = DefaultHasher::new();
+    parts.method.hash(&mut hasher);
+    parts.uri.path().hash(&mut hasher);
+    body.hash(&mut hasher);
+    let fingerprint = hasher.finish();
+
+    let cache = &state.idempotency;
+    let pending = match cache.lookup(&key, fingerprint, Instant::now()) {
+        Lookup::Run => cache.pending(key),
+        Lookup::Replay(response) => return response.replay(),
+        Lookup::InFlight => {
+            return AppError::new(
+                StatusCode::CONFLICT,
+                "idempotency_key_in_use",
+                "a request with this Idempotency-Key is still being processed; retry shortly",
+            )
+            .into_response();
+        }
+        Lookup::Mismatch => {
+            return AppError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "idempotency_key_reused",
+                "this Idempotency-Key was already used for a different request",
+            )
+            .into_response();
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        pending.finish(None);
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    match body::to_bytes(body, usize::MAX).await {
+        Ok(body) => {
+            let cached = CachedResponse {
+                status: parts.status,
+                headers: parts.headers.clone(),
+                body: body.clone(),
+            };
+            pending.finish(Some(cached));
+            Response::from_parts(parts, Body::from(body))
+        }
+        Err(e) => {
+            pending.finish(None);
+            AppError::from(anyhow::anyhow!("failed to read response body: {e}")).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_mins(1);
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::CREATED,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_replay_and_mismatch() {
+        let cache = IdempotencyCache::new(WINDOW);
+        let now = Instant::now();
+
+        assert!(matches!(cache.lookup("k", 1, now), Lookup::Run));
+        assert!(matches!(cache.lookup("k", 1, now), Lookup::InFlight));
+        cache.finish("k", Some(response("done")));
+
+        match cache.lookup("k", 1, now) {
+            Lookup::Replay(cached) => assert_eq!(cached.body, "done"),
+            _ => panic!("expected a replay"),
+        }
+        assert!(matches!(cache.lookup("k", 2, now), Lookup::Mismatch));
+    }
+
+    #[test]
+    fn test_pending_guard() {
+        let cache = IdempotencyCache::new(WINDOW);
+        let now = Instant::now();
+
+        // A cancelled request frees its key
+        assert!(matches!(cache.lookup("k", 1, now), Lookup::Run));
+        drop(cache.pending("k".to_string()));
+        assert!(matches!(cache.lookup("k", 1, now), Lookup::Run));
+
+        cache.pending("k".to_string()).finish(Some(response("done")));
+        assert!(matches!(cache.lookup("k", 1, now), Lookup::Replay(_)));
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = IdempotencyCache::new(WINDOW);
+        let now = Instant::now();
+
+        // In flight for a whole window: assumed hung, so the key runs again
+        assert!(matches!(cache.lookup("hung", 1, now), Lookup::Run));
+        assert!(matches!(cache.lookup("hung", 1, now + WINDOW / 2), Lookup::InFlight));
+        assert!(matches!(cache.lookup("hung", 1, now + WINDOW), Lookup::Run));
+
+        assert!(matches!(cache.lookup("done", 1, now), Lookup::Run));
+        cache.finish("done", Some(response("done")));
+        let later = Instant::now() + WINDOW;
+        assert!(matches!(cache.lookup("done", 1, later), Lookup::Run));
+    }
+}
@@ -3,80 +3,349 @@
 //! Provides a local HTTP API that Claude and other AI tools can use
 //! to interact with worky workspaces.
 
-use anyhow::Result;
+mod etag;
+mod idempotency;
+mod limit;
+mod metrics;
+#[cfg(feature = "openapi")]
+mod openapi;
+mod request_id;
+
+pub use etag::conditional_json;
+pub use idempotency::DEFAULT_IDEMPOTENCY_WINDOW;
+pub use limit::{Limits, DEFAULT_MAX_BODY_BYTES, DEFAULT_REQUESTS_PER_SECOND};
+
+use anyhow::{anyhow, Result};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{
+        multipart::MultipartError, rejection::QueryRejection, DefaultBodyLimit, Multipart, Path,
+        Query, State,
+    },
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
+use idempotency::IdempotencyCache;
+use limit::RateLimiter;
+use metrics::Metrics;
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::trace::TraceLayer;
-use tracing::info;
-use worky_core::{SetOperation, WorkEvent, WorkItem};
-use worky_fs::{workspace::ItemFilter, Workspace};
+use tracing::{info, warn};
+use worky_core::{CoreError, EventType, SetOperation, WorkEvent, WorkItem};
+use worky_fs::{
+    actor,
+    workspace::{FilterQuery, ItemFilter, NewItemFields},
+    AiAudit, Artifact, FsError, Workspace,
+};
+
+/// Largest accepted artifact upload.
+const MAX_ARTIFACT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Responses smaller than this aren't worth compressing.
+const MIN_COMPRESSED_BYTES: u16 = 1024;
 
 /// Server state shared across handlers.
 struct AppState {
     workspace_path: PathBuf,
+    /// Request counters, if `/metrics` is enabled.
+    metrics: Option<Metrics>,
+    /// Responses kept for replay by `Idempotency-Key`.
+    idempotency: IdempotencyCache,
+}
+
+/// Start the tool server, with `GET /metrics` if `metrics` is set.
+///
+/// Browsers on `cors_origins` (e.g. `http://localhost:3000`, or `*` for
+/// any) may call the API; with none, no CORS headers are sent.
+///
+/// # Errors
+/// Returns error if an origin is invalid, binding fails, or the server
+/// encounters an error.
+pub async fn serve(
+    workspace_path: &std::path::Path,
+    host: &str,
+    port: u16,
+    limits: Limits,
+    metrics: bool,
+    cors_origins: &[String],
+) -> Result<()> {
+    let cors = cors_layer(cors_origins)?;
+    let listener = bind(host, port).await?;
+    info!(
+        address = %format!("{host}:{port}"),
+        requests_per_second = limits.requests_per_second,
+        max_body_bytes = limits.max_body_bytes,
+        cors_origins = ?cors_origins,
+        "Starting tool server"
+    );
+
+    serve_on(listener, workspace_path, limits, metrics, cors).await
 }
 
-/// Start the tool server.
+/// Run the tool server on an already bound listener until Ctrl+C.
+///
+/// Useful for binding port 0 and reading the chosen address first. See
+/// [`cors_layer`] for `cors`.
 ///
 /// # Errors
-/// Returns error if binding fails or server encounters an error.
-pub async fn serve(workspace_path: &std::path::Path, host: &str, port: u16) -> Result<()> {
+/// Returns error if the server encounters an error.
+pub async fn serve_on(
+    listener: TcpListener,
+    workspace_path: &std::path::Path,
+    limits: Limits,
+    metrics: bool,
+    cors: Option<CorsLayer>,
+) -> Result<()> {
+    let app = router(workspace_path, limits, metrics, cors);
+
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
+
+    info!("Tool server stopped");
+    Ok(())
+}
+
+/// The tool server's routes and middleware; see [`serve_on`].
+fn router(
+    workspace_path: &std::path::Path,
+    limits: Limits,
+    metrics: bool,
+    cors: Option<CorsLayer>,
+) -> Router {
     let state = Arc::new(AppState {
         workspace_path: workspace_path.to_path_buf(),
+        metrics: metrics.then(Metrics::default),
+        idempotency: IdempotencyCache::new(limits.idempotency_window),
     });
 
-    let app = Router::new()
+    // Mutating routes replay their response for a repeated Idempotency-Key
+    let idempotent = || middleware::from_fn_with_state(state.clone(), idempotency::replay);
+
+    let mut routes = Router::new()
         .route("/health", get(health))
+        .route("/ready", get(ready))
         .route("/search", post(search))
+        .route("/events", get(recent_events))
+        .route("/audit", get(audit_log))
+        .route("/calendar.ics", get(calendar))
+        .route("/items", get(workload))
+        .route("/items", post(create_item).route_layer(idempotent()))
         .route("/items/{uid}", get(get_item))
-        .route("/items/{uid}/set", post(set_fields))
-        .route("/items/{uid}/events", get(get_events).post(add_event))
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+        .route("/items/{uid}/set", post(set_fields).route_layer(idempotent()))
+        .route("/items/{uid}/events", get(get_events))
+        .route("/items/{uid}/events", post(add_event).route_layer(idempotent()))
+        .route(
+            "/items/{uid}/artifacts",
+            post(add_artifact)
+                .route_layer(idempotent())
+                .layer(DefaultBodyLimit::max(MAX_ARTIFACT_BYTES)),
+        );
 
-    let addr = format!("{host}:{port}");
-    info!(address = %addr, "Starting tool server");
+    // Registered after the other routes so the layer counts them too
+    let track = || middleware::from_fn_with_state(state.clone(), metrics::track);
+    if metrics {
+        routes = routes.route_layer(track());
+    }
 
-    let listener = TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    let mut app = routes
+        .layer(middleware::from_fn(limit::payload_too_large_as_json))
+        .layer(DefaultBodyLimit::max(limits.max_body_bytes))
+        .with_state(state.clone());
 
-    Ok(())
+    #[cfg(feature = "openapi")]
+    {
+        app = app.route("/openapi.json", get(openapi_document));
+    }
+
+    if limits.requests_per_second > 0 {
+        let limiter = Arc::new(RateLimiter::new(limits.requests_per_second));
+        app = app.layer(middleware::from_fn_with_state(limiter, limit::rate_limit));
+    }
+
+    // Outside the rate limit, so a busy client can't hide the metrics from
+    // the scraper. Like `/health`, `/metrics` needs no credentials.
+    if metrics {
+        app = app.route(
+            "/metrics",
+            get(metrics::handler).route_layer(track()).with_state(state),
+        );
+    }
+
+    app = app.layer(compression_layer());
+
+    // Outside the rate limit, so preflights aren't counted and 429s still
+    // carry the headers a browser needs to read them
+    if let Some(cors) = cors {
+        app = app.layer(cors);
+    }
+
+    app.layer(
+        TraceLayer::new_for_http()
+            .make_span_with(request_id::make_span)
+            .on_response(request_id::on_response),
+    )
+    .layer(middleware::from_fn(request_id::propagate))
+}
+
+/// CORS for browser-based tools on `origins`, or `None` if there are none.
+///
+/// `*` allows any origin. Preflight `OPTIONS` requests are answered by the
+/// layer without reaching the routes.
+///
+/// # Errors
+/// Returns error if an origin is not of the form `scheme://host[:port]`.
+pub fn cors_layer(origins: &[String]) -> Result<Option<CorsLayer>> {
+    if origins.is_empty() {
+        return Ok(None);
+    }
+
+    let allow_origin = if origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins = origins
+            .iter()
+            .map(|origin| parse_origin(origin))
+            .collect::<Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                header::IF_NONE_MATCH,
+                header::IF_MATCH,
+                idempotency::IDEMPOTENCY_KEY,
+                request_id::REQUEST_ID,
+            ])
+            .expose_headers([
+                header::RETRY_AFTER,
+                header::ETAG,
+                idempotency::IDEMPOTENT_REPLAYED,
+                request_id::REQUEST_ID,
+            ])
+            .max_age(Duration::from_hours(1)),
+    ))
+}
+
+/// `origin` as a header value, if it looks like `scheme://host[:port]`.
+fn parse_origin(origin: &str) -> Result<HeaderValue> {
+    let valid = origin.split_once("://").is_some_and(|(scheme, host)| {
+        !scheme.is_empty() && !host.is_empty() && !host.contains('/')
+    });
+
+    valid
+        .then(|| HeaderValue::from_str(origin).ok())
+        .flatten()
+        .ok_or_else(|| {
+            anyhow!("invalid CORS origin '{origin}'; expected e.g. http://localhost:3000")
+        })
+}
+
+/// Gzip or deflate responses for clients that send `Accept-Encoding`.
+///
+/// Small bodies (e.g. `/health`), images, and event streams are sent as-is.
+/// The board uses this too.
+#[must_use]
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    let predicate = SizeAbove::new(MIN_COMPRESSED_BYTES)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE);
+
+    CompressionLayer::new().compress_when(predicate)
+}
+
+/// Bind a TCP listener, turning `AddrInUse` into an actionable error.
+///
+/// # Errors
+/// Returns error if the address is already in use or cannot be bound.
+pub async fn bind(host: &str, port: u16) -> Result<TcpListener> {
+    let addr = format!("{host}:{port}");
+    match TcpListener::bind(&addr).await {
+        Ok(listener) => Ok(listener),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(anyhow!(
+            "port {port} is already in use on {host}; pass a different --port"
+        )),
+        Err(e) => Err(anyhow::Error::new(e).context(format!("failed to bind {addr}"))),
+    }
+}
+
+/// Resolve once the process receives Ctrl+C.
+pub async fn shutdown_signal() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        // Without a signal handler, keep serving rather than stopping immediately
+        warn!(error = %e, "Failed to install Ctrl+C handler");
+        std::future::pending::<()>().await;
+    }
 }
 
 // --- Request/Response types ---
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 struct HealthResponse {
     status: &'static str,
     version: &'static str,
 }
 
-#[derive(Debug, Deserialize)]
-struct SearchRequest {
-    #[serde(default)]
-    state: Option<String>,
-    #[serde(default)]
-    assignee: Option<String>,
-    #[serde(default)]
-    label: Option<String>,
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct ReadyResponse {
+    /// `ready`, or `unavailable` if the workspace can't be read
+    status: &'static str,
+    version: &'static str,
+    /// Workspace name from its config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    items: Option<usize>,
+    /// Why the workspace can't be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 struct SearchResponse {
     items: Vec<WorkItem>,
     count: usize,
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct CreateItemRequest {
+    title: String,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 struct SetFieldsRequest {
     /// Field assignments as key=value pairs
     assignments: Vec<String>,
@@ -86,6 +355,57 @@ struct SetFieldsRequest {
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct EventsQuery {
+    /// Comma-separated event types to include.
+    #[serde(rename = "type")]
+    types: Option<String>,
+    /// Only events at or after this RFC 3339 timestamp.
+    since: Option<DateTime<Utc>>,
+    /// Only events before this RFC 3339 timestamp.
+    until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct FeedQuery {
+    /// Only events at or after this RFC 3339 timestamp.
+    since: Option<DateTime<Utc>>,
+    /// Maximum number of events to return.
+    #[serde(default = "default_feed_limit")]
+    limit: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct AuditQuery {
+    /// Only entries at or after this RFC 3339 timestamp.
+    since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct WorkloadQuery {
+    /// Whose open items to list; `me` is the user running the server.
+    assignee: String,
+    /// Comma-separated states to narrow to.
+    state: Option<String>,
+}
+
+const fn default_feed_limit() -> usize {
+    50
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+struct FeedEntry {
+    uid: String,
+    event: WorkEvent,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[allow(dead_code)]
 struct AddEventRequest {
     /// Event type
     #[serde(rename = "type")]
@@ -98,8 +418,18 @@ struct AddEventRequest {
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 struct ErrorResponse {
+    /// Stable machine-readable error code (e.g. `item_not_found`)
+    code: &'static str,
+    /// Human-readable message
     error: String,
+    /// The request's `X-Request-Id`, for matching the error to server logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    /// For `version_mismatch`, the item as it is now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item: Option<WorkItem>,
 }
 
 // --- Handlers ---
@@ -111,43 +441,113 @@ async fn health() -> Json<HealthResponse> {
     })
 }
 
+/// Readiness: 200 once the workspace can be opened and listed, else 503.
+async fn ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadyResponse>) {
+    let checked = Workspace::open(&state.workspace_path).and_then(|ws| {
+        let items = ws.list_items(None)?.len();
+        Ok((ws.config().workspace.name.clone(), items))
+    });
+
+    match checked {
+        Ok((workspace, items)) => (
+            StatusCode::OK,
+            Json(ReadyResponse {
+                status: "ready",
+                version: env!("CARGO_PKG_VERSION"),
+                workspace,
+                items: Some(items),
+                error: None,
+            }),
+        ),
+        Err(e) => {
+            warn!(error = %e, "Workspace not ready");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ReadyResponse {
+                    status: "unavailable",
+                    version: env!("CARGO_PKG_VERSION"),
+                    workspace: None,
+                    items: None,
+                    error: Some(e.to_string()),
+                }),
+            )
+        }
+    }
+}
+
+#[cfg(feature = "openapi")]
+async fn openapi_document() -> Json<serde_json::Value> {
+    Json(openapi::document())
+}
+
 async fn search(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<SearchRequest>,
+    Json(query): Json<FilterQuery>,
 ) -> Result<Json<SearchResponse>, AppError> {
     let ws = Workspace::open(&state.workspace_path)?;
 
-    let filter = if req.state.is_some() || req.assignee.is_some() || req.label.is_some() {
-        Some(ItemFilter {
-            state: req.state,
-            assignee: req.assignee,
-            label: req.label,
-        })
-    } else {
-        None
-    };
-
-    let items = ws.list_items(filter.as_ref())?;
+    let filter = ItemFilter::try_from(query)?;
+    let items = ws.list_items(Some(&filter))?;
     let count = items.len();
 
     Ok(Json(SearchResponse { items, count }))
 }
 
+/// Open the workspace, attributing changes to `actor` if the request
+/// names one and to the server's own actor otherwise.
+fn open_as(state: &AppState, actor: Option<&str>) -> Result<Workspace, AppError> {
+    let ws = Workspace::open(&state.workspace_path)?;
+    Ok(ws.with_actor(actor::resolve(actor).name))
+}
+
+async fn create_item(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreateItemRequest>,
+) -> Result<(StatusCode, Json<WorkItem>), AppError> {
+    if req.title.trim().is_empty() {
+        return Err(AppError::bad_request("title must not be empty"));
+    }
+
+    let ws = Workspace::open(&state.workspace_path)?;
+
+    let fields = NewItemFields {
+        state: req.state,
+        assignee: req.assignee,
+        labels: req.labels,
+        description: req.description,
+        ..NewItemFields::default()
+    };
+
+    let item = ws.create_item_with(req.title.trim(), &fields)?;
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+/// Items with a due date as an iCalendar feed, for calendar subscriptions.
+async fn calendar(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let ws = Workspace::open(&state.workspace_path)?;
+    let calendar = ws.calendar()?;
+    Ok(([(header::CONTENT_TYPE, "text/calendar; charset=utf-8")], calendar))
+}
+
 async fn get_item(
     State(state): State<Arc<AppState>>,
     Path(uid): Path<String>,
-) -> Result<Json<WorkItem>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let ws = Workspace::open(&state.workspace_path)?;
     let item = ws.get_item(&uid)?;
-    Ok(Json(item))
+    let version = ws.item_version(&item.uid)?;
+    Ok(etag::versioned_json(&headers, &version, &item))
 }
 
 async fn set_fields(
     State(state): State<Arc<AppState>>,
     Path(uid): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<SetFieldsRequest>,
-) -> Result<Json<WorkItem>, AppError> {
-    let ws = Workspace::open(&state.workspace_path)?;
+) -> Result<Response, AppError> {
+    let ws = open_as(&state, req.actor.as_deref())?;
+    let expected_version = etag::if_match(&headers)?;
 
     // Parse assignments
     let operations: Vec<SetOperation> = req
@@ -156,34 +556,113 @@ async fn set_fields(
         .map(|a| SetOperation::parse(a))
         .collect::<worky_core::Result<Vec<_>>>()?;
 
-    // Update item
-    let item = ws.update_item(&uid, &operations)?;
-
-    // Log AI action if actor specified
-    if let Some(actor) = req.actor {
-        let event = WorkEvent::ai_action("worky-toolserver", "set_fields").with_actor(actor);
-        let slug = uid.strip_prefix("fs:").unwrap_or(&uid);
-        ws.append_event(slug, &event)?;
-    }
+    // Changes made on behalf of an actor are logged as AI actions
+    let item = match req.actor {
+        Some(actor) => {
+            let audit = AiAudit::new("worky-toolserver")
+                .with_actor(actor)
+                .with_details(serde_json::json!({
+                    "assignments": req.assignments,
+                    "request_id": request_id::current(),
+                }));
+            ws.audited(audit)
+                .update_item_if(&uid, &operations, expected_version.as_deref())?
+        }
+        None => ws.update_item_if(&uid, &operations, expected_version.as_deref())?,
+    };
 
-    Ok(Json(item))
+    let version = ws.item_version(&item.uid)?;
+    Ok(etag::versioned_json(&HeaderMap::new(), &version, &item))
 }
 
 async fn get_events(
     State(state): State<Arc<AppState>>,
     Path(uid): Path<String>,
+    query: Result<Query<EventsQuery>, QueryRejection>,
 ) -> Result<Json<Vec<WorkEvent>>, AppError> {
+    let Query(query) = query.map_err(|e| AppError::query(&e))?;
+    let types = query
+        .types
+        .as_deref()
+        .map(|list| {
+            list.split(',')
+                .map(str::parse)
+                .collect::<worky_core::Result<Vec<EventType>>>()
+        })
+        .transpose()
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
     let ws = Workspace::open(&state.workspace_path)?;
-    let events = ws.read_events(&uid, None)?;
+    let events = ws.read_events(&uid, query.since, query.until, types.as_deref())?;
     Ok(Json(events))
 }
 
+/// An assignee's open items, highest priority and earliest due first.
+async fn workload(
+    State(state): State<Arc<AppState>>,
+    query: Result<Query<WorkloadQuery>, QueryRejection>,
+) -> Result<Json<SearchResponse>, AppError> {
+    let Query(query) = query.map_err(|e| AppError::query(&e))?;
+    let assignee = if query.assignee.eq_ignore_ascii_case("me") {
+        actor::current()
+    } else {
+        query.assignee
+    };
+    let states: Vec<String> = query
+        .state
+        .as_deref()
+        .map(|list| list.split(',').map(|s| s.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    let ws = Workspace::open(&state.workspace_path)?;
+    let items = ws.workload(&assignee, &states)?;
+    let count = items.len();
+    Ok(Json(SearchResponse { items, count }))
+}
+
+/// Recent events across all items, newest first.
+async fn recent_events(
+    State(state): State<Arc<AppState>>,
+    query: Result<Query<FeedQuery>, QueryRejection>,
+) -> Result<Json<Vec<FeedEntry>>, AppError> {
+    let Query(query) = query.map_err(|e| AppError::query(&e))?;
+    let ws = Workspace::open(&state.workspace_path)?;
+
+    let entries = ws
+        .recent_events(query.since, query.limit)?
+        .into_iter()
+        .map(|(uid, event)| FeedEntry { uid, event })
+        .collect();
+
+    Ok(Json(entries))
+}
+
+/// The workspace audit log, oldest first.
+async fn audit_log(
+    State(state): State<Arc<AppState>>,
+    query: Result<Query<AuditQuery>, QueryRejection>,
+) -> Result<Json<Vec<FeedEntry>>, AppError> {
+    let Query(query) = query.map_err(|e| AppError::query(&e))?;
+    let ws = Workspace::open(&state.workspace_path)?;
+
+    let entries = ws
+        .read_audit(query.since)?
+        .into_iter()
+        .map(|entry| FeedEntry {
+            uid: entry.uid,
+            event: entry.event,
+        })
+        .collect();
+
+    Ok(Json(entries))
+}
+
 async fn add_event(
     State(state): State<Arc<AppState>>,
     Path(uid): Path<String>,
     Json(req): Json<AddEventRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let ws = Workspace::open(&state.workspace_path)?;
+    let ws = open_as(&state, req.actor.as_deref())?;
 
     // Validate item exists
     ws.get_item(&uid)?;
@@ -197,23 +676,108 @@ async fn add_event(
     })))
 }
 
+/// Store every file part of a multipart upload as an artifact.
+async fn add_artifact(
+    State(state): State<Arc<AppState>>,
+    Path(uid): Path<String>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Vec<Artifact>>), AppError> {
+    let ws = Workspace::open(&state.workspace_path)?;
+    let mut stored = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::multipart(&e))?
+    {
+        let Some(name) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        let data = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::multipart(&e))?;
+
+        let path = ws.add_artifact_data(&uid, &name, &data)?;
+        stored.push(Artifact {
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or(name),
+            size: data.len() as u64,
+        });
+    }
+
+    if stored.is_empty() {
+        return Err(AppError::bad_request("no file parts in upload"));
+    }
+
+    Ok((StatusCode::CREATED, Json(stored)))
+}
+
 // --- Error handling ---
 
-struct AppError(anyhow::Error);
+struct AppError {
+    status: StatusCode,
+    code: &'static str,
+    error: anyhow::Error,
+}
+
+impl AppError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            error: anyhow!(message.into()),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    /// Unparseable query strings, such as a bad `since` timestamp.
+    fn query(rejection: &QueryRejection) -> Self {
+        Self::new(rejection.status(), "bad_request", rejection.body_text())
+    }
+
+    /// Malformed uploads are 400; oversized ones keep their 413.
+    fn multipart(error: &MultipartError) -> Self {
+        let status = error.status();
+        let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+            "payload_too_large"
+        } else {
+            "bad_request"
+        };
+        Self::new(status, code, error.body_text())
+    }
+}
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        let status = if self.0.to_string().contains("not found") {
-            StatusCode::NOT_FOUND
-        } else {
-            StatusCode::INTERNAL_SERVER_ERROR
+        // A rejected update carries the current item and its version, so
+        // the client can merge and retry without another request
+        let current = match self.error.downcast_ref::<FsError>() {
+            Some(FsError::VersionMismatch {
+                version, current, ..
+            }) => Some((version.clone(), (**current).clone())),
+            _ => None,
         };
+        let (version, item) = current.unzip();
 
         let body = Json(ErrorResponse {
-            error: self.0.to_string(),
+            code: self.code,
+            error: self.error.to_string(),
+            request_id: request_id::current(),
+            item,
         });
 
-        (status, body).into_response()
+        let mut response = (self.status, body).into_response();
+        let tag = version.and_then(|v| HeaderValue::from_str(&etag::version_tag(&v)).ok());
+        if let Some(tag) = tag {
+            response.headers_mut().insert(header::ETAG, tag);
+        }
+        response
     }
 }
 
@@ -222,6 +786,297 @@ where
     E: Into<anyhow::Error>,
 {
     fn from(err: E) -> Self {
-        Self(err.into())
+        let error = err.into();
+
+        let (status, code) = match (
+            error.downcast_ref::<FsError>(),
+            error.downcast_ref::<CoreError>(),
+        ) {
+            (Some(fs_error), _) => classify_fs_error(fs_error),
+            (None, Some(core_error)) => classify_core_error(core_error),
+            (None, None) => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        };
+
+        Self {
+            status,
+            code,
+            error,
+        }
+    }
+}
+
+const fn classify_fs_error(error: &FsError) -> (StatusCode, &'static str) {
+    match error {
+        FsError::ItemNotFound(_) => (StatusCode::NOT_FOUND, "item_not_found"),
+        FsError::ItemExists(_) => (StatusCode::CONFLICT, "item_exists"),
+        FsError::InvalidUid(_) => (StatusCode::BAD_REQUEST, "invalid_uid"),
+        FsError::AmbiguousUid { .. } => (StatusCode::BAD_REQUEST, "ambiguous_uid"),
+        FsError::InvalidSlug(_) => (StatusCode::BAD_REQUEST, "invalid_slug"),
+        FsError::DependencyCycle(_) => (StatusCode::CONFLICT, "dependency_cycle"),
+        FsError::NotReversible(_) => (StatusCode::CONFLICT, "not_reversible"),
+        FsError::VersionMismatch { .. } => (StatusCode::CONFLICT, "version_mismatch"),
+        FsError::WipLimitReached { .. } => (StatusCode::CONFLICT, "wip_limit_reached"),
+        FsError::TemplateNotFound { .. } => (StatusCode::NOT_FOUND, "template_not_found"),
+        FsError::ArtifactNotFound(_) => (StatusCode::NOT_FOUND, "artifact_not_found"),
+        FsError::CommentNotFound(_) => (StatusCode::NOT_FOUND, "comment_not_found"),
+        FsError::InvalidArtifactName(_) => (StatusCode::BAD_REQUEST, "invalid_artifact_name"),
+        FsError::Core(core_error) => classify_core_error(core_error),
+        FsError::WorkspaceNotFound(_)
+        | FsError::WorkspaceExists(_)
+        | FsError::NewerConfig { .. }
+        | FsError::Io(_)
+        | FsError::Yaml(_)
+        | FsError::Json(_)
+        | FsError::InvalidIgnorePattern(_)
+        | FsError::NotUtf8 { .. } => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+    }
+}
+
+const fn classify_core_error(error: &CoreError) -> (StatusCode, &'static str) {
+    match error {
+        CoreError::Validation(_) => (StatusCode::BAD_REQUEST, "validation_failed"),
+        CoreError::InvalidPath(_) | CoreError::FieldNotFound(_) => {
+            (StatusCode::BAD_REQUEST, "invalid_path")
+        }
+        CoreError::InvalidStateTransition { .. } => {
+            (StatusCode::BAD_REQUEST, "invalid_state_transition")
+        }
+        CoreError::Json(_) | CoreError::Internal(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "internal")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::extract::ConnectInfo;
+    use axum::http::Request;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    /// A router over a new workspace with one item, `fs:fix-login`.
+    fn app(limits: Limits, metrics: bool) -> (TempDir, Router) {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        ws.create_item("Fix login").unwrap();
+        let app = router(tmp.path(), limits, metrics, None);
+        (tmp, app)
+    }
+
+    /// Send `request` from 127.0.0.1, as `serve_on` would.
+    async fn send(app: &Router, mut request: Request<Body>) -> Response {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 40000));
+        request.extensions_mut().insert(ConnectInfo(addr));
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn post(uri: &str, body: &serde_json::Value) -> Request<Body> {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let limits = Limits {
+            requests_per_second: 2,
+            ..Limits::default()
+        };
+        let (_tmp, app) = app(limits, false);
+
+        for _ in 0..2 {
+            assert_eq!(send(&app, get("/health")).await.status(), StatusCode::OK);
+        }
+
+        let response = send(&app, get("/health")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+        assert_eq!(body_json(response).await["code"], "rate_limited");
+    }
+
+    #[tokio::test]
+    async fn test_body_limit() {
+        let limits = Limits {
+            max_body_bytes: 64,
+            ..Limits::default()
+        };
+        let (_tmp, app) = app(limits, false);
+
+        let title = "x".repeat(100);
+        let response = send(&app, post("/items", &serde_json::json!({"title": title}))).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body_json(response).await["code"], "payload_too_large");
+
+        let response = send(&app, post("/items", &serde_json::json!({"title": "Ok"}))).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        let limits = Limits {
+            requests_per_second: 1,
+            ..Limits::default()
+        };
+        let (_tmp, app) = app(limits, true);
+
+        assert_eq!(send(&app, get("/items/fs:fix-login")).await.status(), StatusCode::OK);
+        let response = send(&app, get("/items/fs:missing")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Scrapes aren't rate limited, and are counted like other requests
+        for _ in 0..2 {
+            assert_eq!(send(&app, get("/metrics")).await.status(), StatusCode::OK);
+        }
+        let response = send(&app, get("/metrics")).await;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(r#"worky_http_requests_total{method="GET",route="/items/{uid}"} 1"#));
+        assert!(text.contains(r#"worky_http_requests_total{method="GET",route="/metrics"} 2"#));
+        assert!(!text.contains("worky_http_errors_total{"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_errors() {
+        let (_tmp, app) = app(Limits::default(), true);
+
+        let response = send(&app, get("/items/fs:missing")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_json(response).await["code"], "item_not_found");
+
+        let response = send(&app, get("/metrics")).await;
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(r#"worky_http_errors_total{route="/items/{uid}",status="404"} 1"#));
+        assert!(text.contains(r#"worky_items{state="TODO"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn test_bad_query() {
+        let (_tmp, app) = app(Limits::default(), false);
+
+        let response = send(&app, get("/items/fs:fix-login/events?since=2024-01-01")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "bad_request");
+        assert!(body["error"].as_str().unwrap().contains("since"));
+
+        let uri = "/items/fs:fix-login/events?since=2024-01-01T00:00:00Z";
+        let response = send(&app, get(uri)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_get_item_etag() {
+        let (tmp, app) = app(Limits::default(), false);
+
+        let response = send(&app, get("/items/fs:fix-login")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+        let tag = response.headers()[header::ETAG].clone();
+
+        // The client's copy is current, whether it sends the tag weak or not
+        for sent in [tag.to_str().unwrap().to_string(), format!("W/{}", tag.to_str().unwrap())] {
+            let mut request = get("/items/fs:fix-login");
+            request
+                .headers_mut()
+                .insert(header::IF_NONE_MATCH, sent.parse().unwrap());
+            let response = send(&app, request).await;
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+            assert_eq!(response.headers()[header::ETAG], tag);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert!(body.is_empty());
+        }
+
+        // Once the item changes, the old tag no longer matches
+        let ws = Workspace::open(tmp.path()).unwrap();
+        ws.update_item("fs:fix-login", &[SetOperation::new("priority", "high")])
+            .unwrap();
+        let mut request = get("/items/fs:fix-login");
+        request.headers_mut().insert(header::IF_NONE_MATCH, tag.clone());
+        let response = send(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], tag);
+        assert_eq!(body_json(response).await["priority"], "high");
+    }
+
+    fn set(assignments: &[&str], if_match: Option<&str>) -> Request<Body> {
+        let mut request = post(
+            "/items/fs:fix-login/set",
+            &serde_json::json!({ "assignments": assignments }),
+        );
+        if let Some(tag) = if_match {
+            request
+                .headers_mut()
+                .insert(header::IF_MATCH, tag.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_set_if_match() {
+        let (_tmp, app) = app(Limits::default(), false);
+
+        let response = send(&app, get("/items/fs:fix-login")).await;
+        let tag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        // The update answers with the new version to send next time
+        let response = send(&app, set(&["priority=high"], Some(&tag))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let newer = response.headers()[header::ETAG].to_str().unwrap().to_string();
+        assert_ne!(newer, tag);
+
+        // A stale version is refused with the item as it is now
+        let response = send(&app, set(&["priority=low"], Some(&tag))).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.headers()[header::ETAG], newer.as_str());
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "version_mismatch");
+        assert_eq!(body["item"]["priority"], "high");
+
+        let response = send(&app, set(&["priority=low"], Some("*"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for bad in ["unquoted", "\"a\", \"b\""] {
+            let response = send(&app, set(&["priority=low"], Some(bad))).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        let (_tmp, app) = app(Limits::default(), false);
+
+        // A new ID is assigned and echoed, in the header and error body
+        let response = send(&app, get("/items/fs:missing")).await;
+        let id = response.headers()[&request_id::REQUEST_ID].to_str().unwrap().to_string();
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+        assert_eq!(body_json(response).await["request_id"], id.as_str());
+
+        // The client's own ID is kept, unless it isn't a usable one
+        let with_id = |id: &str| {
+            let mut request = get("/health");
+            request
+                .headers_mut()
+                .insert(&request_id::REQUEST_ID, id.parse().unwrap());
+            request
+        };
+        let response = send(&app, with_id("job-42")).await;
+        assert_eq!(response.headers()[&request_id::REQUEST_ID], "job-42");
+
+        let long = "x".repeat(200);
+        let response = send(&app, with_id(&long)).await;
+        assert_ne!(response.headers()[&request_id::REQUEST_ID], long.as_str());
     }
 }
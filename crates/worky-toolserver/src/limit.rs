@@ -0,0 +1,194 @@
+//! Per-client rate limiting and request size limits.
+
+use crate::AppError;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default requests per second allowed from one client.
+pub const DEFAULT_REQUESTS_PER_SECOND: u32 = 100;
+/// Default largest accepted JSON request body (1 MiB).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Number of tracked clients above which idle ones are forgotten.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Request limits and retention applied by the tool server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Sustained requests per second allowed from one client IP, with bursts
+    /// of up to a second's worth. Zero disables rate limiting.
+    pub requests_per_second: u32,
+    /// Largest accepted request body, in bytes. Artifact uploads have their
+    /// own, larger limit.
+    pub max_body_bytes: usize,
+    /// How long a response is replayed for a repeated `Idempotency-Key`.
+    pub idempotency_window: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            idempotency_window: crate::DEFAULT_IDEMPOTENCY_WINDOW,
+        }
+    }
+}
+
+/// Token buckets keyed by client IP.
+pub struct RateLimiter {
+    rate: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> Self {
+        Self {
+            rate: f64::from(requests_per_second),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `ip`, or return how long until one is available.
+    fn acquire(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            // A client whose bucket has refilled is indistinguishable from a new one
+            let rate = self.rate;
+            buckets.retain(|_, b| {
+                let elapsed = now.duration_since(b.refilled_at).as_secs_f64();
+                elapsed.mul_add(rate, b.tokens) < rate
+            });
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.rate,
+            refilled_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = elapsed.mul_add(self.rate, bucket.tokens).min(self.rate);
+        bucket.refilled_at = now;
+
+        let result = if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        };
+        drop(buckets);
+
+        result
+    }
+}
+
+/// Reject requests beyond the client's rate with 429 Too Many Requests.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.acquire(addr.ip(), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            // Whole seconds, rounded up
+            let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+            let mut response = AppError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                "too many requests; slow down and retry",
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+/// Give oversized-body rejections from extractors the usual JSON error body.
+pub async fn payload_too_large_as_json(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return AppError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            "request body is too large",
+        )
+        .into_response();
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn ip(n: u32) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::from(n))
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let limiter = RateLimiter::new(2);
+        let now = Instant::now();
+
+        // A second's worth of burst, then a wait for the next token
+        assert!(limiter.acquire(ip(1), now).is_ok());
+        assert!(limiter.acquire(ip(1), now).is_ok());
+        assert_eq!(limiter.acquire(ip(1), now), Err(Duration::from_millis(500)));
+
+        // Other clients have their own bucket
+        assert!(limiter.acquire(ip(2), now).is_ok());
+
+        assert!(limiter.acquire(ip(1), now + Duration::from_millis(500)).is_ok());
+        assert!(limiter.acquire(ip(1), now + Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn test_idle_clients_are_pruned() {
+        let limiter = RateLimiter::new(1);
+        let now = Instant::now();
+        let count = u32::try_from(PRUNE_THRESHOLD).unwrap() + 1;
+        for n in 0..count {
+            limiter.acquire(ip(n), now).unwrap();
+        }
+
+        // Buckets still refilling are kept
+        let soon = now + Duration::from_millis(500);
+        assert!(limiter.acquire(ip(count), soon).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().len(), PRUNE_THRESHOLD + 2);
+
+        let later = now + Duration::from_secs(2);
+        assert!(limiter.acquire(ip(count + 1), later).is_ok());
+        let buckets: Vec<IpAddr> = limiter.buckets.lock().unwrap().keys().copied().collect();
+        assert_eq!(buckets, [ip(count + 1)]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Request and workspace metrics in the Prometheus text format, served at
+//! `/metrics` when the server is started with metrics enabled.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use worky_fs::Workspace;
+
+use crate::AppState;
+
+/// Content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Request counters, kept in [`AppState`] for the life of the server.
+#[derive(Default)]
+pub struct Metrics {
+    /// Requests by method and route template.
+    requests: RwLock<BTreeMap<(String, String), AtomicU64>>,
+    /// Responses with a 4xx or 5xx status, by route template and status.
+    errors: RwLock<BTreeMap<(String, u16), AtomicU64>>,
+}
+
+impl Metrics {
+    fn record(&self, method: &str, route: &str, status: u16) {
+        increment(&self.requests, (method.to_string(), route.to_string()));
+        if status >= 400 {
+            increment(&self.errors, (route.to_string(), status));
+        }
+    }
+
+    /// All metrics in the Prometheus text format, with item counts read
+    /// from the workspace at `workspace_path`.
+    fn render(&self, workspace_path: &Path) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP worky_http_requests_total Requests handled, by route.\n");
+        out.push_str("# TYPE worky_http_requests_total counter\n");
+        for ((method, route), count) in &*read(&self.requests) {
+            let _ = writeln!(
+                out,
+                "worky_http_requests_total{{method=\"{}\",route=\"{}\"}} {}",
+                escape(method),
+                escape(route),
+                count.load(Ordering::Relaxed)
+            );
+        }
+
+        out.push_str("# HELP worky_http_errors_total Responses with a 4xx or 5xx status.\n");
+        out.push_str("# TYPE worky_http_errors_total counter\n");
+        for ((route, status), count) in &*read(&self.errors) {
+            let _ = writeln!(
+                out,
+                "worky_http_errors_total{{route=\"{}\",status=\"{status}\"}} {}",
+                escape(route),
+                count.load(Ordering::Relaxed)
+            );
+        }
+
+        // Counted on each scrape, so edits from the CLI show up too
+        let items = Workspace::open(workspace_path).and_then(|ws| ws.list_items(None));
+        if let Ok(items) = items {
+            let mut by_state: BTreeMap<String, usize> = BTreeMap::new();
+            for item in items {
+                *by_state.entry(item.state).or_default() += 1;
+            }
+
+            out.push_str("# HELP worky_items Work items, by state.\n");
+            out.push_str("# TYPE worky_items gauge\n");
+            for (state, count) in by_state {
+                let _ = writeln!(out, "worky_items{{state=\"{}\"}} {count}", escape(&state));
+            }
+        }
+
+        out
+    }
+}
+
+/// Count a request under its route template, e.g. `/items/{uid}`.
+///
+/// Add with `Router::route_layer` so the matched route is known.
+pub async fn track(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |p| p.as_str().to_string());
+
+    let response = next.run(request).await;
+    if let Some(metrics) = &state.metrics {
+        metrics.record(&method, &route, response.status().as_u16());
+    }
+    response
+}
+
+/// Serve the current metrics.
+pub async fn handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let body = state
+        .metrics
+        .as_ref()
+        .map(|m| m.render(&state.workspace_path))
+        .unwrap_or_default();
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], body)
+}
+
+fn increment<K: Ord>(counters: &RwLock<BTreeMap<K, AtomicU64>>, key: K) {
+    if let Some(counter) = read(counters).get(&key) {
+        counter.fetch_add(1, Ordering::Relaxed);
+        return;
+    }
+
+    counters
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .entry(key)
+        .or_default()
+        .fetch_add(1, Ordering::Relaxed);
+}
+
+fn read<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Escape a label value: backslash, double quote, and newline.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render() {
+        let tmp = TempDir::new().unwrap();
+        let ws = Workspace::init(tmp.path()).unwrap();
+        ws.create_item("Fix login").unwrap();
+
+        let metrics = Metrics::default();
+        metrics.record("GET", "/items/{uid}", 200);
+        metrics.record("GET", "/items/{uid}", 404);
+        metrics.record("POST", "/items", 201);
+
+        let text = metrics.render(tmp.path());
+        let lines: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            lines,
+            [
+                r#"worky_http_requests_total{method="GET",route="/items/{uid}"} 2"#,
+                r#"worky_http_requests_total{method="POST",route="/items"} 1"#,
+                r#"worky_http_errors_total{route="/items/{uid}",status="404"} 1"#,
+                r#"worky_items{state="TODO"} 1"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(r#"a\b"c"#), r#"a\\b\"c"#);
+        assert_eq!(escape("two\nlines"), r"two\nlines");
+    }
+}
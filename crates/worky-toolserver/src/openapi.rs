@@ -0,0 +1,337 @@
+//! Machine-readable description of the HTTP API, served at `/openapi.json`
+//! as an `OpenAPI` 3.1 document.
+//!
+//! Schemas are generated from the request and response types the handlers
+//! use, so they follow changes to those types. Adding a route still means
+//! adding it to [`document`]; a test fails for any route left out.
+
+use crate::{
+    AddEventRequest, AuditQuery, CreateItemRequest, ErrorResponse, EventsQuery, FeedEntry,
+    FeedQuery, HealthResponse, ReadyResponse, SearchResponse, SetFieldsRequest, WorkloadQuery,
+};
+use schemars::{generate::SchemaSettings, JsonSchema, SchemaGenerator};
+use serde_json::{json, Value};
+use worky_core::{WorkEvent, WorkItem};
+use worky_fs::{workspace::FilterQuery, Artifact};
+
+/// Build the `OpenAPI` document for the tool server.
+// `{uid}` in the paths is a path template, not a format argument
+#[allow(clippy::too_many_lines, clippy::literal_string_with_formatting_args)]
+pub fn document() -> Value {
+    let mut spec = Spec::new();
+
+    let uid = json!({
+        "name": "uid",
+        "in": "path",
+        "required": true,
+        "description": "Item UID, e.g. `fs:fix-login`",
+        "schema": { "type": "string" }
+    });
+
+    let idempotency_key = json!({
+        "name": "Idempotency-Key",
+        "in": "header",
+        "required": false,
+        "description": "Retrying with the same key and body replays the first response \
+            (marked `Idempotent-Replayed: true`) instead of applying the change again",
+        "schema": { "type": "string", "maxLength": 255 }
+    });
+
+    let mut events_parameters = vec![uid.clone()];
+    events_parameters.extend(spec.query::<EventsQuery>());
+
+    let paths = json!({
+        "/health": {
+            "get": {
+                "summary": "Liveness check; always succeeds while the server runs",
+                "responses": spec.responses::<HealthResponse>("200"),
+            }
+        },
+        "/ready": {
+            "get": {
+                "summary": "Readiness check; 503 if the workspace can't be read",
+                "responses": {
+                    "200": {
+                        "description": "Workspace is readable",
+                        "content": json_content(&spec.schema::<ReadyResponse>()),
+                    },
+                    "503": {
+                        "description": "Workspace is missing or unreadable",
+                        "content": json_content(&spec.schema::<ReadyResponse>()),
+                    },
+                },
+            }
+        },
+        "/openapi.json": {
+            "get": {
+                "summary": "This document",
+                "responses": {
+                    "200": {
+                        "description": "OpenAPI document",
+                        "content": json_content(&json!({ "type": "object" })),
+                    }
+                },
+            }
+        },
+        "/metrics": {
+            "get": {
+                "summary": "Prometheus metrics; served only with `--metrics`",
+                "responses": {
+                    "200": {
+                        "description": "Metrics in the Prometheus text format",
+                        "content": { "text/plain": { "schema": { "type": "string" } } },
+                    }
+                },
+            }
+        },
+        "/search": {
+            "post": {
+                "summary": "Search items",
+                "requestBody": spec.body::<FilterQuery>(),
+                "responses": spec.responses::<SearchResponse>("200"),
+            }
+        },
+        "/events": {
+            "get": {
+                "summary": "Recent events across all items, newest first",
+                "parameters": spec.query::<FeedQuery>(),
+                "responses": spec.responses::<Vec<FeedEntry>>("200"),
+            }
+        },
+        "/audit": {
+            "get": {
+                "summary": "The workspace audit log, oldest first (empty unless audit.enabled)",
+                "parameters": spec.query::<AuditQuery>(),
+                "responses": spec.responses::<Vec<FeedEntry>>("200"),
+            }
+        },
+        "/calendar.ics": {
+            "get": {
+                "summary": "Items with a due date (`fields.due`) as an iCalendar feed",
+                "responses": {
+                    "200": {
+                        "description": "iCalendar feed",
+                        "content": { "text/calendar": { "schema": { "type": "string" } } },
+                    }
+                },
+            }
+        },
+        "/items": {
+            "get": {
+                "summary": "An assignee's open items, highest priority and earliest due first",
+                "parameters": spec.query::<WorkloadQuery>(),
+                "responses": spec.responses::<SearchResponse>("200"),
+            },
+            "post": {
+                "summary": "Create an item",
+                "parameters": [idempotency_key],
+                "requestBody": spec.body::<CreateItemRequest>(),
+                "responses": spec.responses::<WorkItem>("201"),
+            }
+        },
+        "/items/{uid}": {
+            "get": {
+                "summary": "Get an item",
+                "parameters": [uid, {
+                    "name": "If-None-Match",
+                    "in": "header",
+                    "required": false,
+                    "description": "`ETag` from an earlier response; 304 if the item is unchanged",
+                    "schema": { "type": "string" }
+                }],
+                "responses": not_modified(spec.responses::<WorkItem>("200")),
+            }
+        },
+        "/items/{uid}/set": {
+            "post": {
+                "summary": "Set fields",
+                "parameters": [uid, idempotency_key, {
+                    "name": "If-Match",
+                    "in": "header",
+                    "required": false,
+                    "description": "`ETag` from `GET /items/{uid}`; 409 `version_mismatch`, with \
+                        the current item, if the item has changed since",
+                    "schema": { "type": "string" }
+                }],
+                "requestBody": spec.body::<SetFieldsRequest>(),
+                "responses": spec.responses::<WorkItem>("200"),
+            }
+        },
+        "/items/{uid}/events": {
+            "get": {
+                "summary": "List an item's events",
+                "parameters": events_parameters,
+                "responses": spec.responses::<Vec<WorkEvent>>("200"),
+            },
+            "post": {
+                "summary": "Add a comment event",
+                "parameters": [uid, idempotency_key],
+                "requestBody": spec.body::<AddEventRequest>(),
+                "responses": spec.responses::<Value>("200"),
+            }
+        },
+        "/items/{uid}/artifacts": {
+            "post": {
+                "summary": "Upload files as artifacts",
+                "parameters": [uid, idempotency_key],
+                "requestBody": {
+                    "required": true,
+                    "content": {
+                        "multipart/form-data": {
+                            "schema": {
+                                "type": "object",
+                                "additionalProperties": { "type": "string", "format": "binary" }
+                            }
+                        }
+                    }
+                },
+                "responses": spec.responses::<Vec<Artifact>>("201"),
+            }
+        },
+    });
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "worky tool server",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": spec.generator.take_definitions(true),
+        },
+    })
+}
+
+/// Collects component schemas while the paths are built.
+struct Spec {
+    generator: SchemaGenerator,
+}
+
+impl Spec {
+    fn new() -> Self {
+        let settings = SchemaSettings::draft2020_12().with(|s| {
+            s.definitions_path = "/components/schemas".into();
+            s.meta_schema = None;
+        });
+
+        Self {
+            generator: settings.into_generator(),
+        }
+    }
+
+    /// A schema for `T`, referencing named types in the components.
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    /// A required JSON request body of type `T`.
+    fn body<T: JsonSchema>(&mut self) -> Value {
+        json!({
+            "required": true,
+            "content": json_content(&self.schema::<T>()),
+        })
+    }
+
+    /// A success response of type `T`, plus the shared error response.
+    fn responses<T: JsonSchema>(&mut self, status: &str) -> Value {
+        json!({
+            status: {
+                "description": "Success",
+                "content": json_content(&self.schema::<T>()),
+            },
+            "default": {
+                "description": "Error with a stable `code`",
+                "content": json_content(&self.schema::<ErrorResponse>()),
+            },
+        })
+    }
+
+    /// Query parameters for each property of `T`.
+    fn query<T: JsonSchema>(&mut self) -> Vec<Value> {
+        let schema = T::json_schema(&mut self.generator);
+        let required = schema.get("required").and_then(Value::as_array);
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+
+        properties
+            .iter()
+            .map(|(name, property)| {
+                let mut property = property.clone();
+                let description = property
+                    .as_object_mut()
+                    .and_then(|p| p.remove("description"));
+
+                let mut parameter = json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.is_some_and(|r| r.contains(&json!(name))),
+                    "schema": property,
+                });
+                if let Some(description) = description {
+                    parameter["description"] = description;
+                }
+                parameter
+            })
+            .collect()
+    }
+}
+
+fn json_content(schema: &Value) -> Value {
+    json!({ "application/json": { "schema": schema } })
+}
+
+/// `responses` plus the 304 of a conditional GET.
+fn not_modified(mut responses: Value) -> Value {
+    responses["304"] = json!({ "description": "Unchanged since the `If-None-Match` ETag" });
+    responses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Every `(method, path)` registered in [`crate::router`], read from its source.
+    fn routes() -> BTreeSet<(String, String)> {
+        let source = include_str!("lib.rs");
+        let start = source.find("\nfn router(").unwrap();
+        let end = start + source[start..].find("\n}\n").unwrap();
+
+        source[start..end]
+            .split(".route(")
+            .skip(1)
+            .map(|call| {
+                let mut parts = call.splitn(3, '"');
+                let path = parts.nth(1).unwrap();
+                let rest = parts.next().unwrap().trim_start_matches([',', ' ', '\n']);
+                let method = &rest[..rest.find('(').unwrap()];
+                (method.to_string(), path.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_openapi_covers_routes() {
+        let routes = routes();
+        assert!(routes.contains(&("post".into(), "/items/{uid}/artifacts".into())));
+        assert!(routes.contains(&("get".into(), "/metrics".into())));
+
+        let document = document();
+        let documented: BTreeSet<(String, String)> = document["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, operations)| {
+                operations
+                    .as_object()
+                    .unwrap()
+                    .keys()
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect();
+
+        assert_eq!(routes, documented);
+    }
+}
@@ -0,0 +1,80 @@
+//! Correlation IDs for requests via the `X-Request-Id` header.
+//!
+//! Each request keeps the ID its client sent, or gets a new one. The ID is
+//! recorded on the request's tracing span, so log lines from a change can
+//! be traced back to the call that made it, and is echoed in the response
+//! header and in error bodies.
+
+use axum::{
+    extract::Request,
+    http::{header::HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Duration;
+use tracing::{debug, Span};
+use uuid::Uuid;
+
+/// Request and response header carrying the ID.
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied ID kept; longer ones are replaced.
+const MAX_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    /// ID of the request being handled, for error bodies.
+    static CURRENT: String;
+}
+
+/// Keep or assign the request's ID, and echo it on the response.
+///
+/// Add outside the `TraceLayer` so its span sees the ID.
+pub async fn propagate(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| is_valid(id))
+        .map_or_else(|| Uuid::new_v4().to_string(), String::from);
+
+    // Only visible ASCII gets here, so this can't fail
+    let value = HeaderValue::from_str(&id).expect("request ID is a valid header value");
+    request.headers_mut().insert(REQUEST_ID, value.clone());
+
+    let mut response = CURRENT.scope(id, next.run(request)).await;
+    response.headers_mut().insert(REQUEST_ID, value);
+    response
+}
+
+/// The ID of the request being handled, if any.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(Clone::clone).ok()
+}
+
+/// The span each request is handled in, tagged with its ID.
+pub fn make_span(request: &Request) -> Span {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %id,
+    )
+}
+
+/// Log each response's status and how long it took.
+pub fn on_response(response: &Response, latency: Duration, _span: &Span) {
+    debug!(
+        status = response.status().as_u16(),
+        latency = ?latency,
+        "Finished request"
+    );
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}